@@ -27,6 +27,8 @@
 extern "C" {
 #endif // __cplusplus
 
+// Generated by `libcommons::ffi::header`, do not edit.
+
 /// UTF-8 owned string.
 ///
 /// Libcommons strings are not null terminated.
@@ -45,6 +47,219 @@ typedef struct LIBCOMMONS_PREFIXED(ffi_str_p) {
     uintptr_t len;
 } LIBCOMMONS_PREFIXED(ffi_str_p);
 
+/// Wide pointer to an array.
+///
+/// `buf` points to `len` elements of the same type, or is `NULL` if
+/// `len` is 0.
+typedef struct LIBCOMMONS_PREFIXED(ffi_slice_p) {
+    void *buf;
+    uintptr_t len;
+} LIBCOMMONS_PREFIXED(ffi_slice_p);
+
+/// Owned growable array.
+///
+/// `buf` points to `len` elements of the same type.
+typedef struct LIBCOMMONS_PREFIXED(ffi_vec_t) {
+    void *buf;
+    uintptr_t len;
+    uintptr_t capacity;
+    void (*drop)(struct LIBCOMMONS_PREFIXED(ffi_vec_t) *);
+} LIBCOMMONS_PREFIXED(ffi_vec_t);
+
+/// Owned byte buffer.
+///
+/// Unlike `ffi_string_t`, contents don't have to be valid UTF-8.
+typedef struct LIBCOMMONS_PREFIXED(ffi_bytes_t) {
+    uint8_t *buf;
+    uintptr_t len;
+    uintptr_t capacity;
+    void (*drop)(struct LIBCOMMONS_PREFIXED(ffi_bytes_t) *);
+} LIBCOMMONS_PREFIXED(ffi_bytes_t);
+
+/// Wide pointer to a UTF-16 string slice.
+///
+/// Contents don't have to be valid UTF-16, and are not null terminated.
+typedef struct LIBCOMMONS_PREFIXED(ffi_wide_str_p) {
+    const uint16_t *buf;
+    uintptr_t len;
+} LIBCOMMONS_PREFIXED(ffi_wide_str_p);
+
+/// UTF-16 owned string.
+///
+/// Contents don't have to be valid UTF-16, and are not null terminated.
+typedef struct LIBCOMMONS_PREFIXED(ffi_wide_string_t) {
+    uint16_t *buf;
+    uintptr_t len;
+    uintptr_t capacity;
+    void (*drop)(struct LIBCOMMONS_PREFIXED(ffi_wide_string_t) *);
+} LIBCOMMONS_PREFIXED(ffi_wide_string_t);
+
+/// Key-value pair of `ffi_map_t`.
+typedef struct LIBCOMMONS_PREFIXED(ffi_map_entry_t) {
+    LIBCOMMONS_PREFIXED(ffi_string_t) key;
+    LIBCOMMONS_PREFIXED(ffi_string_t) value;
+} LIBCOMMONS_PREFIXED(ffi_map_entry_t);
+
+/// List of string key-value pairs.
+///
+/// Keys are unique. `drop` frees the entries and the buffer.
+typedef struct LIBCOMMONS_PREFIXED(ffi_map_t) {
+    LIBCOMMONS_PREFIXED(ffi_map_entry_t) *buf;
+    uintptr_t len;
+    uintptr_t capacity;
+    void (*drop)(struct LIBCOMMONS_PREFIXED(ffi_map_t) *);
+} LIBCOMMONS_PREFIXED(ffi_map_t);
+
+/// Owned opaque value.
+///
+/// `ptr` can only be passed back to functions of the library that
+/// created it. `drop` destroys the value.
+typedef struct LIBCOMMONS_PREFIXED(ffi_box_t) {
+    void *ptr;
+    void (*drop)(void *);
+} LIBCOMMONS_PREFIXED(ffi_box_t);
+
+/// Shared immutable value.
+///
+/// `clone` adds an owner, `release` removes one.
+typedef struct LIBCOMMONS_PREFIXED(ffi_arc_t) {
+    const void *ptr;
+    void (*clone)(const struct LIBCOMMONS_PREFIXED(ffi_arc_t) *);
+    void (*release)(struct LIBCOMMONS_PREFIXED(ffi_arc_t) *);
+} LIBCOMMONS_PREFIXED(ffi_arc_t);
+
+/// Shared immutable UTF-8 string.
+///
+/// `clone` adds an owner, `release` removes one.
+///
+/// Libcommons strings are not null terminated.
+typedef struct LIBCOMMONS_PREFIXED(ffi_arc_str_t) {
+    const char *buf;
+    uintptr_t len;
+    void (*clone)(const struct LIBCOMMONS_PREFIXED(ffi_arc_str_t) *);
+    void (*release)(struct LIBCOMMONS_PREFIXED(ffi_arc_str_t) *);
+} LIBCOMMONS_PREFIXED(ffi_arc_str_t);
+
+/// Span of time.
+///
+/// `nanos` is always less than a second.
+typedef struct LIBCOMMONS_PREFIXED(ffi_duration_t) {
+    uint64_t secs;
+    uint32_t nanos;
+} LIBCOMMONS_PREFIXED(ffi_duration_t);
+
+/// Point in time, relative to the Unix epoch.
+///
+/// `secs` is negative for points before the epoch, and `nanos` always
+/// counts forward from `secs`. `nanos` is always less than a second.
+typedef struct LIBCOMMONS_PREFIXED(ffi_timestamp_t) {
+    int64_t secs;
+    uint32_t nanos;
+} LIBCOMMONS_PREFIXED(ffi_timestamp_t);
+
+/// Callback returning `RET` and taking `ARGS`, a parenthesized parameter
+/// list starting with the context pointer.
+///
+/// `call` must be called with `context` as the first argument. `drop`
+/// destroys the context, if set.
+///
+/// Each use declares a new type, so `typedef` it once:
+/// `typedef LIBCOMMONS_FFI_CALLBACK(int, (void *, int)) int_callback_t;`
+#define LIBCOMMONS_FFI_CALLBACK(RET, ARGS)                                     \
+    struct {                                                                   \
+        RET(*call) ARGS;                                                       \
+        void *context;                                                         \
+        void (*drop)(void *);                                                  \
+    }
+
+/// Free a callback declared with `LIBCOMMONS_FFI_CALLBACK`.
+///
+/// Will call the internal `drop` method and set all values to 0.
+#define LIBCOMMONS_FFI_CALLBACK_FREE(CALLBACK)                                 \
+    do {                                                                       \
+        if ((CALLBACK)->drop != NULL)                                          \
+            (CALLBACK)->drop((CALLBACK)->context);                             \
+        memset((CALLBACK), 0, sizeof(*(CALLBACK)));                            \
+    } while (0)
+
+/// Optional value of type `T`.
+///
+/// `value` is only initialized if `is_some` is not 0.
+///
+/// Each use declares a new type, so `typedef` it once:
+/// `typedef LIBCOMMONS_FFI_OPTION(int) option_int_t;`
+#define LIBCOMMONS_FFI_OPTION(T)                                               \
+    struct {                                                                   \
+        uint8_t is_some;                                                       \
+        T value;                                                               \
+    }
+
+/// Either a value of type `T` or an error of type `E`.
+///
+/// `ok` is initialized if `is_err` is 0, `err` if it's 1.
+///
+/// Each use declares a new type, so `typedef` it once:
+/// `typedef LIBCOMMONS_FFI_RESULT(int, int) result_int_t;`
+#define LIBCOMMONS_FFI_RESULT(T, E)                                            \
+    struct {                                                                   \
+        uint8_t is_err;                                                        \
+        union {                                                                \
+            T ok;                                                              \
+            E err;                                                             \
+        };                                                                     \
+    }
+
+/// ABI version of this header.
+///
+/// Pass to `libcommons_abi_check` of a loaded library before exchanging
+/// any other values with it.
+#define LIBCOMMONS_ABI_VERSION 1
+
+/// Panics in Rust functions are caught instead of aborting.
+#define LIBCOMMONS_CAPABILITY_UNWIND 1
+
+/// Features of the libcommons build a library is using.
+///
+/// Fields are only ever appended, so fields past `size` must not be read.
+/// `flags` is a set of `LIBCOMMONS_CAPABILITY_*` flags.
+typedef struct LIBCOMMONS_PREFIXED(abi_capabilities_t) {
+    uintptr_t size;
+    uint32_t abi_version;
+    uint32_t flags;
+    LIBCOMMONS_PREFIXED(ffi_str_p) version;
+} LIBCOMMONS_PREFIXED(abi_capabilities_t);
+
+// Provided by Rust libraries
+
+/// Get the ABI version of this library.
+uint32_t libcommons_abi_version(void);
+
+/// Check if a library built against ABI `version` is compatible with
+/// this one.
+bool libcommons_abi_check(uint32_t version);
+
+/// Get the capabilities of this library.
+const LIBCOMMONS_PREFIXED(abi_capabilities_t) *libcommons_abi_capabilities(void);
+
+/// Check if there's an error stored for this thread.
+bool libcommons_has_last_error(void);
+
+/// Get the message of the error stored for this thread.
+///
+/// The string is empty if there is none, and is only valid until the
+/// error is replaced or cleared.
+LIBCOMMONS_PREFIXED(ffi_str_p) libcommons_last_error(void);
+
+/// Take the message of the error stored for this thread.
+///
+/// The string is empty if there is none.
+LIBCOMMONS_PREFIXED(ffi_string_t) libcommons_take_last_error(void);
+
+/// Clear the error stored for this thread.
+void libcommons_clear_last_error(void);
+
+// End of generated declarations.
+
 // ffi_string_t
 
 /// Allocate a new FFI string via a C string.
@@ -104,6 +319,111 @@ LIBCOMMONS_PREFIXED(ffi_str_p)
 LIBCOMMONS_PREFIXED(ffi_str_substr)(LIBCOMMONS_PREFIXED(ffi_str_p) str,
                                     uintptr_t start, uintptr_t len);
 
+// ffi_vec_t
+
+/// Allocate a new FFI vector by copying `len` elements of `size` bytes.
+///
+/// If vector is empty, no allocation is performed. Returns an empty vector
+/// if `len * size` overflows or allocation fails.
+LIBCOMMONS_PREFIXED(ffi_vec_t)
+LIBCOMMONS_PREFIXED(ffi_vec_new)(void *buf, uintptr_t len, uintptr_t size);
+
+/// Convert a vector to a slice.
+LIBCOMMONS_PREFIXED(ffi_slice_p)
+LIBCOMMONS_PREFIXED(ffi_vec_slice)(LIBCOMMONS_PREFIXED(ffi_vec_t) *);
+
+/// Free an FFI vector.
+///
+/// Will call the internal `drop` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_vec_free)(LIBCOMMONS_PREFIXED(ffi_vec_t) *);
+
+// ffi_bytes_t
+
+/// Allocate a new FFI byte buffer by copying `len` bytes.
+///
+/// If buffer is empty, no allocation is performed. Returns an empty buffer
+/// if allocation fails.
+LIBCOMMONS_PREFIXED(ffi_bytes_t)
+LIBCOMMONS_PREFIXED(ffi_bytes_new)(const uint8_t *buf, uintptr_t len);
+
+/// Convert a byte buffer to a slice.
+LIBCOMMONS_PREFIXED(ffi_slice_p)
+LIBCOMMONS_PREFIXED(ffi_bytes_slice)(LIBCOMMONS_PREFIXED(ffi_bytes_t) *);
+
+/// Free an FFI byte buffer.
+///
+/// Will call the internal `drop` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_bytes_free)(LIBCOMMONS_PREFIXED(ffi_bytes_t) *);
+
+// ffi_wide_string_t
+
+/// Allocate a new FFI UTF-16 string by copying `len` code units.
+///
+/// If string is empty, no allocation is performed. Returns an empty string
+/// if `len` code units don't fit in memory or allocation fails.
+LIBCOMMONS_PREFIXED(ffi_wide_string_t)
+LIBCOMMONS_PREFIXED(ffi_wide_string_new)(const uint16_t *buf, uintptr_t len);
+
+/// Convert a UTF-16 string to a string slice.
+LIBCOMMONS_PREFIXED(ffi_wide_str_p)
+LIBCOMMONS_PREFIXED(ffi_wide_string_slice)(
+    LIBCOMMONS_PREFIXED(ffi_wide_string_t) *);
+
+/// Free an FFI UTF-16 string.
+///
+/// Will call the internal `drop` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_wide_string_free)(
+    LIBCOMMONS_PREFIXED(ffi_wide_string_t) *);
+
+// ffi_map_t
+
+/// Get the value of a key, or `NULL` if map doesn't contain it.
+const LIBCOMMONS_PREFIXED(ffi_string_t) *
+LIBCOMMONS_PREFIXED(ffi_map_get)(const LIBCOMMONS_PREFIXED(ffi_map_t) *,
+                                 LIBCOMMONS_PREFIXED(ffi_str_p) key);
+
+/// Free an FFI map.
+///
+/// Will call the internal `drop` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_map_free)(LIBCOMMONS_PREFIXED(ffi_map_t) *);
+
+// ffi_box_t
+
+/// Free an opaque value.
+///
+/// Will call the internal `drop` method and set all values to 0, so
+/// freeing twice is harmless.
+void LIBCOMMONS_PREFIXED(ffi_box_free)(LIBCOMMONS_PREFIXED(ffi_box_t) *);
+
+// ffi_arc_t
+
+/// Add an owner to a shared value.
+LIBCOMMONS_PREFIXED(ffi_arc_t)
+LIBCOMMONS_PREFIXED(ffi_arc_clone)(const LIBCOMMONS_PREFIXED(ffi_arc_t) *);
+
+/// Remove an owner from a shared value.
+///
+/// Will call the internal `release` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_arc_release)(LIBCOMMONS_PREFIXED(ffi_arc_t) *);
+
+// ffi_arc_str_t
+
+/// Add an owner to a shared string.
+LIBCOMMONS_PREFIXED(ffi_arc_str_t)
+LIBCOMMONS_PREFIXED(ffi_arc_str_clone)(
+    const LIBCOMMONS_PREFIXED(ffi_arc_str_t) *);
+
+/// Convert a shared string to a string slice.
+LIBCOMMONS_PREFIXED(ffi_str_p)
+LIBCOMMONS_PREFIXED(ffi_arc_str_slice)(
+    const LIBCOMMONS_PREFIXED(ffi_arc_str_t) *);
+
+/// Remove an owner from a shared string.
+///
+/// Will call the internal `release` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_arc_str_release)(
+    LIBCOMMONS_PREFIXED(ffi_arc_str_t) *);
+
 #ifdef LIBCOMMONS_IMPLEMENTATION
 
 void __libcommons_internal_string_t_drop(LIBCOMMONS_PREFIXED(ffi_string_t) *
@@ -328,6 +648,264 @@ LIBCOMMONS_PREFIXED(ffi_str_substr)(LIBCOMMONS_PREFIXED(ffi_str_p) str,
     return value;
 }
 
+// ffi_vec_t
+
+void __libcommons_internal_vec_t_drop(LIBCOMMONS_PREFIXED(ffi_vec_t) * self) {
+    if (self->buf != NULL)
+        LIBCOMMONS_DEALLOCATOR(self->buf);
+}
+
+/// Allocate a new FFI vector by copying `len` elements of `size` bytes.
+///
+/// If vector is empty, no allocation is performed. Returns an empty vector
+/// if `len * size` overflows or allocation fails.
+LIBCOMMONS_PREFIXED(ffi_vec_t)
+LIBCOMMONS_PREFIXED(ffi_vec_new)(void *buf, uintptr_t len, uintptr_t size) {
+    LIBCOMMONS_PREFIXED(ffi_vec_t)
+    value = {
+        .buf = NULL,
+        .len = 0,
+        .capacity = 0,
+        .drop = NULL,
+    };
+    if (len == 0 || size == 0 || len > UINTPTR_MAX / size)
+        return value;
+
+    void *newbuf = LIBCOMMONS_ALLOCATOR(len * size);
+    if (newbuf == NULL)
+        return value;
+    memcpy(newbuf, buf, len * size);
+    value.buf = newbuf;
+    value.len = len;
+    value.capacity = len;
+    value.drop = &__libcommons_internal_vec_t_drop;
+    return value;
+}
+
+/// Convert a vector to a slice.
+LIBCOMMONS_PREFIXED(ffi_slice_p)
+LIBCOMMONS_PREFIXED(ffi_vec_slice)(LIBCOMMONS_PREFIXED(ffi_vec_t) * vec) {
+    LIBCOMMONS_PREFIXED(ffi_slice_p)
+    value = {
+        .buf = vec->buf,
+        .len = vec->len,
+    };
+    return value;
+}
+
+/// Free an FFI vector.
+///
+/// Will call the internal `drop` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_vec_free)(LIBCOMMONS_PREFIXED(ffi_vec_t) * self) {
+    if (self->drop != NULL)
+        self->drop(self);
+    memset(self, 0, sizeof(LIBCOMMONS_PREFIXED(ffi_vec_t)));
+}
+
+// ffi_bytes_t
+
+void __libcommons_internal_bytes_t_drop(LIBCOMMONS_PREFIXED(ffi_bytes_t) *
+                                        self) {
+    if (self->buf != NULL)
+        LIBCOMMONS_DEALLOCATOR(self->buf);
+}
+
+/// Allocate a new FFI byte buffer by copying `len` bytes.
+///
+/// If buffer is empty, no allocation is performed. Returns an empty buffer
+/// if allocation fails.
+LIBCOMMONS_PREFIXED(ffi_bytes_t)
+LIBCOMMONS_PREFIXED(ffi_bytes_new)(const uint8_t *buf, uintptr_t len) {
+    LIBCOMMONS_PREFIXED(ffi_bytes_t)
+    value = {
+        .buf = NULL,
+        .len = 0,
+        .capacity = 0,
+        .drop = NULL,
+    };
+    if (len == 0)
+        return value;
+
+    uint8_t *newbuf = (uint8_t *)LIBCOMMONS_ALLOCATOR(len);
+    if (newbuf == NULL)
+        return value;
+    memcpy(newbuf, buf, len);
+    value.buf = newbuf;
+    value.len = len;
+    value.capacity = len;
+    value.drop = &__libcommons_internal_bytes_t_drop;
+    return value;
+}
+
+/// Convert a byte buffer to a slice.
+LIBCOMMONS_PREFIXED(ffi_slice_p)
+LIBCOMMONS_PREFIXED(ffi_bytes_slice)(LIBCOMMONS_PREFIXED(ffi_bytes_t) * bytes) {
+    LIBCOMMONS_PREFIXED(ffi_slice_p)
+    value = {
+        .buf = bytes->buf,
+        .len = bytes->len,
+    };
+    return value;
+}
+
+/// Free an FFI byte buffer.
+///
+/// Will call the internal `drop` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_bytes_free)(LIBCOMMONS_PREFIXED(ffi_bytes_t) *
+                                         self) {
+    if (self->drop != NULL)
+        self->drop(self);
+    memset(self, 0, sizeof(LIBCOMMONS_PREFIXED(ffi_bytes_t)));
+}
+
+// ffi_wide_string_t
+
+void __libcommons_internal_wide_string_t_drop(
+    LIBCOMMONS_PREFIXED(ffi_wide_string_t) * self) {
+    if (self->buf != NULL)
+        LIBCOMMONS_DEALLOCATOR(self->buf);
+}
+
+/// Allocate a new FFI UTF-16 string by copying `len` code units.
+///
+/// If string is empty, no allocation is performed. Returns an empty string
+/// if `len` code units don't fit in memory or allocation fails.
+LIBCOMMONS_PREFIXED(ffi_wide_string_t)
+LIBCOMMONS_PREFIXED(ffi_wide_string_new)(const uint16_t *buf, uintptr_t len) {
+    LIBCOMMONS_PREFIXED(ffi_wide_string_t)
+    value = {
+        .buf = NULL,
+        .len = 0,
+        .capacity = 0,
+        .drop = NULL,
+    };
+    if (len == 0 || len > UINTPTR_MAX / sizeof(uint16_t))
+        return value;
+
+    uint16_t *newbuf =
+        (uint16_t *)LIBCOMMONS_ALLOCATOR(len * sizeof(uint16_t));
+    if (newbuf == NULL)
+        return value;
+    memcpy(newbuf, buf, len * sizeof(uint16_t));
+    value.buf = newbuf;
+    value.len = len;
+    value.capacity = len;
+    value.drop = &__libcommons_internal_wide_string_t_drop;
+    return value;
+}
+
+/// Convert a UTF-16 string to a string slice.
+LIBCOMMONS_PREFIXED(ffi_wide_str_p)
+LIBCOMMONS_PREFIXED(ffi_wide_string_slice)(
+    LIBCOMMONS_PREFIXED(ffi_wide_string_t) * str) {
+    LIBCOMMONS_PREFIXED(ffi_wide_str_p)
+    value = {
+        .buf = str->buf,
+        .len = str->len,
+    };
+    return value;
+}
+
+/// Free an FFI UTF-16 string.
+///
+/// Will call the internal `drop` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_wide_string_free)(
+    LIBCOMMONS_PREFIXED(ffi_wide_string_t) * self) {
+    if (self->drop != NULL)
+        self->drop(self);
+    memset(self, 0, sizeof(LIBCOMMONS_PREFIXED(ffi_wide_string_t)));
+}
+
+// ffi_map_t
+
+/// Get the value of a key, or `NULL` if map doesn't contain it.
+const LIBCOMMONS_PREFIXED(ffi_string_t) *
+LIBCOMMONS_PREFIXED(ffi_map_get)(const LIBCOMMONS_PREFIXED(ffi_map_t) * map,
+                                 LIBCOMMONS_PREFIXED(ffi_str_p) key) {
+    for (uintptr_t i = 0; i < map->len; i++) {
+        LIBCOMMONS_PREFIXED(ffi_str_p)
+        entry = LIBCOMMONS_PREFIXED(ffi_string_slice)(&map->buf[i].key);
+        if (LIBCOMMONS_PREFIXED(ffi_str_eq)(entry, key))
+            return &map->buf[i].value;
+    }
+    return NULL;
+}
+
+/// Free an FFI map.
+///
+/// Will call the internal `drop` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_map_free)(LIBCOMMONS_PREFIXED(ffi_map_t) * self) {
+    if (self->drop != NULL)
+        self->drop(self);
+    memset(self, 0, sizeof(LIBCOMMONS_PREFIXED(ffi_map_t)));
+}
+
+// ffi_box_t
+
+/// Free an opaque value.
+///
+/// Will call the internal `drop` method and set all values to 0, so
+/// freeing twice is harmless.
+void LIBCOMMONS_PREFIXED(ffi_box_free)(LIBCOMMONS_PREFIXED(ffi_box_t) * self) {
+    if (self->ptr != NULL && self->drop != NULL)
+        self->drop(self->ptr);
+    memset(self, 0, sizeof(LIBCOMMONS_PREFIXED(ffi_box_t)));
+}
+
+// ffi_arc_t
+
+/// Add an owner to a shared value.
+LIBCOMMONS_PREFIXED(ffi_arc_t)
+LIBCOMMONS_PREFIXED(ffi_arc_clone)(const LIBCOMMONS_PREFIXED(ffi_arc_t) *
+                                   self) {
+    if (self->clone != NULL)
+        self->clone(self);
+    return *self;
+}
+
+/// Remove an owner from a shared value.
+///
+/// Will call the internal `release` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_arc_release)(LIBCOMMONS_PREFIXED(ffi_arc_t) *
+                                          self) {
+    if (self->release != NULL)
+        self->release(self);
+    memset(self, 0, sizeof(LIBCOMMONS_PREFIXED(ffi_arc_t)));
+}
+
+// ffi_arc_str_t
+
+/// Add an owner to a shared string.
+LIBCOMMONS_PREFIXED(ffi_arc_str_t)
+LIBCOMMONS_PREFIXED(ffi_arc_str_clone)(
+    const LIBCOMMONS_PREFIXED(ffi_arc_str_t) * self) {
+    if (self->clone != NULL)
+        self->clone(self);
+    return *self;
+}
+
+/// Convert a shared string to a string slice.
+LIBCOMMONS_PREFIXED(ffi_str_p)
+LIBCOMMONS_PREFIXED(ffi_arc_str_slice)(
+    const LIBCOMMONS_PREFIXED(ffi_arc_str_t) * self) {
+    LIBCOMMONS_PREFIXED(ffi_str_p)
+    value = {
+        .buf = (char *)self->buf,
+        .len = self->len,
+    };
+    return value;
+}
+
+/// Remove an owner from a shared string.
+///
+/// Will call the internal `release` method and set all values to 0.
+void LIBCOMMONS_PREFIXED(ffi_arc_str_release)(
+    LIBCOMMONS_PREFIXED(ffi_arc_str_t) * self) {
+    if (self->release != NULL)
+        self->release(self);
+    memset(self, 0, sizeof(LIBCOMMONS_PREFIXED(ffi_arc_str_t)));
+}
+
 #endif // LIBCOMMONS_IMPLEMENTATION
 
 #ifdef __cplusplus
@@ -1,2 +1,35 @@
 #[cfg(feature = "nightly")]
+pub mod abi;
+#[cfg(feature = "nightly")]
+pub mod arc;
+#[cfg(feature = "nightly")]
+pub mod boxed;
+#[cfg(feature = "nightly")]
+pub mod bytes;
+#[cfg(feature = "nightly")]
+pub mod callback;
+#[cfg(feature = "nightly")]
+pub mod error;
+#[cfg(feature = "nightly")]
+mod guard;
+#[cfg(feature = "nightly")]
+pub mod header;
+#[cfg(feature = "nightly")]
+pub mod map;
+#[cfg(feature = "nightly")]
+pub mod option;
+#[cfg(feature = "nightly")]
+pub mod result;
+#[cfg(feature = "nightly")]
+pub mod slice;
+#[cfg(feature = "nightly")]
 pub mod str;
+#[cfg(feature = "nightly")]
+pub mod time;
+#[cfg(feature = "nightly")]
+pub mod vec;
+#[cfg(feature = "nightly")]
+pub mod wide;
+
+#[cfg(feature = "nightly")]
+pub use guard::{PanicValue, guard};
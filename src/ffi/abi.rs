@@ -0,0 +1,94 @@
+//! ABI version negotiation.
+//!
+//! Libraries loaded at runtime may be built against a different version
+//! of libcommons. Since FFI types are passed by layout, mixing
+//! incompatible versions corrupts memory instead of failing to link.
+//! Compare versions with [check] before exchanging any other values.
+//!
+//! ```
+//! use libcommons::ffi::abi::{self, ABI_VERSION};
+//!
+//! assert!(abi::check(ABI_VERSION));
+//! assert!(!abi::check(ABI_VERSION + 1));
+//!
+//! let capabilities = abi::capabilities();
+//! assert_eq!(capabilities.abi_version, ABI_VERSION);
+//! assert_eq!(capabilities.version.as_str(), env!("CARGO_PKG_VERSION"));
+//! ```
+
+use std::mem::size_of;
+
+use super::str::{FfiStr, FfiStrPtr};
+
+/// Version of the layout of FFI types and exported functions.
+///
+/// Changed whenever an existing type or function changes incompatibly.
+/// Additions are advertised through [Capabilities] instead.
+pub const ABI_VERSION: u32 = 1;
+
+/// Panics in functions wrapped in [guard](super::guard) are caught.
+///
+/// Not set if the library is built with `panic = "abort"`.
+pub const CAPABILITY_UNWIND: u32 = 1 << 0;
+
+/// Features of the libcommons build a library is using.
+///
+/// See `libcommons.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Capabilities {
+    /// Size of this struct in bytes.
+    ///
+    /// Fields are only ever appended, so fields past `size` must not be
+    /// read.
+    pub size: usize,
+    /// [ABI_VERSION] of the library.
+    pub abi_version: u32,
+    /// Set of `CAPABILITY_*` flags.
+    pub flags: u32,
+    /// Crate version of libcommons.
+    pub version: FfiStrPtr<'static>,
+}
+
+static CAPABILITIES: Capabilities = Capabilities {
+    size: size_of::<Capabilities>(),
+    abi_version: ABI_VERSION,
+    flags: if cfg!(panic = "unwind") {
+        CAPABILITY_UNWIND
+    } else {
+        0
+    },
+    version: FfiStr::from_str(env!("CARGO_PKG_VERSION")).as_ptr(),
+};
+
+unsafe impl Sync for Capabilities {}
+
+/// Check if a library built against ABI `version` is compatible with
+/// this one.
+pub const fn check(version: u32) -> bool {
+    version == ABI_VERSION
+}
+
+/// Get the capabilities of this build.
+pub fn capabilities() -> &'static Capabilities {
+    &CAPABILITIES
+}
+
+/// Get the ABI version of this library.
+#[unsafe(no_mangle)]
+pub extern "C" fn libcommons_abi_version() -> u32 {
+    ABI_VERSION
+}
+
+/// Check if a library built against ABI `version` is compatible with
+/// this one.
+#[unsafe(no_mangle)]
+pub extern "C" fn libcommons_abi_check(version: u32) -> bool {
+    check(version)
+}
+
+/// Get the capabilities of this library.
+#[unsafe(no_mangle)]
+pub extern "C" fn libcommons_abi_capabilities() -> &'static Capabilities {
+    capabilities()
+}
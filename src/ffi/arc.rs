@@ -0,0 +1,259 @@
+use std::{
+    borrow::Borrow,
+    fmt::{Debug, Display},
+    ops::Deref,
+    ptr,
+    sync::Arc,
+};
+
+use super::str::{FfiStr, FfiStrPtr};
+
+unsafe extern "C" fn __libcommons_rust_arc_clone<T>(arc: *const FfiArc<T>) {
+    unsafe { Arc::increment_strong_count((*arc).ptr) }
+}
+
+unsafe extern "C" fn __libcommons_rust_arc_release<T>(arc: *mut FfiArc<T>) {
+    unsafe { Arc::decrement_strong_count((*arc).ptr) }
+}
+
+unsafe extern "C" fn __libcommons_rust_arc_str_clone(arc: *const FfiArcStr) {
+    unsafe { Arc::increment_strong_count((*arc).as_str_ptr()) }
+}
+
+unsafe extern "C" fn __libcommons_rust_arc_str_release(arc: *mut FfiArcStr) {
+    unsafe { Arc::decrement_strong_count((*arc).as_str_ptr()) }
+}
+
+/// An FFI-compatible reference-counted pointer to immutable data.
+///
+/// Both sides share ownership through the `clone` and `release`
+/// functions stored alongside the pointer. Cloning and releasing from
+/// different threads is fine for values created by Rust; functions
+/// provided by C must be thread-safe too.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::arc::FfiArc;
+///
+/// let config = FfiArc::new(vec![1, 2, 3]);
+/// let copy = config.clone();
+/// assert!(FfiArc::ptr_eq(&config, &copy));
+/// assert_eq!(*copy, [1, 2, 3]);
+/// ```
+#[repr(C)]
+pub struct FfiArc<T> {
+    pub(super) ptr: *const T,
+    pub(super) clone: Option<unsafe extern "C" fn(*const FfiArc<T>)>,
+    pub(super) release: Option<unsafe extern "C" fn(*mut FfiArc<T>)>,
+}
+impl<T> FfiArc<T> {
+    /// Move a value into a new reference-counted allocation.
+    pub fn new(value: T) -> Self {
+        Arc::new(value).into()
+    }
+
+    /// Check if both pointers point to the same value.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ptr::eq(this.ptr, other.ptr)
+    }
+
+    /// Get the pointer to the value.
+    pub fn as_ptr(this: &Self) -> *const T {
+        this.ptr
+    }
+}
+impl<T> Drop for FfiArc<T> {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self) }
+        }
+    }
+}
+impl<T> Clone for FfiArc<T> {
+    fn clone(&self) -> Self {
+        if let Some(clone) = self.clone {
+            unsafe { clone(self) }
+        }
+        Self {
+            ptr: self.ptr,
+            clone: self.clone,
+            release: self.release,
+        }
+    }
+}
+impl<T> Deref for FfiArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.ptr }
+    }
+}
+impl<T> AsRef<T> for FfiArc<T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+impl<T> Borrow<T> for FfiArc<T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+impl<T: Debug> Debug for FfiArc<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+impl<T: Display> Display for FfiArc<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+impl<T: PartialEq> PartialEq for FfiArc<T> {
+    fn eq(&self, other: &Self) -> bool {
+        T::eq(self, other)
+    }
+}
+impl<T: Eq> Eq for FfiArc<T> {}
+impl<T> From<Arc<T>> for FfiArc<T> {
+    fn from(value: Arc<T>) -> Self {
+        Self {
+            ptr: Arc::into_raw(value),
+            clone: Some(__libcommons_rust_arc_clone::<T>),
+            release: Some(__libcommons_rust_arc_release::<T>),
+        }
+    }
+}
+unsafe impl<T: Send + Sync> Send for FfiArc<T> {}
+unsafe impl<T: Send + Sync> Sync for FfiArc<T> {}
+
+/// An FFI-compatible reference-counted string.
+///
+/// Like [FfiArc], but for string slices.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::arc::FfiArcStr;
+///
+/// let config = FfiArcStr::from("verbose = true");
+/// let copies: Vec<_> = (0..3).map(|_| config.clone()).collect();
+/// assert!(copies.iter().all(|x| x.as_str().as_ptr() == config.as_str().as_ptr()));
+/// assert_eq!(copies[0], "verbose = true");
+/// ```
+#[repr(C)]
+pub struct FfiArcStr {
+    pub(super) buf: *const u8,
+    pub(super) len: usize,
+    pub(super) clone: Option<unsafe extern "C" fn(*const FfiArcStr)>,
+    pub(super) release: Option<unsafe extern "C" fn(*mut FfiArcStr)>,
+}
+impl FfiArcStr {
+    /// Get a string reference.
+    pub const fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(std::slice::from_raw_parts(self.buf, self.len)) }
+    }
+
+    /// Get an FFI string reference.
+    pub const fn as_ffi_str(&self) -> &FfiStr {
+        FfiStr::from_str(self.as_str())
+    }
+
+    /// Make this string passable via ffi.
+    pub const fn as_ptr(&self) -> FfiStrPtr<'_> {
+        self.as_ffi_str().as_ptr()
+    }
+
+    /// Get the length of this string in bytes.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if string is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn as_str_ptr(&self) -> *const str {
+        ptr::slice_from_raw_parts(self.buf, self.len) as *const str
+    }
+}
+impl Drop for FfiArcStr {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self) }
+        }
+    }
+}
+impl Clone for FfiArcStr {
+    fn clone(&self) -> Self {
+        if let Some(clone) = self.clone {
+            unsafe { clone(self) }
+        }
+        Self {
+            buf: self.buf,
+            len: self.len,
+            clone: self.clone,
+            release: self.release,
+        }
+    }
+}
+impl AsRef<str> for FfiArcStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl AsRef<FfiStr> for FfiArcStr {
+    fn as_ref(&self) -> &FfiStr {
+        self.as_ffi_str()
+    }
+}
+impl Borrow<str> for FfiArcStr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+impl Debug for FfiArcStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+impl Display for FfiArcStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+impl PartialEq for FfiArcStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+impl Eq for FfiArcStr {}
+impl<'a> PartialEq<&'a str> for FfiArcStr {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+impl From<Arc<str>> for FfiArcStr {
+    fn from(value: Arc<str>) -> Self {
+        let len = value.len();
+        Self {
+            buf: Arc::into_raw(value) as *const u8,
+            len,
+            clone: Some(__libcommons_rust_arc_str_clone),
+            release: Some(__libcommons_rust_arc_str_release),
+        }
+    }
+}
+impl<'a> From<&'a str> for FfiArcStr {
+    fn from(value: &'a str) -> Self {
+        Arc::<str>::from(value).into()
+    }
+}
+impl From<String> for FfiArcStr {
+    fn from(value: String) -> Self {
+        Arc::<str>::from(value).into()
+    }
+}
+unsafe impl Send for FfiArcStr {}
+unsafe impl Sync for FfiArcStr {}
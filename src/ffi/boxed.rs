@@ -0,0 +1,247 @@
+use std::{
+    fmt::{Debug, Display},
+    mem::{ManuallyDrop, replace},
+    ops::{Deref, DerefMut},
+    ptr::null_mut,
+};
+
+unsafe extern "C" fn __libcommons_rust_drop_box<T>(ptr: *mut T) {
+    unsafe {
+        drop(Box::from_raw(ptr));
+    }
+}
+
+/// An FFI-compatible owned pointer to an opaque value.
+///
+/// C only sees the pointer and the `drop` function that destroys the
+/// value, so it can't do anything with the value other than pass it
+/// back or free it.
+///
+/// See `libcommons.h` and [ffi_handle](crate::ffi_handle).
+///
+/// ```
+/// use libcommons::ffi::boxed::FfiBox;
+///
+/// let mut value = FfiBox::new(vec![1, 2]);
+/// value.push(3);
+///
+/// let ptr = FfiBox::into_raw(value);
+/// let value = unsafe { FfiBox::from_raw(ptr) };
+/// assert_eq!(unsafe { FfiBox::into_inner(value) }, [1, 2, 3]);
+/// ```
+#[repr(C)]
+pub struct FfiBox<T> {
+    pub(super) ptr: *mut T,
+    pub(super) drop: Option<unsafe extern "C" fn(*mut T)>,
+}
+impl<T> FfiBox<T> {
+    /// Move a value to the heap.
+    pub fn new(value: T) -> Self {
+        Box::new(value).into()
+    }
+
+    /// Get the pointer to the value, leaking it.
+    ///
+    /// Use [FfiBox::from_raw] to get it back.
+    pub fn into_raw(this: Self) -> *mut T {
+        ManuallyDrop::new(this).ptr
+    }
+
+    /// Get back an [FfiBox] leaked with [FfiBox::into_raw].
+    ///
+    /// ## Safety
+    /// Provided pointer must have been allocated by Rust, i.e. returned by
+    /// [Box::into_raw] or by [FfiBox::into_raw] of a box created in Rust,
+    /// and not be used afterwards.
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        Self {
+            ptr,
+            drop: Some(__libcommons_rust_drop_box::<T>),
+        }
+    }
+
+    /// Convert this [FfiBox] into a [Box].
+    ///
+    /// ## Safety
+    /// The value must have been allocated by Rust, like with
+    /// [FfiBox::new], rather than by C with its own allocator and `drop`.
+    ///
+    /// ## Panics
+    /// Panics if the box has been freed.
+    pub unsafe fn into_box(this: Self) -> Box<T> {
+        assert!(!this.ptr.is_null(), "FfiBox used after being freed");
+        unsafe { Box::from_raw(Self::into_raw(this)) }
+    }
+
+    /// Get the value back.
+    ///
+    /// ## Safety
+    /// See [FfiBox::into_box].
+    ///
+    /// ## Panics
+    /// Panics if the box has been freed.
+    pub unsafe fn into_inner(this: Self) -> T {
+        unsafe { *Self::into_box(this) }
+    }
+
+    /// Free a handle in place, leaving it null.
+    ///
+    /// Used by [ffi_handle](crate::ffi_handle).
+    ///
+    /// ## Safety
+    /// Provided pointer must be null or point to a valid [FfiBox], which
+    /// may have already been freed by this function or by C.
+    #[doc(hidden)]
+    pub unsafe fn __free(handle: *mut Self) {
+        unsafe {
+            if let Some(handle) = handle.as_mut() {
+                handle.free();
+            }
+        }
+    }
+
+    fn free(&mut self) {
+        let ptr = replace(&mut self.ptr, null_mut());
+        if !ptr.is_null()
+            && let Some(drop) = self.drop.take()
+        {
+            unsafe { drop(ptr) }
+        }
+    }
+}
+impl<T> Drop for FfiBox<T> {
+    fn drop(&mut self) {
+        self.free();
+    }
+}
+impl<T> Deref for FfiBox<T> {
+    type Target = T;
+
+    /// ## Panics
+    /// Panics if the box has been freed.
+    fn deref(&self) -> &Self::Target {
+        assert!(!self.ptr.is_null(), "FfiBox used after being freed");
+        unsafe { &*self.ptr }
+    }
+}
+impl<T> DerefMut for FfiBox<T> {
+    /// ## Panics
+    /// Panics if the box has been freed.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        assert!(!self.ptr.is_null(), "FfiBox used after being freed");
+        unsafe { &mut *self.ptr }
+    }
+}
+impl<T> AsRef<T> for FfiBox<T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+impl<T> AsMut<T> for FfiBox<T> {
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+impl<T: Clone> Clone for FfiBox<T> {
+    fn clone(&self) -> Self {
+        Self::new(T::clone(self))
+    }
+}
+impl<T: Default> Default for FfiBox<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+impl<T: Debug> Debug for FfiBox<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+impl<T: Display> Display for FfiBox<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+impl<T: PartialEq> PartialEq for FfiBox<T> {
+    fn eq(&self, other: &Self) -> bool {
+        T::eq(self, other)
+    }
+}
+impl<T: Eq> Eq for FfiBox<T> {}
+impl<T> From<Box<T>> for FfiBox<T> {
+    fn from(value: Box<T>) -> Self {
+        unsafe { Self::from_raw(Box::into_raw(value)) }
+    }
+}
+
+/// Generate extern functions for passing a Rust type to C as an opaque
+/// [FfiBox](crate::ffi::boxed::FfiBox) handle.
+///
+/// `free` takes a pointer to the handle, drops the value and leaves the
+/// handle null, so freeing twice is harmless. Optional `clone` takes a
+/// pointer to the handle and returns a new one.
+///
+/// ```
+/// use libcommons::ffi::boxed::FfiBox;
+///
+/// #[derive(Clone)]
+/// pub struct Counter(u32);
+///
+/// libcommons::ffi_handle!(Counter {
+///     free: counter_free,
+///     clone: counter_clone,
+/// });
+///
+/// #[unsafe(no_mangle)]
+/// pub extern "C" fn counter_new() -> FfiBox<Counter> {
+///     FfiBox::new(Counter(0))
+/// }
+///
+/// let mut counter = counter_new();
+/// counter.0 += 1;
+/// let mut copy = unsafe { counter_clone(&counter) };
+/// unsafe {
+///     counter_free(&mut counter);
+///     counter_free(&mut counter);
+/// }
+/// assert_eq!(copy.0, 1);
+/// unsafe { counter_free(&mut copy) };
+/// ```
+#[macro_export]
+macro_rules! ffi_handle {
+    ($ty:ty { free: $free:ident $(, clone: $clone:ident)? $(,)? }) => {
+        /// Free a handle, leaving it null.
+        ///
+        /// ## Safety
+        /// Handle must be null, valid or already freed.
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $free(handle: *mut $crate::ffi::boxed::FfiBox<$ty>) {
+            unsafe { $crate::ffi::boxed::FfiBox::__free(handle) }
+        }
+        $(
+            /// Clone a handle.
+            ///
+            /// ## Safety
+            /// Handle must be valid.
+            #[unsafe(no_mangle)]
+            pub unsafe extern "C" fn $clone(
+                handle: *const $crate::ffi::boxed::FfiBox<$ty>,
+            ) -> $crate::ffi::boxed::FfiBox<$ty> {
+                unsafe { ::core::clone::Clone::clone(&*handle) }
+            }
+        )?
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::FfiBox;
+
+    #[test]
+    #[should_panic = "FfiBox used after being freed"]
+    fn deref_freed() {
+        let mut value = FfiBox::new(1);
+        unsafe { FfiBox::__free(&mut value) };
+        let _ = *value;
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    fmt::Debug,
+    io,
+    mem::{forget, transmute},
+};
+
+use super::{
+    slice::{FfiSlice, FfiSliceMut},
+    str::FfiString,
+    vec::FfiVec,
+};
+
+/// An FFI-compatible owned byte buffer.
+///
+/// Unlike [FfiString], contents don't have to be valid UTF-8.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::bytes::FfiBytes;
+///
+/// let mut bytes = FfiBytes::from(&[0xde, 0xad][..]);
+/// bytes.extend_from_slice(&[0xbe, 0xef]);
+/// assert_eq!(bytes.as_slice(), &[0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(bytes.into_vec(), vec![0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[repr(transparent)]
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct FfiBytes(pub(super) FfiVec<u8>);
+impl FfiBytes {
+    /// Create a new FfiBytes.
+    ///
+    /// This method will not allocate.
+    pub const fn new() -> Self {
+        Self(FfiVec::new())
+    }
+
+    /// Create a new FfiBytes with specified capacity.
+    ///
+    /// This method will not allocate if `len` is 0.
+    pub fn with_capacity(len: usize) -> Self {
+        Self(FfiVec::with_capacity(len))
+    }
+
+    /// Get the length of this buffer in bytes.
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if buffer is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Obtain this buffer's capacity.
+    pub const fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Get a slice of the bytes.
+    pub const fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    /// Get a mutable slice of the bytes.
+    pub const fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+
+    /// Get a wide pointer to the bytes.
+    pub const fn as_ffi_slice(&self) -> FfiSlice<'_, u8> {
+        self.0.as_ffi_slice()
+    }
+
+    /// Get a mutable wide pointer to the bytes.
+    pub const fn as_ffi_slice_mut(&mut self) -> FfiSliceMut<'_, u8> {
+        self.0.as_ffi_slice_mut()
+    }
+
+    /// Append a byte.
+    pub fn push(&mut self, byte: u8) {
+        self.0.push(byte);
+    }
+
+    /// Append bytes from a slice.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.0.extend(bytes);
+    }
+
+    /// Convert this [FfiBytes] into a [Vec].
+    ///
+    /// Bytes are copied into a new buffer, since this one may have been
+    /// allocated outside of Rust.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0.into_vec()
+    }
+}
+impl Debug for FfiBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+impl PartialEq<[u8]> for FfiBytes {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+impl PartialEq<&[u8]> for FfiBytes {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.as_slice() == *other
+    }
+}
+impl io::Write for FfiBytes {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+impl From<Vec<u8>> for FfiBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value.into())
+    }
+}
+impl From<&[u8]> for FfiBytes {
+    fn from(value: &[u8]) -> Self {
+        Self(value.into())
+    }
+}
+impl From<FfiVec<u8>> for FfiBytes {
+    fn from(value: FfiVec<u8>) -> Self {
+        Self(value)
+    }
+}
+impl From<FfiString> for FfiBytes {
+    /// Reuses the buffer of the string.
+    fn from(value: FfiString) -> Self {
+        // Both types have the same layout.
+        let drop: Option<unsafe extern "C" fn(*mut FfiVec<u8>)> = unsafe { transmute(value.drop) };
+        let bytes = Self(FfiVec {
+            buf: value.buf,
+            len: value.len,
+            capacity: value.capacity,
+            drop,
+        });
+        forget(value);
+        bytes
+    }
+}
+impl From<FfiBytes> for Vec<u8> {
+    fn from(value: FfiBytes) -> Self {
+        value.into_vec()
+    }
+}
+impl From<FfiBytes> for FfiVec<u8> {
+    fn from(value: FfiBytes) -> Self {
+        value.0
+    }
+}
+impl FromIterator<u8> for FfiBytes {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        Self(FfiVec::from_iter(iter))
+    }
+}
+impl Extend<u8> for FfiBytes {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+impl<'a> Extend<&'a u8> for FfiBytes {
+    fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+impl AsRef<[u8]> for FfiBytes {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+impl AsMut<[u8]> for FfiBytes {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+impl Borrow<[u8]> for FfiBytes {
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+impl BorrowMut<[u8]> for FfiBytes {
+    fn borrow_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
@@ -0,0 +1,126 @@
+use std::{ffi::c_void, fmt::Debug, ptr::null_mut};
+
+unsafe extern "C" fn __libcommons_rust_drop_context<F>(context: *mut c_void) {
+    unsafe {
+        drop(Box::from_raw(context.cast::<F>()));
+    }
+}
+
+/// Arguments of an [FfiCallback].
+///
+/// Implemented for tuples of up to 6 elements.
+pub trait CallbackArgs<Ret> {
+    /// Function taking the context pointer followed by the arguments.
+    type Fn: Copy;
+}
+
+/// An FFI-compatible callback.
+///
+/// Consists of a function, a context pointer passed to it as the first
+/// argument and an optional function destroying the context. Closures
+/// are boxed into the context. Argument types are given as a tuple.
+///
+/// Panics in Rust closures abort the process, since they can't unwind
+/// through C.
+///
+/// See `LIBCOMMONS_FFI_CALLBACK` in `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::callback::FfiCallback;
+///
+/// let mut total = 0;
+/// let mut add = FfiCallback::<(i32, i32), i32>::new(move |x, y| {
+///     total += x + y;
+///     total
+/// });
+/// assert_eq!(add.call(1, 2), 3);
+/// assert_eq!(add.call(3, 4), 10);
+/// ```
+#[repr(C)]
+pub struct FfiCallback<Args: CallbackArgs<Ret>, Ret> {
+    call: Args::Fn,
+    context: *mut c_void,
+    drop: Option<unsafe extern "C" fn(*mut c_void)>,
+}
+impl<Args: CallbackArgs<Ret>, Ret> FfiCallback<Args, Ret> {
+    /// Create an [FfiCallback] from its parts.
+    ///
+    /// `drop` is called with `context` when the callback is dropped.
+    ///
+    /// ## Safety
+    /// `call` must be safe to call with `context` for as long as the
+    /// callback exists, and `drop` must be safe to call with `context`
+    /// once.
+    pub const unsafe fn from_raw_parts(
+        call: Args::Fn,
+        context: *mut c_void,
+        drop: Option<unsafe extern "C" fn(*mut c_void)>,
+    ) -> Self {
+        Self {
+            call,
+            context,
+            drop,
+        }
+    }
+
+    /// Get the context pointer.
+    pub const fn context(&self) -> *mut c_void {
+        self.context
+    }
+}
+impl<Args: CallbackArgs<Ret>, Ret> Drop for FfiCallback<Args, Ret> {
+    fn drop(&mut self) {
+        if let Some(drop) = self.drop {
+            unsafe { drop(self.context) }
+        }
+        self.context = null_mut();
+    }
+}
+impl<Args: CallbackArgs<Ret>, Ret> Debug for FfiCallback<Args, Ret> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FfiCallback")
+            .field("context", &self.context)
+            .finish_non_exhaustive()
+    }
+}
+
+macro_rules! impl_callback {
+    ($($arg:ident),*) => {
+        impl<Ret, $($arg),*> CallbackArgs<Ret> for ($($arg,)*) {
+            type Fn = unsafe extern "C" fn(*mut c_void $(, $arg)*) -> Ret;
+        }
+        impl<Ret, $($arg),*> FfiCallback<($($arg,)*), Ret> {
+            /// Create a callback from a closure.
+            pub fn new<F: FnMut($($arg),*) -> Ret + 'static>(f: F) -> Self {
+                #[allow(non_snake_case)]
+                unsafe extern "C" fn call<F: FnMut($($arg),*) -> Ret, Ret, $($arg),*>(
+                    context: *mut c_void,
+                    $($arg: $arg),*
+                ) -> Ret {
+                    unsafe { (*context.cast::<F>())($($arg),*) }
+                }
+
+                unsafe {
+                    Self::from_raw_parts(
+                        call::<F, Ret, $($arg),*>,
+                        Box::into_raw(Box::new(f)).cast(),
+                        Some(__libcommons_rust_drop_context::<F>),
+                    )
+                }
+            }
+
+            /// Call the callback.
+            #[allow(non_snake_case)]
+            pub fn call(&mut self, $($arg: $arg),*) -> Ret {
+                unsafe { (self.call)(self.context $(, $arg)*) }
+            }
+        }
+    };
+}
+impl_callback!();
+impl_callback!(A);
+impl_callback!(A, B);
+impl_callback!(A, B, C);
+impl_callback!(A, B, C, D);
+impl_callback!(A, B, C, D, E);
+impl_callback!(A, B, C, D, E, G);
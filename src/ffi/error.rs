@@ -0,0 +1,97 @@
+//! Per-thread last error.
+//!
+//! Functions exported to C report failures through return values, and
+//! store the error here so C can get its message afterwards.
+//!
+//! ```
+//! use libcommons::ffi::error::{last_error_message, set_last_error, take_last_error};
+//!
+//! set_last_error("file not found");
+//! assert_eq!(last_error_message().unwrap(), "file not found");
+//!
+//! let error = take_last_error().unwrap();
+//! assert_eq!(error.to_string(), "file not found");
+//! assert!(take_last_error().is_none());
+//! assert!(last_error_message().is_none());
+//! ```
+
+use std::{cell::RefCell, error::Error, fmt::Write};
+
+use super::str::{FfiStr, FfiStrPtr, FfiString};
+
+struct LastError {
+    error: Option<Box<dyn Error + Send + Sync>>,
+    /// Message of `error`, kept for C.
+    message: FfiString,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<LastError> = const {
+        RefCell::new(LastError {
+            error: None,
+            message: FfiString::new(),
+        })
+    };
+}
+
+/// Store an error for this thread, replacing the previous one.
+pub fn set_last_error(error: impl Into<Box<dyn Error + Send + Sync>>) {
+    let error = error.into();
+    LAST_ERROR.with_borrow_mut(|x| {
+        x.message.clear();
+        let _ = write!(x.message, "{error}");
+        x.error = Some(error);
+    });
+}
+
+/// Take the error stored for this thread.
+pub fn take_last_error() -> Option<Box<dyn Error + Send + Sync>> {
+    LAST_ERROR.with_borrow_mut(|x| {
+        x.message.clear();
+        x.error.take()
+    })
+}
+
+/// Copy the message of the error stored for this thread.
+pub fn last_error_message() -> Option<String> {
+    LAST_ERROR.with_borrow(|x| x.error.as_ref().map(|_| x.message.as_str().to_owned()))
+}
+
+/// Check if there's an error stored for this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn libcommons_has_last_error() -> bool {
+    LAST_ERROR.with_borrow(|x| x.error.is_some())
+}
+
+/// Get the message of the error stored for this thread.
+///
+/// The string is empty if there is none. Use [last_error_message] from
+/// Rust.
+///
+/// ## Safety
+/// The returned string must not be used after the error is replaced,
+/// taken or cleared on this thread, or after the thread exits.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn libcommons_last_error() -> FfiStrPtr<'static> {
+    LAST_ERROR.with_borrow(|x| {
+        let bytes = x.message.as_bytes();
+        unsafe { FfiStr::from_raw_parts(bytes.as_ptr(), bytes.len()) }.as_ptr()
+    })
+}
+
+/// Take the message of the error stored for this thread.
+///
+/// The string is empty if there is none.
+#[unsafe(no_mangle)]
+pub extern "C" fn libcommons_take_last_error() -> FfiString {
+    LAST_ERROR.with_borrow_mut(|x| {
+        x.error = None;
+        std::mem::take(&mut x.message)
+    })
+}
+
+/// Clear the error stored for this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn libcommons_clear_last_error() {
+    take_last_error();
+}
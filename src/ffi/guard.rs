@@ -0,0 +1,139 @@
+use std::{
+    any::Any,
+    panic::{AssertUnwindSafe, catch_unwind},
+    ptr::{null, null_mut},
+};
+
+use super::{error::set_last_error, option::FfiOption, result::FfiResult};
+
+/// Value returned by [guard] when the function panics.
+pub trait PanicValue {
+    fn panic_value() -> Self;
+}
+impl PanicValue for () {
+    fn panic_value() -> Self {}
+}
+impl PanicValue for bool {
+    /// `false`.
+    fn panic_value() -> Self {
+        false
+    }
+}
+macro_rules! impl_panic_value {
+    ($value:expr => $($ty:ty),*) => {$(
+        impl PanicValue for $ty {
+            #[doc = concat!("`", stringify!($value), "`.")]
+            fn panic_value() -> Self {
+                $value
+            }
+        }
+    )*};
+}
+impl_panic_value!(-1 => i8, i16, i32, i64, i128, isize);
+impl_panic_value!(Self::MAX => u8, u16, u32, u64, u128, usize);
+impl_panic_value!(Self::NAN => f32, f64);
+impl<T> PanicValue for *const T {
+    /// Null pointer.
+    fn panic_value() -> Self {
+        null()
+    }
+}
+impl<T> PanicValue for *mut T {
+    /// Null pointer.
+    fn panic_value() -> Self {
+        null_mut()
+    }
+}
+impl<T> PanicValue for Option<T> {
+    fn panic_value() -> Self {
+        None
+    }
+}
+impl<T> PanicValue for FfiOption<T> {
+    fn panic_value() -> Self {
+        FfiOption::None
+    }
+}
+impl<T, E: PanicValue> PanicValue for FfiResult<T, E> {
+    /// Error with the panic value of `E`.
+    fn panic_value() -> Self {
+        FfiResult::Err(E::panic_value())
+    }
+}
+
+/// Run a function, catching panics.
+///
+/// If the function panics, the message is stored as the
+/// [last error](super::error) and [PanicValue::panic_value] is returned.
+/// Use this in every `extern "C"` function, since unwinding into C is
+/// undefined behavior. See also [ffi_export](crate::ffi_export).
+///
+/// The function is assumed to be unwind safe, since nothing can observe
+/// its state after a panic other than through the returned error.
+///
+/// ```
+/// use libcommons::ffi::{error::take_last_error, guard};
+///
+/// let value: i32 = guard(|| 2 + 2);
+/// assert_eq!(value, 4);
+///
+/// let value: i32 = guard(|| panic!("oh no"));
+/// assert_eq!(value, -1);
+/// assert_eq!(take_last_error().unwrap().to_string(), "oh no");
+/// ```
+pub fn guard<T: PanicValue>(f: impl FnOnce() -> T) -> T {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(x) => x,
+        Err(why) => {
+            set_last_error(panic_message(&*why));
+            T::panic_value()
+        }
+    }
+}
+
+/// Get the message of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(x) = payload.downcast_ref::<&str>() {
+        x
+    } else if let Some(x) = payload.downcast_ref::<String>() {
+        x
+    } else {
+        "panic"
+    }
+}
+
+/// Define an `extern "C"` function with its body wrapped in [guard].
+///
+/// [guard]: crate::ffi::guard
+///
+/// ```
+/// use libcommons::ffi::error::last_error_message;
+///
+/// libcommons::ffi_export! {
+///     /// Divide two numbers.
+///     pub fn checked_div(a: i32, b: i32) -> i32 {
+///         a / b
+///     }
+/// }
+///
+/// assert_eq!(checked_div(6, 3), 2);
+/// assert_eq!(checked_div(1, 0), -1);
+/// assert_eq!(last_error_message().unwrap(), "attempt to divide by zero");
+/// ```
+#[macro_export]
+macro_rules! ffi_export {
+    ($(#[$meta:meta])* $vis:vis fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)? $body:block) => {
+        $(#[$meta])*
+        #[unsafe(no_mangle)]
+        $vis extern "C" fn $name($($arg: $ty),*) $(-> $ret)? {
+            $crate::ffi::guard(move || $body)
+        }
+    };
+    ($(#[$meta:meta])* $vis:vis unsafe fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)? $body:block) => {
+        $(#[$meta])*
+        #[unsafe(no_mangle)]
+        $vis unsafe extern "C" fn $name($($arg: $ty),*) $(-> $ret)? {
+            $crate::ffi::guard(move || $body)
+        }
+    };
+}
@@ -0,0 +1,491 @@
+//! C header generation.
+//!
+//! Struct layouts are written from the `#[repr(C)]` types, so the header
+//! can't silently drift from them: renaming or reordering fields breaks
+//! generation. `include/libcommons.h` embeds [declarations] and is
+//! checked against them in tests.
+//!
+//! Use [generate] in a build script of a library exporting functions
+//! that take or return libcommons types:
+//!
+//! ```
+//! let mut header = Vec::new();
+//! libcommons::ffi::header::generate(&mut header).unwrap();
+//!
+//! let header = String::from_utf8(header).unwrap();
+//! assert!(header.contains("typedef struct LIBCOMMONS_PREFIXED(ffi_string_t) {"));
+//! ```
+
+use std::{
+    io::{self, Write},
+    mem::offset_of,
+};
+
+use super::{
+    abi::{ABI_VERSION, CAPABILITY_UNWIND, Capabilities},
+    arc::{FfiArc, FfiArcStr},
+    boxed::FfiBox,
+    bytes::FfiBytes,
+    map::{FfiMap, FfiMapEntry},
+    slice::FfiSlice,
+    str::{FfiStrPtr, FfiString},
+    time::{FfiDuration, FfiTimestamp},
+    vec::FfiVec,
+    wide::{FfiWideStr, FfiWideString},
+};
+
+/// First line of [declarations] in `libcommons.h`.
+pub const BEGIN: &str = "// Generated by `libcommons::ffi::header`, do not edit.";
+/// Last line of [declarations] in `libcommons.h`.
+pub const END: &str = "// End of generated declarations.";
+
+/// Write a standalone C header with [declarations].
+///
+/// Unlike `libcommons.h`, it doesn't include C implementations of
+/// helper functions.
+pub fn generate(out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "#ifndef LIBCOMMONS_H")?;
+    writeln!(
+        out,
+        "#define LIBCOMMONS_H \"{}\"",
+        env!("CARGO_PKG_VERSION")
+    )?;
+    writeln!(out)?;
+    writeln!(out, "#ifndef LIBCOMMONS_PREFIX")?;
+    writeln!(out, "#define LIBCOMMONS_PREFIX libcommons_")?;
+    writeln!(out, "#endif // LIBCOMMONS_PREFIX")?;
+    writeln!(out)?;
+    writeln!(out, "#define __LIBCOMMONS_PREFIXED(A, B) A##B")?;
+    writeln!(
+        out,
+        "#define _LIBCOMMONS_PREFIXED(A, B) __LIBCOMMONS_PREFIXED(A, B)"
+    )?;
+    writeln!(
+        out,
+        "#define LIBCOMMONS_PREFIXED(A) _LIBCOMMONS_PREFIXED(LIBCOMMONS_PREFIX, A)"
+    )?;
+    writeln!(out)?;
+    writeln!(out, "#include <stdbool.h>")?;
+    writeln!(out, "#include <stddef.h>")?;
+    writeln!(out, "#include <stdint.h>")?;
+    writeln!(out, "#include <string.h>")?;
+    writeln!(out)?;
+    writeln!(out, "#ifdef __cplusplus")?;
+    writeln!(out, "extern \"C\" {{")?;
+    writeln!(out, "#endif // __cplusplus")?;
+    writeln!(out)?;
+    declarations(out)?;
+    writeln!(out)?;
+    writeln!(out, "#ifdef __cplusplus")?;
+    writeln!(out, "}}")?;
+    writeln!(out, "#endif // __cplusplus")?;
+    writeln!(out)?;
+    writeln!(out, "#endif // LIBCOMMONS_H")
+}
+
+/// Write type declarations and prototypes of functions exported by Rust,
+/// between [BEGIN] and [END].
+///
+/// Expects `LIBCOMMONS_PREFIXED` and standard headers to be available.
+pub fn declarations(out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "{BEGIN}")?;
+    writeln!(out)?;
+
+    write_struct(
+        out,
+        "UTF-8 owned string.\n\nLibcommons strings are not null terminated.",
+        "ffi_string_t",
+        &[
+            ("char *buf", offset_of!(FfiString, buf)),
+            ("uintptr_t len", offset_of!(FfiString, len)),
+            ("uintptr_t capacity", offset_of!(FfiString, capacity)),
+            (
+                "void (*drop)(struct LIBCOMMONS_PREFIXED(ffi_string_t) *)",
+                offset_of!(FfiString, drop),
+            ),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Wide pointer to a UTF-8 string slice.\n\nLibcommons strings are not null terminated.",
+        "ffi_str_p",
+        &[
+            ("char *buf", offset_of!(FfiStrPtr, buf)),
+            ("uintptr_t len", offset_of!(FfiStrPtr, len)),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Wide pointer to an array.\n\n`buf` points to `len` elements of the same type, or is `NULL` if\n`len` is 0.",
+        "ffi_slice_p",
+        &[
+            ("void *buf", offset_of!(FfiSlice<u8>, buf)),
+            ("uintptr_t len", offset_of!(FfiSlice<u8>, len)),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Owned growable array.\n\n`buf` points to `len` elements of the same type.",
+        "ffi_vec_t",
+        &[
+            ("void *buf", offset_of!(FfiVec<u8>, buf)),
+            ("uintptr_t len", offset_of!(FfiVec<u8>, len)),
+            ("uintptr_t capacity", offset_of!(FfiVec<u8>, capacity)),
+            (
+                "void (*drop)(struct LIBCOMMONS_PREFIXED(ffi_vec_t) *)",
+                offset_of!(FfiVec<u8>, drop),
+            ),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Owned byte buffer.\n\nUnlike `ffi_string_t`, contents don't have to be valid UTF-8.",
+        "ffi_bytes_t",
+        &[
+            ("uint8_t *buf", offset_of!(FfiBytes, 0.buf)),
+            ("uintptr_t len", offset_of!(FfiBytes, 0.len)),
+            ("uintptr_t capacity", offset_of!(FfiBytes, 0.capacity)),
+            (
+                "void (*drop)(struct LIBCOMMONS_PREFIXED(ffi_bytes_t) *)",
+                offset_of!(FfiBytes, 0.drop),
+            ),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Wide pointer to a UTF-16 string slice.\n\nContents don't have to be valid UTF-16, and are not null terminated.",
+        "ffi_wide_str_p",
+        &[
+            ("const uint16_t *buf", offset_of!(FfiWideStr, buf)),
+            ("uintptr_t len", offset_of!(FfiWideStr, len)),
+        ],
+    )?;
+    write_struct(
+        out,
+        "UTF-16 owned string.\n\nContents don't have to be valid UTF-16, and are not null terminated.",
+        "ffi_wide_string_t",
+        &[
+            ("uint16_t *buf", offset_of!(FfiWideString, 0.buf)),
+            ("uintptr_t len", offset_of!(FfiWideString, 0.len)),
+            ("uintptr_t capacity", offset_of!(FfiWideString, 0.capacity)),
+            (
+                "void (*drop)(struct LIBCOMMONS_PREFIXED(ffi_wide_string_t) *)",
+                offset_of!(FfiWideString, 0.drop),
+            ),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Key-value pair of `ffi_map_t`.",
+        "ffi_map_entry_t",
+        &[
+            (
+                "LIBCOMMONS_PREFIXED(ffi_string_t) key",
+                offset_of!(FfiMapEntry, key),
+            ),
+            (
+                "LIBCOMMONS_PREFIXED(ffi_string_t) value",
+                offset_of!(FfiMapEntry, value),
+            ),
+        ],
+    )?;
+    write_struct(
+        out,
+        "List of string key-value pairs.\n\nKeys are unique. `drop` frees the entries and the buffer.",
+        "ffi_map_t",
+        &[
+            (
+                "LIBCOMMONS_PREFIXED(ffi_map_entry_t) *buf",
+                offset_of!(FfiMap, 0.buf),
+            ),
+            ("uintptr_t len", offset_of!(FfiMap, 0.len)),
+            ("uintptr_t capacity", offset_of!(FfiMap, 0.capacity)),
+            (
+                "void (*drop)(struct LIBCOMMONS_PREFIXED(ffi_map_t) *)",
+                offset_of!(FfiMap, 0.drop),
+            ),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Owned opaque value.\n\n`ptr` can only be passed back to functions of the library that\ncreated it. `drop` destroys the value.",
+        "ffi_box_t",
+        &[
+            ("void *ptr", offset_of!(FfiBox<u8>, ptr)),
+            ("void (*drop)(void *)", offset_of!(FfiBox<u8>, drop)),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Shared immutable value.\n\n`clone` adds an owner, `release` removes one.",
+        "ffi_arc_t",
+        &[
+            ("const void *ptr", offset_of!(FfiArc<u8>, ptr)),
+            (
+                "void (*clone)(const struct LIBCOMMONS_PREFIXED(ffi_arc_t) *)",
+                offset_of!(FfiArc<u8>, clone),
+            ),
+            (
+                "void (*release)(struct LIBCOMMONS_PREFIXED(ffi_arc_t) *)",
+                offset_of!(FfiArc<u8>, release),
+            ),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Shared immutable UTF-8 string.\n\n`clone` adds an owner, `release` removes one.\n\nLibcommons strings are not null terminated.",
+        "ffi_arc_str_t",
+        &[
+            ("const char *buf", offset_of!(FfiArcStr, buf)),
+            ("uintptr_t len", offset_of!(FfiArcStr, len)),
+            (
+                "void (*clone)(const struct LIBCOMMONS_PREFIXED(ffi_arc_str_t) *)",
+                offset_of!(FfiArcStr, clone),
+            ),
+            (
+                "void (*release)(struct LIBCOMMONS_PREFIXED(ffi_arc_str_t) *)",
+                offset_of!(FfiArcStr, release),
+            ),
+        ],
+    )?;
+
+    write_struct(
+        out,
+        "Span of time.\n\n`nanos` is always less than a second.",
+        "ffi_duration_t",
+        &[
+            ("uint64_t secs", offset_of!(FfiDuration, secs)),
+            ("uint32_t nanos", offset_of!(FfiDuration, nanos)),
+        ],
+    )?;
+    write_struct(
+        out,
+        "Point in time, relative to the Unix epoch.\n\n`secs` is negative for points before the epoch, and `nanos` always\ncounts forward from `secs`. `nanos` is always less than a second.",
+        "ffi_timestamp_t",
+        &[
+            ("int64_t secs", offset_of!(FfiTimestamp, secs)),
+            ("uint32_t nanos", offset_of!(FfiTimestamp, nanos)),
+        ],
+    )?;
+
+    write_macro(
+        out,
+        "Callback returning `RET` and taking `ARGS`, a parenthesized parameter\nlist starting with the context pointer.\n\n`call` must be called with `context` as the first argument. `drop`\ndestroys the context, if set.\n\nEach use declares a new type, so `typedef` it once:\n`typedef LIBCOMMONS_FFI_CALLBACK(int, (void *, int)) int_callback_t;`",
+        "LIBCOMMONS_FFI_CALLBACK(RET, ARGS)",
+        &[
+            "struct {",
+            "    RET(*call) ARGS;",
+            "    void *context;",
+            "    void (*drop)(void *);",
+            "}",
+        ],
+    )?;
+    write_macro(
+        out,
+        "Free a callback declared with `LIBCOMMONS_FFI_CALLBACK`.\n\nWill call the internal `drop` method and set all values to 0.",
+        "LIBCOMMONS_FFI_CALLBACK_FREE(CALLBACK)",
+        &[
+            "do {",
+            "    if ((CALLBACK)->drop != NULL)",
+            "        (CALLBACK)->drop((CALLBACK)->context);",
+            "    memset((CALLBACK), 0, sizeof(*(CALLBACK)));",
+            "} while (0)",
+        ],
+    )?;
+    write_macro(
+        out,
+        "Optional value of type `T`.\n\n`value` is only initialized if `is_some` is not 0.\n\nEach use declares a new type, so `typedef` it once:\n`typedef LIBCOMMONS_FFI_OPTION(int) option_int_t;`",
+        "LIBCOMMONS_FFI_OPTION(T)",
+        &["struct {", "    uint8_t is_some;", "    T value;", "}"],
+    )?;
+    write_macro(
+        out,
+        "Either a value of type `T` or an error of type `E`.\n\n`ok` is initialized if `is_err` is 0, `err` if it's 1.\n\nEach use declares a new type, so `typedef` it once:\n`typedef LIBCOMMONS_FFI_RESULT(int, int) result_int_t;`",
+        "LIBCOMMONS_FFI_RESULT(T, E)",
+        &[
+            "struct {",
+            "    uint8_t is_err;",
+            "    union {",
+            "        T ok;",
+            "        E err;",
+            "    };",
+            "}",
+        ],
+    )?;
+
+    write_define(
+        out,
+        "ABI version of this header.\n\nPass to `libcommons_abi_check` of a loaded library before exchanging\nany other values with it.",
+        "LIBCOMMONS_ABI_VERSION",
+        ABI_VERSION,
+    )?;
+    write_define(
+        out,
+        "Panics in Rust functions are caught instead of aborting.",
+        "LIBCOMMONS_CAPABILITY_UNWIND",
+        CAPABILITY_UNWIND,
+    )?;
+    write_struct(
+        out,
+        "Features of the libcommons build a library is using.\n\nFields are only ever appended, so fields past `size` must not be read.\n`flags` is a set of `LIBCOMMONS_CAPABILITY_*` flags.",
+        "abi_capabilities_t",
+        &[
+            ("uintptr_t size", offset_of!(Capabilities, size)),
+            (
+                "uint32_t abi_version",
+                offset_of!(Capabilities, abi_version),
+            ),
+            ("uint32_t flags", offset_of!(Capabilities, flags)),
+            (
+                "LIBCOMMONS_PREFIXED(ffi_str_p) version",
+                offset_of!(Capabilities, version),
+            ),
+        ],
+    )?;
+
+    writeln!(out, "// Provided by Rust libraries")?;
+    writeln!(out)?;
+    write_function(
+        out,
+        "Get the ABI version of this library.",
+        "uint32_t libcommons_abi_version(void)",
+    )?;
+    write_function(
+        out,
+        "Check if a library built against ABI `version` is compatible with\nthis one.",
+        "bool libcommons_abi_check(uint32_t version)",
+    )?;
+    write_function(
+        out,
+        "Get the capabilities of this library.",
+        "const LIBCOMMONS_PREFIXED(abi_capabilities_t) *libcommons_abi_capabilities(void)",
+    )?;
+    write_function(
+        out,
+        "Check if there's an error stored for this thread.",
+        "bool libcommons_has_last_error(void)",
+    )?;
+    write_function(
+        out,
+        "Get the message of the error stored for this thread.\n\nThe string is empty if there is none, and is only valid until the\nerror is replaced or cleared.",
+        "LIBCOMMONS_PREFIXED(ffi_str_p) libcommons_last_error(void)",
+    )?;
+    write_function(
+        out,
+        "Take the message of the error stored for this thread.\n\nThe string is empty if there is none.",
+        "LIBCOMMONS_PREFIXED(ffi_string_t) libcommons_take_last_error(void)",
+    )?;
+    write_function(
+        out,
+        "Clear the error stored for this thread.",
+        "void libcommons_clear_last_error(void)",
+    )?;
+
+    writeln!(out, "{END}")
+}
+
+fn write_doc(out: &mut impl Write, doc: &str) -> io::Result<()> {
+    for line in doc.lines() {
+        match line {
+            "" => writeln!(out, "///")?,
+            x => writeln!(out, "/// {x}")?,
+        }
+    }
+    Ok(())
+}
+
+/// Write a struct typedef.
+///
+/// ## Panics
+/// Panics if `fields` are not in the order of offsets.
+fn write_struct(
+    out: &mut impl Write,
+    doc: &str,
+    name: &str,
+    fields: &[(&str, usize)],
+) -> io::Result<()> {
+    assert!(
+        fields.windows(2).all(|x| x[0].1 < x[1].1),
+        "fields of {name} are out of order",
+    );
+
+    write_doc(out, doc)?;
+    writeln!(out, "typedef struct LIBCOMMONS_PREFIXED({name}) {{")?;
+    for (decl, _) in fields {
+        writeln!(out, "    {decl};")?;
+    }
+    writeln!(out, "}} LIBCOMMONS_PREFIXED({name});")?;
+    writeln!(out)
+}
+
+/// Write a multiline macro, aligning backslashes to the 80th column.
+fn write_macro(out: &mut impl Write, doc: &str, head: &str, body: &[&str]) -> io::Result<()> {
+    write_doc(out, doc)?;
+    writeln!(out, "{:79}\\", format!("#define {head}"))?;
+    for (i, line) in body.iter().enumerate() {
+        if i + 1 == body.len() {
+            writeln!(out, "    {line}")?;
+        } else {
+            writeln!(out, "{:79}\\", format!("    {line}"))?;
+        }
+    }
+    writeln!(out)
+}
+
+fn write_define(out: &mut impl Write, doc: &str, name: &str, value: u32) -> io::Result<()> {
+    write_doc(out, doc)?;
+    writeln!(out, "#define {name} {value}")?;
+    writeln!(out)
+}
+
+fn write_function(out: &mut impl Write, doc: &str, decl: &str) -> io::Result<()> {
+    write_doc(out, doc)?;
+    writeln!(out, "{decl};")?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BEGIN, END, declarations};
+    use crate::ffi::{
+        abi::{
+            Capabilities, libcommons_abi_capabilities, libcommons_abi_check, libcommons_abi_version,
+        },
+        error::{
+            libcommons_clear_last_error, libcommons_has_last_error, libcommons_last_error,
+            libcommons_take_last_error,
+        },
+        str::{FfiStrPtr, FfiString},
+    };
+
+    #[test]
+    fn prototypes_match() {
+        let _: extern "C" fn() -> bool = libcommons_has_last_error;
+        let _: unsafe extern "C" fn() -> FfiStrPtr<'static> = libcommons_last_error;
+        let _: extern "C" fn() -> FfiString = libcommons_take_last_error;
+        let _: extern "C" fn() = libcommons_clear_last_error;
+        let _: extern "C" fn() -> u32 = libcommons_abi_version;
+        let _: extern "C" fn(u32) -> bool = libcommons_abi_check;
+        let _: extern "C" fn() -> &'static Capabilities = libcommons_abi_capabilities;
+    }
+
+    #[test]
+    fn header_is_up_to_date() {
+        let header = include_str!("../../include/libcommons.h");
+        let start = header
+            .find(BEGIN)
+            .expect("no generated declarations in libcommons.h");
+        let end = header
+            .find(END)
+            .expect("no generated declarations in libcommons.h")
+            + END.len();
+
+        let mut generated = Vec::new();
+        declarations(&mut generated).unwrap();
+        assert_eq!(
+            &header[start..=end],
+            String::from_utf8(generated).unwrap(),
+            "libcommons.h is out of date, regenerate it with `ffi::header::declarations`",
+        );
+    }
+}
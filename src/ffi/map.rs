@@ -0,0 +1,163 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    mem::replace,
+};
+
+use super::{
+    str::{FfiStr, FfiString},
+    vec::FfiVec,
+};
+
+/// A key-value pair of an [FfiMap].
+///
+/// See `libcommons.h`.
+#[repr(C)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FfiMapEntry {
+    pub key: FfiString,
+    pub value: FfiString,
+}
+
+/// An FFI-compatible list of string key-value pairs.
+///
+/// Keys are unique, and entries stay in insertion order. Lookups are
+/// linear, since maps crossing FFI are expected to be small.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::map::FfiMap;
+///
+/// let mut config = FfiMap::new();
+/// config.insert("name", "demo");
+/// config.insert("verbose", "false");
+/// config.insert("verbose", "true");
+///
+/// assert_eq!(config.len(), 2);
+/// assert_eq!(config.get("verbose").unwrap().as_str(), "true");
+/// assert_eq!(
+///     config.iter().collect::<Vec<_>>(),
+///     [("name", "demo"), ("verbose", "true")],
+/// );
+/// ```
+#[repr(transparent)]
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct FfiMap(pub(super) FfiVec<FfiMapEntry>);
+impl FfiMap {
+    /// Create a new FfiMap.
+    ///
+    /// This method will not allocate.
+    pub const fn new() -> Self {
+        Self(FfiVec::new())
+    }
+
+    /// Get the number of entries.
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if map is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the entries.
+    pub const fn as_slice(&self) -> &[FfiMapEntry] {
+        self.0.as_slice()
+    }
+
+    /// Get the value of a key.
+    pub fn get(&self, key: &str) -> Option<&FfiStr> {
+        self.entry(key).map(|x| x.value.as_ffi_str())
+    }
+
+    /// Check if map contains a key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entry(key).is_some()
+    }
+
+    /// Set the value of a key, returning the previous one.
+    pub fn insert(
+        &mut self,
+        key: impl Into<FfiString>,
+        value: impl Into<FfiString>,
+    ) -> Option<FfiString> {
+        let key = key.into();
+        let value = value.into();
+        match self.position(key.as_str()) {
+            Some(x) => Some(replace(&mut self.0.as_mut_slice()[x].value, value)),
+            None => {
+                self.0.push(FfiMapEntry { key, value });
+                None
+            }
+        }
+    }
+
+    /// Remove a key, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<FfiString> {
+        let position = self.position(key)?;
+        self.0.as_mut_slice()[position..].rotate_left(1);
+        self.0.pop().map(|x| x.value)
+    }
+
+    /// Iterate over keys and values.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.as_slice()
+            .iter()
+            .map(|x| (x.key.as_str(), x.value.as_str()))
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        self.as_slice().iter().position(|x| x.key.as_str() == key)
+    }
+
+    fn entry(&self, key: &str) -> Option<&FfiMapEntry> {
+        self.as_slice().iter().find(|x| x.key.as_str() == key)
+    }
+}
+impl Debug for FfiMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+impl<K: Into<FfiString>, V: Into<FfiString>> FromIterator<(K, V)> for FfiMap {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+impl<K: Into<FfiString>, V: Into<FfiString>> Extend<(K, V)> for FfiMap {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+impl<K: Into<FfiString>, V: Into<FfiString>, S> From<HashMap<K, V, S>> for FfiMap {
+    fn from(value: HashMap<K, V, S>) -> Self {
+        value.into_iter().collect()
+    }
+}
+impl<K: Into<FfiString>, V: Into<FfiString>> From<BTreeMap<K, V>> for FfiMap {
+    fn from(value: BTreeMap<K, V>) -> Self {
+        value.into_iter().collect()
+    }
+}
+impl From<FfiMap> for HashMap<String, String> {
+    fn from(value: FfiMap) -> Self {
+        value
+            .iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect()
+    }
+}
+impl From<FfiMap> for BTreeMap<String, String> {
+    fn from(value: FfiMap) -> Self {
+        value
+            .iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect()
+    }
+}
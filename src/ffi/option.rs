@@ -0,0 +1,107 @@
+/// An FFI-compatible [Option].
+///
+/// Laid out as a `uint8_t` tag followed by the value, which is only
+/// initialized if the tag is nonzero. See `LIBCOMMONS_FFI_OPTION` in
+/// `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::option::FfiOption;
+///
+/// let value: FfiOption<u32> = Some(5).into();
+/// assert!(value.is_some());
+/// assert_eq!(value.into_option(), Some(5));
+/// assert_eq!(FfiOption::<u32>::None.into_option(), None);
+/// ```
+#[repr(C, u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FfiOption<T> {
+    #[default]
+    None,
+    Some(T),
+}
+impl<T> FfiOption<T> {
+    /// Check if this is [FfiOption::Some].
+    pub const fn is_some(&self) -> bool {
+        matches!(self, Self::Some(_))
+    }
+
+    /// Check if this is [FfiOption::None].
+    pub const fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// Get a reference to the value.
+    pub const fn as_ref(&self) -> FfiOption<&T> {
+        match self {
+            Self::Some(x) => FfiOption::Some(x),
+            Self::None => FfiOption::None,
+        }
+    }
+
+    /// Get a mutable reference to the value.
+    pub const fn as_mut(&mut self) -> FfiOption<&mut T> {
+        match self {
+            Self::Some(x) => FfiOption::Some(x),
+            Self::None => FfiOption::None,
+        }
+    }
+
+    /// Convert this [FfiOption] into an [Option].
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Some(x) => Some(x),
+            Self::None => None,
+        }
+    }
+}
+impl<T> From<Option<T>> for FfiOption<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(x) => Self::Some(x),
+            None => Self::None,
+        }
+    }
+}
+impl<T> From<FfiOption<T>> for Option<T> {
+    fn from(value: FfiOption<T>) -> Self {
+        value.into_option()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FfiOption;
+    use crate::ffi::result::FfiResult;
+
+    /// Get offset of `field` in `value` and the tag byte.
+    fn layout<T, F>(value: &T, field: &F) -> (usize, u8) {
+        let base = value as *const T;
+        let offset = field as *const F as usize - base as usize;
+        (offset, unsafe { base.cast::<u8>().read() })
+    }
+
+    #[test]
+    fn layout_matches_c() {
+        // Must match `tests/option.c`.
+        assert_eq!(size_of::<FfiOption<u32>>(), 8);
+        assert_eq!(size_of::<FfiResult<u64, i32>>(), 16);
+
+        let value = FfiOption::Some(5u32);
+        let FfiOption::Some(x) = &value else {
+            unreachable!()
+        };
+        assert_eq!(layout(&value, x), (4, 1));
+
+        let value = FfiResult::<u64, i32>::Ok(5);
+        let FfiResult::Ok(x) = &value else {
+            unreachable!()
+        };
+        assert_eq!(layout(&value, x), (8, 0));
+
+        let value = FfiResult::<u64, i32>::Err(-1);
+        let FfiResult::Err(x) = &value else {
+            unreachable!()
+        };
+        assert_eq!(layout(&value, x), (8, 1));
+    }
+}
@@ -0,0 +1,70 @@
+/// An FFI-compatible [Result].
+///
+/// Laid out as a `uint8_t` tag followed by a union of the value and the
+/// error. The tag is 0 for [FfiResult::Ok] and 1 for [FfiResult::Err].
+/// See `LIBCOMMONS_FFI_RESULT` in `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::result::FfiResult;
+///
+/// let value: FfiResult<u32, i32> = Ok(5).into();
+/// assert!(value.is_ok());
+/// assert_eq!(value.into_result(), Ok(5));
+///
+/// let value: FfiResult<u32, i32> = Err(-1).into();
+/// assert_eq!(Result::from(value), Err(-1));
+/// ```
+#[repr(C, u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FfiResult<T, E> {
+    Ok(T),
+    Err(E),
+}
+impl<T, E> FfiResult<T, E> {
+    /// Check if this is [FfiResult::Ok].
+    pub const fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok(_))
+    }
+
+    /// Check if this is [FfiResult::Err].
+    pub const fn is_err(&self) -> bool {
+        matches!(self, Self::Err(_))
+    }
+
+    /// Get a reference to the value or the error.
+    pub const fn as_ref(&self) -> FfiResult<&T, &E> {
+        match self {
+            Self::Ok(x) => FfiResult::Ok(x),
+            Self::Err(x) => FfiResult::Err(x),
+        }
+    }
+
+    /// Get a mutable reference to the value or the error.
+    pub const fn as_mut(&mut self) -> FfiResult<&mut T, &mut E> {
+        match self {
+            Self::Ok(x) => FfiResult::Ok(x),
+            Self::Err(x) => FfiResult::Err(x),
+        }
+    }
+
+    /// Convert this [FfiResult] into a [Result].
+    pub fn into_result(self) -> Result<T, E> {
+        match self {
+            Self::Ok(x) => Ok(x),
+            Self::Err(x) => Err(x),
+        }
+    }
+}
+impl<T, E> From<Result<T, E>> for FfiResult<T, E> {
+    fn from(value: Result<T, E>) -> Self {
+        match value {
+            Ok(x) => Self::Ok(x),
+            Err(x) => Self::Err(x),
+        }
+    }
+}
+impl<T, E> From<FfiResult<T, E>> for Result<T, E> {
+    fn from(value: FfiResult<T, E>) -> Self {
+        value.into_result()
+    }
+}
@@ -0,0 +1,245 @@
+use std::{fmt::Debug, marker::PhantomData, slice};
+
+/// A wide pointer to an FFI-compatible slice.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::slice::FfiSlice;
+///
+/// let data = [1u8, 2, 3];
+/// let ptr = FfiSlice::from(&data[..]);
+/// assert_eq!(ptr.len(), 3);
+/// assert_eq!(ptr.as_slice(), &[1, 2, 3]);
+/// ```
+#[repr(C)]
+pub struct FfiSlice<'a, T> {
+    pub(super) buf: *const T,
+    pub(super) len: usize,
+    _phantom: PhantomData<&'a [T]>,
+}
+impl<'a, T> FfiSlice<'a, T> {
+    /// Make a slice passable via ffi.
+    pub const fn new(slice: &'a [T]) -> Self {
+        Self {
+            buf: slice.as_ptr(),
+            len: slice.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create an [FfiSlice] referencing the buffer.
+    ///
+    /// ## Safety
+    /// Provided pointer must either be null or point to `len` valid
+    /// elements for the entire time that this [FfiSlice] will be used.
+    pub const unsafe fn from_raw_parts(buf: *const T, len: usize) -> Self {
+        Self {
+            buf,
+            len,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Obtain the underlying pointer.
+    pub const fn as_ptr(&self) -> *const T {
+        self.buf
+    }
+
+    /// Get length of this [FfiSlice].
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether this [FfiSlice] is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Convert this [FfiSlice] to a slice.
+    pub const fn as_slice(&self) -> &'a [T] {
+        if self.buf.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.buf, self.len) }
+        }
+    }
+}
+impl<T> Clone for FfiSlice<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for FfiSlice<'_, T> {}
+impl<T: Debug> Debug for FfiSlice<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+impl<T: PartialEq> PartialEq for FfiSlice<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+impl<T: Eq> Eq for FfiSlice<'_, T> {}
+impl<T> AsRef<[T]> for FfiSlice<'_, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+impl<'a, T> From<&'a [T]> for FfiSlice<'a, T> {
+    fn from(value: &'a [T]) -> Self {
+        Self::new(value)
+    }
+}
+impl<'a, T, const N: usize> From<&'a [T; N]> for FfiSlice<'a, T> {
+    fn from(value: &'a [T; N]) -> Self {
+        Self::new(value)
+    }
+}
+impl<'a, T> From<&'a Vec<T>> for FfiSlice<'a, T> {
+    fn from(value: &'a Vec<T>) -> Self {
+        Self::new(value)
+    }
+}
+impl<'a, T> From<FfiSlice<'a, T>> for &'a [T] {
+    fn from(value: FfiSlice<'a, T>) -> Self {
+        value.as_slice()
+    }
+}
+
+/// A wide pointer to an FFI-compatible mutable slice.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::slice::FfiSliceMut;
+///
+/// let mut data = [1u8, 2, 3];
+/// let mut ptr = FfiSliceMut::from(&mut data[..]);
+/// ptr.as_mut_slice()[0] = 4;
+/// assert_eq!(data, [4, 2, 3]);
+/// ```
+#[repr(C)]
+pub struct FfiSliceMut<'a, T> {
+    buf: *mut T,
+    len: usize,
+    _phantom: PhantomData<&'a mut [T]>,
+}
+impl<'a, T> FfiSliceMut<'a, T> {
+    /// Make a mutable slice passable via ffi.
+    pub const fn new(slice: &'a mut [T]) -> Self {
+        Self {
+            buf: slice.as_mut_ptr(),
+            len: slice.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create an [FfiSliceMut] referencing the buffer.
+    ///
+    /// ## Safety
+    /// Provided pointer must either be null or point to `len` valid
+    /// elements for the entire time that this [FfiSliceMut] will be used,
+    /// and must not be accessed through other pointers meanwhile.
+    pub const unsafe fn from_raw_parts(buf: *mut T, len: usize) -> Self {
+        Self {
+            buf,
+            len,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Obtain the underlying pointer.
+    pub const fn as_ptr(&self) -> *const T {
+        self.buf
+    }
+
+    /// Obtain the underlying mutable pointer.
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf
+    }
+
+    /// Get length of this [FfiSliceMut].
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether this [FfiSliceMut] is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Convert this [FfiSliceMut] to a slice.
+    pub const fn as_slice(&self) -> &[T] {
+        if self.buf.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.buf, self.len) }
+        }
+    }
+
+    /// Convert this [FfiSliceMut] to a mutable slice.
+    pub const fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.buf.is_null() {
+            &mut []
+        } else {
+            unsafe { slice::from_raw_parts_mut(self.buf, self.len) }
+        }
+    }
+
+    /// Convert this [FfiSliceMut] into a mutable slice with the original
+    /// lifetime.
+    pub const fn into_slice(self) -> &'a mut [T] {
+        if self.buf.is_null() {
+            &mut []
+        } else {
+            unsafe { slice::from_raw_parts_mut(self.buf, self.len) }
+        }
+    }
+
+    /// Reborrow this [FfiSliceMut] as an [FfiSlice].
+    pub const fn as_ffi_slice(&self) -> FfiSlice<'_, T> {
+        unsafe { FfiSlice::from_raw_parts(self.buf, self.len) }
+    }
+}
+impl<T: Debug> Debug for FfiSliceMut<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+impl<T> AsRef<[T]> for FfiSliceMut<'_, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+impl<T> AsMut<[T]> for FfiSliceMut<'_, T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+impl<'a, T> From<&'a mut [T]> for FfiSliceMut<'a, T> {
+    fn from(value: &'a mut [T]) -> Self {
+        Self::new(value)
+    }
+}
+impl<'a, T, const N: usize> From<&'a mut [T; N]> for FfiSliceMut<'a, T> {
+    fn from(value: &'a mut [T; N]) -> Self {
+        Self::new(value)
+    }
+}
+impl<'a, T> From<&'a mut Vec<T>> for FfiSliceMut<'a, T> {
+    fn from(value: &'a mut Vec<T>) -> Self {
+        Self::new(value)
+    }
+}
+impl<'a, T> From<FfiSliceMut<'a, T>> for &'a mut [T] {
+    fn from(value: FfiSliceMut<'a, T>) -> Self {
+        value.into_slice()
+    }
+}
+impl<'a, T> From<FfiSliceMut<'a, T>> for FfiSlice<'a, T> {
+    fn from(value: FfiSliceMut<'a, T>) -> Self {
+        unsafe { FfiSlice::from_raw_parts(value.buf, value.len) }
+    }
+}
@@ -1,13 +1,18 @@
 use std::{
     borrow::Borrow,
-    ffi::c_char,
-    fmt::{Debug, Display},
+    ffi::{CStr, CString, FromBytesWithNulError, NulError, c_char},
+    fmt::{self, Debug, Display},
     marker::PhantomData,
     mem::{forget, transmute},
+    ops::{Bound, RangeBounds},
     ptr::null_mut,
     slice,
+    str::Utf8Error,
+    string::FromUtf8Error,
 };
 
+use super::slice::FfiSliceMut;
+
 unsafe extern "C" fn __libcommons_rust_drop(string: *mut FfiString) {
     unsafe {
         let string = string.as_mut().unwrap();
@@ -19,6 +24,11 @@ unsafe extern "C" fn __libcommons_rust_drop(string: *mut FfiString) {
     }
 }
 
+/// Convert a range to a pair of bounds, which can index a [str].
+fn bounds(range: impl RangeBounds<usize>) -> (Bound<usize>, Bound<usize>) {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
+
 /// An FFI-compatible string slice.
 ///
 /// This cannot be passed via FFI on its own. Use [FfiStr::as_ptr] to get a C-compatible wide pointer.
@@ -64,6 +74,87 @@ impl FfiStr {
         unsafe { transmute(str::from_utf8_unchecked(slice)) }
     }
 
+    /// Convert a [CStr] to [FfiStr], without the NUL terminator.
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiStr;
+    ///
+    /// assert_eq!(FfiStr::from_cstr(c"hello").unwrap().as_str(), "hello");
+    /// assert!(FfiStr::from_cstr(c"\xFF").is_err());
+    /// ```
+    ///
+    /// ## Errors
+    /// Fails if the string is not valid UTF-8.
+    pub fn from_cstr(cstr: &CStr) -> Result<&Self, Utf8Error> {
+        cstr.to_str().map(Self::from_str)
+    }
+
+    /// Copy this [FfiStr] into a NUL-terminated [CString].
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiStr;
+    ///
+    /// assert_eq!(FfiStr::from_str("hi").to_cstring().unwrap().as_c_str(), c"hi");
+    /// assert!(FfiStr::from_str("a\0b").to_cstring().is_err());
+    /// ```
+    ///
+    /// ## Errors
+    /// Fails if the string contains NUL bytes.
+    pub fn to_cstring(&self) -> Result<CString, NulError> {
+        CString::new(self.as_bytes())
+    }
+
+    /// Get a substring of this [FfiStr].
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiStr;
+    ///
+    /// let str = FfiStr::from_str("héllo");
+    /// assert_eq!(str.slice(3..).as_str(), "llo");
+    /// assert_eq!(str.slice(..3).as_str(), "hé");
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if `range` is out of bounds or doesn't start and end on
+    /// char boundaries. See [FfiStr::get] for a non-panicking version.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> &Self {
+        Self::from_str(&self.inner[bounds(range)])
+    }
+
+    /// Get a substring of this [FfiStr].
+    ///
+    /// Returns [None] if `range` is out of bounds or doesn't start and
+    /// end on char boundaries.
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiStr;
+    ///
+    /// let str = FfiStr::from_str("héllo");
+    /// assert_eq!(str.get(1..3).unwrap().as_str(), "é");
+    /// assert!(str.get(1..2).is_none());
+    /// assert!(str.get(..10).is_none());
+    /// ```
+    pub fn get(&self, range: impl RangeBounds<usize>) -> Option<&Self> {
+        self.inner.get(bounds(range)).map(Self::from_str)
+    }
+
+    /// Split this [FfiStr] in two at a byte index.
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiStr;
+    ///
+    /// let (key, value) = FfiStr::from_str("key=value").split_at(3);
+    /// assert_eq!(key.as_str(), "key");
+    /// assert_eq!(value.as_str(), "=value");
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if `mid` is out of bounds or not on a char boundary.
+    pub fn split_at(&self, mid: usize) -> (&Self, &Self) {
+        let (a, b) = self.inner.split_at(mid);
+        (Self::from_str(a), Self::from_str(b))
+    }
+
     /// Make this [FfiStr] passable via ffi.
     pub const fn as_ptr(&self) -> FfiStrPtr<'_> {
         FfiStrPtr {
@@ -83,6 +174,13 @@ impl<'a> From<&'a str> for &'a FfiStr {
         FfiStr::from_str(value)
     }
 }
+impl<'a> TryFrom<&'a CStr> for &'a FfiStr {
+    type Error = Utf8Error;
+
+    fn try_from(value: &'a CStr) -> Result<Self, Self::Error> {
+        FfiStr::from_cstr(value)
+    }
+}
 impl<'a> From<FfiStrPtr<'a>> for &'a FfiStr {
     fn from(value: FfiStrPtr<'a>) -> Self {
         unsafe { FfiStr::from_raw_parts(value.buf, value.len) }
@@ -112,8 +210,8 @@ impl ToOwned for FfiStr {
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub struct FfiStrPtr<'a> {
-    buf: *const u8,
-    len: usize,
+    pub(super) buf: *const u8,
+    pub(super) len: usize,
     _phantom: PhantomData<&'a str>,
 }
 impl<'a> FfiStrPtr<'a> {
@@ -135,14 +233,56 @@ impl<'a> FfiStrPtr<'a> {
     /// Convert this [FfiStrPtr] to [str].
     pub const fn as_str(&self) -> &str {
         unsafe {
-            if self.is_empty() {
-                str::from_utf8_unchecked(slice::from_raw_parts(self.buf, self.len))
-            } else {
+            if self.buf.is_null() {
                 ""
+            } else {
+                str::from_utf8_unchecked(slice::from_raw_parts(self.buf, self.len))
             }
         }
     }
 
+    /// Get a substring of this [FfiStrPtr].
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiStr;
+    ///
+    /// let ptr = FfiStr::from_str("hello, world").as_ptr();
+    /// assert_eq!(ptr.slice(7..).as_str(), "world");
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if `range` is out of bounds or doesn't start and end on
+    /// char boundaries. See [FfiStrPtr::get] for a non-panicking version.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> FfiStrPtr<'a> {
+        self.as_ffi_str().slice(range).as_ptr()
+    }
+
+    /// Get a substring of this [FfiStrPtr].
+    ///
+    /// Returns [None] if `range` is out of bounds or doesn't start and
+    /// end on char boundaries.
+    pub fn get(&self, range: impl RangeBounds<usize>) -> Option<FfiStrPtr<'a>> {
+        self.as_ffi_str().get(range).map(FfiStr::as_ptr)
+    }
+
+    /// Split this [FfiStrPtr] in two at a byte index.
+    ///
+    /// ## Panics
+    /// Panics if `mid` is out of bounds or not on a char boundary.
+    pub fn split_at(&self, mid: usize) -> (FfiStrPtr<'a>, FfiStrPtr<'a>) {
+        let (a, b) = self.as_ffi_str().split_at(mid);
+        (a.as_ptr(), b.as_ptr())
+    }
+
+    /// Convert this [FfiStrPtr] to [FfiStr], keeping its lifetime.
+    fn as_ffi_str(&self) -> &'a FfiStr {
+        if self.buf.is_null() {
+            FfiStr::from_str("")
+        } else {
+            unsafe { FfiStr::from_raw_parts(self.buf, self.len) }
+        }
+    }
+
     /// Clone this [FfiStrPtr] into an [FfiString].
     pub fn to_ffi_string(&self) -> FfiString {
         self.into()
@@ -164,10 +304,10 @@ impl<'a> From<&'a FfiStr> for FfiStrPtr<'a> {
 /// See `libcommons.h`.
 #[repr(C)]
 pub struct FfiString {
-    buf: *mut u8,
-    len: usize,
-    capacity: usize,
-    drop: Option<unsafe extern "C" fn(*mut FfiString)>,
+    pub(super) buf: *mut u8,
+    pub(super) len: usize,
+    pub(super) capacity: usize,
+    pub(super) drop: Option<unsafe extern "C" fn(*mut FfiString)>,
 }
 impl FfiString {
     /// Create a new FfiString.
@@ -215,6 +355,51 @@ impl FfiString {
         ffi
     }
 
+    /// Create an FfiString from a buffer allocated elsewhere.
+    ///
+    /// `drop` is called with the string when it needs to free the buffer,
+    /// either when dropped or when the buffer is re-allocated. If `drop` is
+    /// [None], the buffer is never freed.
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiString;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static FREED: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// unsafe extern "C" fn free(string: *mut FfiString) {
+    ///     // A C allocator would free the buffer here.
+    ///     FREED.store(unsafe { (*string).capacity() }, Ordering::Relaxed);
+    /// }
+    ///
+    /// static mut BUF: [u8; 8] = *b"Hi!\0\0\0\0\0";
+    ///
+    /// let mut string =
+    ///     unsafe { FfiString::from_raw_parts_with_drop(&raw mut BUF as *mut u8, 3, 8, Some(free)) };
+    /// string.push_str(" :)");
+    /// assert_eq!(string, "Hi! :)");
+    /// drop(string);
+    /// assert_eq!(FREED.load(Ordering::Relaxed), 8);
+    /// ```
+    ///
+    /// ## Safety
+    /// `buf` must be valid for reads and writes of `capacity` bytes, and
+    /// its first `len` bytes must be valid UTF-8. `buf` may be null only
+    /// if `capacity` is 0. `drop` must free the buffer and nothing else.
+    pub const unsafe fn from_raw_parts_with_drop(
+        buf: *mut u8,
+        len: usize,
+        capacity: usize,
+        drop: Option<unsafe extern "C" fn(*mut FfiString)>,
+    ) -> Self {
+        Self {
+            buf,
+            len,
+            capacity,
+            drop,
+        }
+    }
+
     /// Get underlying bytes.
     ///
     /// ```
@@ -240,7 +425,7 @@ impl FfiString {
     /// assert_eq!(string.as_str(), "Hi!");
     /// ```
     pub const fn as_str(&self) -> &str {
-        unsafe { str::from_utf8_unchecked(slice::from_raw_parts(self.buf, self.len)) }
+        unsafe { str::from_utf8_unchecked(self.as_bytes()) }
     }
 
     /// Get an FFI string reference.
@@ -313,47 +498,169 @@ impl FfiString {
             return;
         }
 
+        let olen = str.len();
+        if self.len + olen > self.capacity {
+            self.reserve(olen);
+        }
+
         unsafe {
-            let olen = str.len();
+            slice::from_raw_parts_mut(self.buf, self.capacity)[self.len..][..olen]
+                .copy_from_slice(str.as_bytes());
+        }
+        self.len += olen;
+    }
 
-            if self.len + olen <= self.capacity {
-                slice::from_raw_parts_mut(self.buf, self.capacity)[self.len..][..olen]
-                    .copy_from_slice(str.as_bytes());
-                self.len += olen;
-                return;
-            }
+    /// Make sure there's space for at least `additional` more bytes.
+    ///
+    /// Will re-allocate the internal buffer the same way as
+    /// [FfiString::push_str].
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiString;
+    ///
+    /// let mut string = FfiString::from("Hi!");
+    /// string.reserve(10);
+    /// assert!(string.capacity() >= 13);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        if self.len + additional <= self.capacity {
+            return;
+        }
 
-            let newsize = (self.capacity + olen)
-                .checked_next_power_of_two()
-                .and_then(|x| x.checked_mul(2))
-                .expect("string is too long");
-            let mut newstring = {
-                let mut s = String::with_capacity(newsize);
-                let funny_s = String::from_raw_parts(s.as_mut_ptr(), self.len + olen, s.capacity());
-                forget(s);
-                funny_s
-            };
-
-            if !self.buf.is_null() {
-                newstring.as_bytes_mut()[0..self.len].copy_from_slice(self.as_bytes());
-            }
-            newstring.as_bytes_mut()[self.len..olen].copy_from_slice(str.as_bytes());
+        let newsize = (self.capacity + additional)
+            .checked_next_power_of_two()
+            .and_then(|x| x.checked_mul(2))
+            .expect("string is too long");
+        self.realloc(newsize);
+    }
 
+    /// Shrink the internal buffer to fit the string.
+    ///
+    /// Empty strings free the buffer.
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiString;
+    ///
+    /// let mut string = FfiString::with_capacity(128);
+    /// string.push_str("Hi!");
+    /// string.shrink_to_fit();
+    /// assert_eq!(string.capacity(), 3);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        if self.len == 0 {
+            *self = Self::new();
+        } else if self.len < self.capacity {
+            self.realloc(self.len);
+        }
+    }
+
+    /// Shorten the string to `len` bytes.
+    ///
+    /// Does nothing if the string is already shorter. The buffer is kept.
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiString;
+    ///
+    /// let mut string = FfiString::from("Hello, world!");
+    /// string.truncate(5);
+    /// assert_eq!(string, "Hello");
+    /// ```
+    ///
+    /// ## Panics
+    /// Panics if `len` is not on a char boundary.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            assert!(self.as_str().is_char_boundary(len), "not a char boundary");
+            self.len = len;
+        }
+    }
+
+    /// Remove all contents, keeping the buffer.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Remove the last character.
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiString;
+    ///
+    /// let mut string = FfiString::from("ёж");
+    /// assert_eq!(string.pop(), Some('ж'));
+    /// assert_eq!(string.pop(), Some('ё'));
+    /// assert_eq!(string.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<char> {
+        let char = self.as_str().chars().next_back()?;
+        self.len -= char.len_utf8();
+        Some(char)
+    }
+
+    /// Move the string into a new buffer of `capacity` bytes, freeing the
+    /// old one.
+    fn realloc(&mut self, capacity: usize) {
+        let mut string = String::with_capacity(capacity);
+        string.push_str(self.as_str());
+        unsafe {
             if !self.buf.is_null()
                 && let Some(drop) = self.drop
             {
                 drop(self);
             }
+        }
 
-            self.buf = newstring.as_mut_ptr();
-            self.len += olen;
-            self.drop = Some(__libcommons_rust_drop);
-            self.capacity = newstring.capacity();
+        self.buf = string.as_mut_ptr();
+        self.capacity = string.capacity();
+        self.drop = Some(__libcommons_rust_drop);
+        forget(string);
+    }
 
-            forget(newstring);
+    /// Write a NUL terminator after the string.
+    ///
+    /// The terminator is not counted in the length, so pushing more data
+    /// overwrites it.
+    ///
+    /// Will re-allocate the internal buffer if there's no space for it.
+    pub fn push_nul(&mut self) {
+        if self.len == self.capacity {
+            self.push_str("\0");
+            self.len -= 1;
+        } else {
+            unsafe { self.buf.add(self.len).write(0) }
         }
     }
 
+    /// Get this string as a [CStr].
+    ///
+    /// Writes a NUL terminator with [FfiString::push_nul], so the buffer
+    /// can be passed to C functions expecting NUL-terminated strings.
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiString;
+    ///
+    /// let mut string = FfiString::from("Hi!");
+    /// assert_eq!(string.as_c_string().unwrap(), c"Hi!");
+    ///
+    /// string.push_str(" there");
+    /// assert_eq!(string.as_c_string().unwrap(), c"Hi! there");
+    /// assert_eq!(string.len(), 9);
+    /// ```
+    ///
+    /// ## Errors
+    /// Fails if the string contains NUL bytes.
+    pub fn as_c_string(&mut self) -> Result<&CStr, FromBytesWithNulError> {
+        self.push_nul();
+        CStr::from_bytes_with_nul(unsafe { slice::from_raw_parts(self.buf, self.len + 1) })
+    }
+
+    /// Copy this string into a NUL-terminated [CString].
+    ///
+    /// ## Errors
+    /// Fails if the string contains NUL bytes.
+    pub fn to_cstring(&self) -> Result<CString, NulError> {
+        CString::new(self.as_bytes())
+    }
+
     /// Append a character.
     ///
     /// Will re-allocate the internal buffer if the string is
@@ -392,18 +699,29 @@ impl Default for FfiString {
         Self::new()
     }
 }
+impl Clone for FfiString {
+    fn clone(&self) -> Self {
+        self.as_str().into()
+    }
+}
+impl PartialEq for FfiString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+impl Eq for FfiString {}
 impl<'a> PartialEq<&'a str> for FfiString {
     fn eq(&self, other: &&'a str) -> bool {
         (&self.as_str()).eq(other)
     }
 }
 impl Debug for FfiString {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Debug::fmt(self.as_str(), f)
     }
 }
 impl Display for FfiString {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Display::fmt(self.as_str(), f)
     }
 }
@@ -423,6 +741,61 @@ impl From<String> for FfiString {
         ffi
     }
 }
+impl fmt::Write for FfiString {
+    /// ```
+    /// use libcommons::ffi::str::FfiString;
+    /// use std::fmt::Write;
+    ///
+    /// let mut string = FfiString::new();
+    /// write!(string, "error {}: {}", 404, "not found").unwrap();
+    /// assert_eq!(string, "error 404: not found");
+    /// ```
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+}
+impl Extend<char> for FfiString {
+    fn extend<T: IntoIterator<Item = char>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for char in iter {
+            self.push_str(char.encode_utf8(&mut [0; 4]));
+        }
+    }
+}
+impl<'a> Extend<&'a str> for FfiString {
+    fn extend<T: IntoIterator<Item = &'a str>>(&mut self, iter: T) {
+        for str in iter {
+            self.push_str(str);
+        }
+    }
+}
+impl FromIterator<char> for FfiString {
+    /// ```
+    /// use libcommons::ffi::str::FfiString;
+    ///
+    /// let string: FfiString = "hello".chars().rev().collect();
+    /// assert_eq!(string, "olleh");
+    /// ```
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        let mut string = Self::new();
+        string.extend(iter);
+        string
+    }
+}
+impl<'a> FromIterator<&'a str> for FfiString {
+    fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+        let mut string = Self::new();
+        string.extend(iter);
+        string
+    }
+}
 impl<'a> From<&'a str> for FfiString {
     fn from(value: &'a str) -> Self {
         String::from(value).into()
@@ -433,6 +806,13 @@ impl<'a> From<&'a FfiStr> for FfiString {
         Self::from(value.as_str())
     }
 }
+impl<'a> TryFrom<&'a CStr> for FfiString {
+    type Error = Utf8Error;
+
+    fn try_from(value: &'a CStr) -> Result<Self, Self::Error> {
+        FfiStr::from_cstr(value).map(Self::from)
+    }
+}
 impl<'a> From<FfiStrPtr<'a>> for FfiString {
     fn from(value: FfiStrPtr<'a>) -> Self {
         Self::from(value.as_str())
@@ -463,3 +843,117 @@ impl Borrow<str> for FfiString {
         self.as_str()
     }
 }
+
+/// A zeroed buffer for C to write a string into.
+///
+/// Pass the buffer to C with [FfiStringBuilder::as_ffi_slice_mut] or
+/// [FfiStringBuilder::as_mut_ptr], then turn the written part into an
+/// [FfiString] with [FfiStringBuilder::finish].
+///
+/// ```
+/// use libcommons::ffi::str::FfiStringBuilder;
+///
+/// // Stands in for a C function filling a buffer.
+/// unsafe extern "C" fn get_name(buf: *mut u8, capacity: usize) -> usize {
+///     let name = b"libcommons";
+///     let len = name.len().min(capacity);
+///     unsafe { buf.copy_from_nonoverlapping(name.as_ptr(), len) };
+///     len
+/// }
+///
+/// let mut builder = FfiStringBuilder::with_capacity(64);
+/// let len = unsafe { get_name(builder.as_mut_ptr(), builder.capacity()) };
+/// assert_eq!(builder.finish(len).unwrap(), "libcommons");
+/// ```
+pub struct FfiStringBuilder {
+    buf: Vec<u8>,
+}
+impl FfiStringBuilder {
+    /// Allocate a zeroed buffer of `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: vec![0; capacity],
+        }
+    }
+
+    /// Get the size of the buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Obtain the pointer to the buffer.
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.buf.as_mut_ptr()
+    }
+
+    /// Get the buffer.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+
+    /// Get a mutable wide pointer to the buffer.
+    pub fn as_ffi_slice_mut(&mut self) -> FfiSliceMut<'_, u8> {
+        FfiSliceMut::new(&mut self.buf)
+    }
+
+    /// Turn the first `len` bytes of the buffer into an [FfiString].
+    ///
+    /// ## Errors
+    /// Fails if the bytes are not valid UTF-8.
+    ///
+    /// ## Panics
+    /// Panics if `len` is larger than the capacity.
+    pub fn finish(mut self, len: usize) -> Result<FfiString, FromUtf8Error> {
+        assert!(
+            len <= self.buf.len(),
+            "length {len} exceeds capacity {}",
+            self.buf.len(),
+        );
+        self.buf.truncate(len);
+        String::from_utf8(self.buf).map(FfiString::from)
+    }
+
+    /// Turn the buffer up to the first NUL byte into an [FfiString].
+    ///
+    /// Takes the whole buffer if C didn't write a NUL terminator.
+    ///
+    /// ```
+    /// use libcommons::ffi::str::FfiStringBuilder;
+    ///
+    /// let mut builder = FfiStringBuilder::with_capacity(16);
+    /// builder.as_mut_slice()[..3].copy_from_slice(b"abc");
+    /// assert_eq!(builder.finish_nul().unwrap(), "abc");
+    /// ```
+    ///
+    /// ## Errors
+    /// Fails if the bytes are not valid UTF-8.
+    pub fn finish_nul(self) -> Result<FfiString, FromUtf8Error> {
+        let len = self
+            .buf
+            .iter()
+            .position(|&x| x == 0)
+            .unwrap_or(self.buf.len());
+        self.finish(len)
+    }
+}
+impl Debug for FfiStringBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FfiStringBuilder")
+            .field("capacity", &self.buf.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FfiString;
+
+    #[test]
+    fn push_str_past_capacity() {
+        let mut string = FfiString::with_capacity(4);
+        string.push_str("abc");
+        string.push_str("defgh");
+        assert_eq!(string.as_str(), "abcdefgh");
+        assert!(string.capacity() >= 8);
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// An FFI-compatible span of time.
+///
+/// `nanos` is always less than a second.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use std::time::Duration;
+/// use libcommons::ffi::time::FfiDuration;
+///
+/// let timeout = FfiDuration::from(Duration::from_millis(1500));
+/// assert_eq!(timeout.secs, 1);
+/// assert_eq!(timeout.nanos, 500_000_000);
+/// assert_eq!(Duration::from(timeout), Duration::from_millis(1500));
+/// ```
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FfiDuration {
+    /// Whole seconds.
+    pub secs: u64,
+    /// Nanoseconds past `secs`.
+    pub nanos: u32,
+}
+impl FfiDuration {
+    /// A duration of zero.
+    pub const ZERO: Self = Self { secs: 0, nanos: 0 };
+
+    /// Create a new FfiDuration.
+    ///
+    /// Nanoseconds over a second are carried into `secs`.
+    ///
+    /// ## Panics
+    /// Panics if `secs` overflows.
+    pub const fn new(secs: u64, nanos: u32) -> Self {
+        let duration = Duration::new(secs, nanos);
+        Self {
+            secs: duration.as_secs(),
+            nanos: duration.subsec_nanos(),
+        }
+    }
+
+    /// Convert this [FfiDuration] into a [Duration].
+    pub const fn to_duration(self) -> Duration {
+        Duration::new(self.secs, self.nanos)
+    }
+}
+impl From<Duration> for FfiDuration {
+    fn from(value: Duration) -> Self {
+        Self {
+            secs: value.as_secs(),
+            nanos: value.subsec_nanos(),
+        }
+    }
+}
+impl From<FfiDuration> for Duration {
+    fn from(value: FfiDuration) -> Self {
+        value.to_duration()
+    }
+}
+
+/// An FFI-compatible point in time, relative to the Unix epoch.
+///
+/// `secs` is negative for points before the epoch, and `nanos` always
+/// counts forward from `secs`, so -0.25 seconds is stored as `-1` and
+/// `750_000_000`.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use libcommons::ffi::time::FfiTimestamp;
+///
+/// let before = FfiTimestamp::from(UNIX_EPOCH - Duration::from_millis(250));
+/// assert_eq!((before.secs, before.nanos), (-1, 750_000_000));
+///
+/// let now = FfiTimestamp::now();
+/// assert!(now > before);
+/// ```
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FfiTimestamp {
+    /// Whole seconds since the Unix epoch.
+    pub secs: i64,
+    /// Nanoseconds past `secs`.
+    pub nanos: u32,
+}
+impl FfiTimestamp {
+    /// The Unix epoch.
+    pub const UNIX_EPOCH: Self = Self { secs: 0, nanos: 0 };
+
+    /// Get the current system time.
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// Convert this [FfiTimestamp] into a [SystemTime].
+    ///
+    /// Returns [None] if the platform can't represent it.
+    pub fn to_system_time(self) -> Option<SystemTime> {
+        let nanos = Duration::from_nanos(self.nanos as u64);
+        if self.secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(self.secs as u64))?
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_secs(self.secs.unsigned_abs()))?
+        }
+        .checked_add(nanos)
+    }
+}
+impl From<SystemTime> for FfiTimestamp {
+    /// Times too far from the epoch saturate.
+    fn from(value: SystemTime) -> Self {
+        match value.duration_since(UNIX_EPOCH) {
+            Ok(x) => Self {
+                secs: x.as_secs().try_into().unwrap_or(i64::MAX),
+                nanos: x.subsec_nanos(),
+            },
+            Err(x) => {
+                let x = x.duration();
+                let secs = x.as_secs().try_into().unwrap_or(i64::MAX);
+                match x.subsec_nanos() {
+                    0 => Self {
+                        secs: -secs,
+                        nanos: 0,
+                    },
+                    nanos => Self {
+                        secs: -secs - 1,
+                        nanos: NANOS_PER_SEC - nanos,
+                    },
+                }
+            }
+        }
+    }
+}
+impl From<FfiTimestamp> for SystemTime {
+    /// ## Panics
+    /// Panics if the platform can't represent the timestamp. See
+    /// [FfiTimestamp::to_system_time] for a non-panicking version.
+    fn from(value: FfiTimestamp) -> Self {
+        value
+            .to_system_time()
+            .expect("timestamp out of range for SystemTime")
+    }
+}
@@ -0,0 +1,284 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    fmt::Debug,
+    mem::{forget, take},
+    ptr::{self, null_mut},
+    slice,
+};
+
+use super::slice::{FfiSlice, FfiSliceMut};
+
+unsafe extern "C" fn __libcommons_rust_drop_vec<T>(vec: *mut FfiVec<T>) {
+    unsafe {
+        let vec = vec.as_mut().unwrap();
+        drop(Vec::from_raw_parts(vec.buf, vec.len, vec.capacity));
+    }
+}
+
+/// An FFI-compatible growable vector.
+///
+/// The buffer is freed with the `drop` function stored alongside it, so
+/// a vector created on either side of FFI can be dropped on the other.
+/// Vectors that were not allocated by Rust are moved into a new buffer
+/// when they need to grow.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::vec::FfiVec;
+///
+/// let mut vec = FfiVec::from(vec![1, 2]);
+/// vec.push(3);
+/// vec.extend([4, 5]);
+/// assert_eq!(vec.as_slice(), &[1, 2, 3, 4, 5]);
+/// assert_eq!(vec.into_vec(), vec![1, 2, 3, 4, 5]);
+/// ```
+#[repr(C)]
+pub struct FfiVec<T> {
+    pub(super) buf: *mut T,
+    pub(super) len: usize,
+    pub(super) capacity: usize,
+    pub(super) drop: Option<unsafe extern "C" fn(*mut FfiVec<T>)>,
+}
+impl<T> FfiVec<T> {
+    /// Create a new FfiVec.
+    ///
+    /// This method will not allocate.
+    ///
+    /// ```
+    /// use libcommons::ffi::vec::FfiVec;
+    ///
+    /// let vec = FfiVec::<u32>::new();
+    /// assert!(vec.is_empty());
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            buf: null_mut(),
+            len: 0,
+            capacity: 0,
+            drop: None,
+        }
+    }
+
+    /// Create a new FfiVec with specified capacity.
+    ///
+    /// This method will not allocate if `len` is 0.
+    ///
+    /// ```
+    /// use libcommons::ffi::vec::FfiVec;
+    ///
+    /// let vec = FfiVec::<u32>::with_capacity(16);
+    /// assert!(vec.capacity() >= 16);
+    /// ```
+    pub fn with_capacity(len: usize) -> Self {
+        Vec::with_capacity(len).into()
+    }
+
+    /// Get the number of elements.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if vector is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Obtain this vector's capacity.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get a slice of the elements.
+    pub const fn as_slice(&self) -> &[T] {
+        if self.buf.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.buf, self.len) }
+        }
+    }
+
+    /// Get a mutable slice of the elements.
+    pub const fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.buf.is_null() {
+            &mut []
+        } else {
+            unsafe { slice::from_raw_parts_mut(self.buf, self.len) }
+        }
+    }
+
+    /// Get a wide pointer to the elements.
+    pub const fn as_ffi_slice(&self) -> FfiSlice<'_, T> {
+        FfiSlice::new(self.as_slice())
+    }
+
+    /// Get a mutable wide pointer to the elements.
+    pub const fn as_ffi_slice_mut(&mut self) -> FfiSliceMut<'_, T> {
+        FfiSliceMut::new(self.as_mut_slice())
+    }
+
+    /// Append an element.
+    ///
+    /// Will re-allocate the internal buffer if the vector is full.
+    ///
+    /// ```
+    /// use libcommons::ffi::vec::FfiVec;
+    ///
+    /// let mut vec = FfiVec::new();
+    /// vec.push("a");
+    /// vec.push("b");
+    /// assert_eq!(vec.as_slice(), &["a", "b"]);
+    /// ```
+    pub fn push(&mut self, value: T) {
+        if self.len == self.capacity {
+            self.grow(1);
+        }
+        unsafe {
+            self.buf.add(self.len).write(value);
+        }
+        self.len += 1;
+    }
+
+    /// Remove the last element.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe { Some(self.buf.add(self.len).read()) }
+    }
+
+    /// Convert this [FfiVec] into a [Vec].
+    ///
+    /// Elements are moved into a new buffer, since this one may have
+    /// been allocated outside of Rust.
+    pub fn into_vec(mut self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.len);
+        unsafe {
+            if !self.buf.is_null() {
+                ptr::copy_nonoverlapping(self.buf, vec.as_mut_ptr(), self.len);
+                vec.set_len(self.len);
+            }
+        }
+        // Elements are moved, so only the buffer is freed.
+        self.len = 0;
+        vec
+    }
+
+    /// Re-allocate the buffer to fit at least `additional` more elements.
+    fn grow(&mut self, additional: usize) {
+        let mut vec = take(self).into_vec();
+        vec.reserve(additional.max(vec.capacity()));
+        *self = vec.into();
+    }
+}
+impl<T> Drop for FfiVec<T> {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.buf.is_null()
+                && let Some(drop) = self.drop
+            {
+                drop(self);
+            }
+        }
+    }
+}
+impl<T> Default for FfiVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Clone> Clone for FfiVec<T> {
+    fn clone(&self) -> Self {
+        self.as_slice().to_vec().into()
+    }
+}
+impl<T: Debug> Debug for FfiVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+impl<T: PartialEq> PartialEq for FfiVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+impl<T: Eq> Eq for FfiVec<T> {}
+impl<T: PartialEq> PartialEq<[T]> for FfiVec<T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+impl<T: PartialEq> PartialEq<&[T]> for FfiVec<T> {
+    fn eq(&self, other: &&[T]) -> bool {
+        self.as_slice() == *other
+    }
+}
+impl<T> From<Vec<T>> for FfiVec<T> {
+    fn from(mut vec: Vec<T>) -> Self {
+        let ffi = Self {
+            buf: if vec.capacity() == 0 {
+                null_mut()
+            } else {
+                vec.as_mut_ptr()
+            },
+            len: vec.len(),
+            capacity: vec.capacity(),
+            drop: (vec.capacity() != 0).then_some(__libcommons_rust_drop_vec::<T>),
+        };
+        forget(vec);
+        ffi
+    }
+}
+impl<T: Clone> From<&[T]> for FfiVec<T> {
+    fn from(value: &[T]) -> Self {
+        value.to_vec().into()
+    }
+}
+impl<T> From<FfiVec<T>> for Vec<T> {
+    fn from(value: FfiVec<T>) -> Self {
+        value.into_vec()
+    }
+}
+impl<T> FromIterator<T> for FfiVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
+impl<T> Extend<T> for FfiVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let additional = iter.size_hint().0;
+        if self.capacity - self.len < additional {
+            self.grow(additional);
+        }
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+impl<'a, T: Copy + 'a> Extend<&'a T> for FfiVec<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+impl<T> AsRef<[T]> for FfiVec<T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+impl<T> AsMut<[T]> for FfiVec<T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+impl<T> Borrow<[T]> for FfiVec<T> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+impl<T> BorrowMut<[T]> for FfiVec<T> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
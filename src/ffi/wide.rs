@@ -0,0 +1,338 @@
+use std::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    marker::PhantomData,
+    slice,
+    string::FromUtf16Error,
+};
+
+#[cfg(windows)]
+use std::{
+    ffi::{OsStr, OsString},
+    os::windows::ffi::{OsStrExt, OsStringExt},
+};
+
+use super::vec::FfiVec;
+
+/// A wide pointer to an FFI-compatible UTF-16 string slice.
+///
+/// Like strings on Windows, contents are not required to be valid
+/// UTF-16, and are not NUL-terminated.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::wide::FfiWideStr;
+///
+/// let units: Vec<u16> = "héllo".encode_utf16().collect();
+/// let ptr = FfiWideStr::new(&units);
+/// assert_eq!(ptr.len(), 5);
+/// assert_eq!(ptr.to_string().unwrap(), "héllo");
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct FfiWideStr<'a> {
+    pub(super) buf: *const u16,
+    pub(super) len: usize,
+    _phantom: PhantomData<&'a [u16]>,
+}
+impl<'a> FfiWideStr<'a> {
+    /// Make a UTF-16 string passable via ffi.
+    pub const fn new(units: &'a [u16]) -> Self {
+        Self {
+            buf: units.as_ptr(),
+            len: units.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create an [FfiWideStr] referencing the buffer.
+    ///
+    /// ## Safety
+    /// Provided pointer must either be null or point to `len` valid
+    /// code units for the entire time that this [FfiWideStr] will be used.
+    pub const unsafe fn from_raw_parts(buf: *const u16, len: usize) -> Self {
+        Self {
+            buf,
+            len,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create an [FfiWideStr] referencing a NUL-terminated string, without
+    /// the terminator.
+    ///
+    /// ```
+    /// use libcommons::ffi::wide::FfiWideStr;
+    ///
+    /// let units = [b'h' as u16, b'i' as u16, 0];
+    /// let ptr = unsafe { FfiWideStr::from_ptr(units.as_ptr()) };
+    /// assert_eq!(ptr.as_slice(), &units[..2]);
+    /// ```
+    ///
+    /// ## Safety
+    /// Provided pointer must point to a NUL-terminated buffer that is
+    /// valid for the entire time that this [FfiWideStr] will be used.
+    pub unsafe fn from_ptr(buf: *const u16) -> Self {
+        let mut len = 0;
+        while unsafe { buf.add(len).read() } != 0 {
+            len += 1;
+        }
+        unsafe { Self::from_raw_parts(buf, len) }
+    }
+
+    /// Obtain the underlying pointer.
+    pub const fn as_ptr(&self) -> *const u16 {
+        self.buf
+    }
+
+    /// Get length of this [FfiWideStr] in code units.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether this [FfiWideStr] is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the code units of this string.
+    pub const fn as_slice(&self) -> &'a [u16] {
+        if self.buf.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.buf, self.len) }
+        }
+    }
+
+    /// Decode this string.
+    ///
+    /// ## Errors
+    /// Fails if the string is not valid UTF-16.
+    pub fn to_string(&self) -> Result<String, FromUtf16Error> {
+        String::from_utf16(self.as_slice())
+    }
+
+    /// Decode this string, replacing invalid data with
+    /// [char::REPLACEMENT_CHARACTER].
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf16_lossy(self.as_slice())
+    }
+
+    /// Copy this string into an [OsString].
+    #[cfg(windows)]
+    pub fn to_os_string(&self) -> OsString {
+        OsString::from_wide(self.as_slice())
+    }
+
+    /// Clone this [FfiWideStr] into an [FfiWideString].
+    pub fn to_ffi_wide_string(&self) -> FfiWideString {
+        self.as_slice().into()
+    }
+}
+impl Debug for FfiWideStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+impl PartialEq for FfiWideStr<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+impl Eq for FfiWideStr<'_> {}
+impl AsRef<[u16]> for FfiWideStr<'_> {
+    fn as_ref(&self) -> &[u16] {
+        self.as_slice()
+    }
+}
+impl<'a> From<&'a [u16]> for FfiWideStr<'a> {
+    fn from(value: &'a [u16]) -> Self {
+        Self::new(value)
+    }
+}
+impl<'a> From<&'a FfiWideString> for FfiWideStr<'a> {
+    fn from(value: &'a FfiWideString) -> Self {
+        value.as_ptr()
+    }
+}
+
+/// An FFI-compatible owned UTF-16 string.
+///
+/// Like strings on Windows, contents are not required to be valid
+/// UTF-16. Use [FfiWideString::as_slice_with_nul] to pass it to
+/// functions expecting NUL-terminated strings.
+///
+/// See `libcommons.h`.
+///
+/// ```
+/// use libcommons::ffi::wide::FfiWideString;
+///
+/// let mut string = FfiWideString::from("Hello");
+/// string.push_str(", world");
+/// assert_eq!(string.to_string().unwrap(), "Hello, world");
+/// assert_eq!(string.as_slice_with_nul().last(), Some(&0));
+/// assert_eq!(string.len(), 12);
+/// ```
+#[repr(transparent)]
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct FfiWideString(pub(super) FfiVec<u16>);
+impl FfiWideString {
+    /// Create a new FfiWideString.
+    ///
+    /// This method will not allocate.
+    pub const fn new() -> Self {
+        Self(FfiVec::new())
+    }
+
+    /// Create a new FfiWideString with capacity for `len` code units.
+    ///
+    /// This method will not allocate if `len` is 0.
+    pub fn with_capacity(len: usize) -> Self {
+        Self(FfiVec::with_capacity(len))
+    }
+
+    /// Get the length of this string in code units.
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if string is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Obtain this string's capacity in code units.
+    pub const fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Get the code units of this string.
+    pub const fn as_slice(&self) -> &[u16] {
+        self.0.as_slice()
+    }
+
+    /// Make this string passable via ffi.
+    pub const fn as_ptr(&self) -> FfiWideStr<'_> {
+        FfiWideStr::new(self.as_slice())
+    }
+
+    /// Append a string.
+    pub fn push_str(&mut self, str: &str) {
+        self.0.extend(str.encode_utf16());
+    }
+
+    /// Append UTF-16 code units.
+    pub fn push_wide(&mut self, units: &[u16]) {
+        self.0.extend(units);
+    }
+
+    /// Write a NUL terminator after the string.
+    ///
+    /// The terminator is not counted in the length, so pushing more data
+    /// overwrites it.
+    ///
+    /// Will re-allocate the internal buffer if there's no space for it.
+    pub fn push_nul(&mut self) {
+        self.0.push(0);
+        self.0.len -= 1;
+    }
+
+    /// Get the code units of this string followed by a NUL terminator.
+    ///
+    /// Writes the terminator with [FfiWideString::push_nul].
+    pub fn as_slice_with_nul(&mut self) -> &[u16] {
+        self.push_nul();
+        unsafe { slice::from_raw_parts(self.0.buf, self.0.len + 1) }
+    }
+
+    /// Decode this string.
+    ///
+    /// ## Errors
+    /// Fails if the string is not valid UTF-16.
+    pub fn to_string(&self) -> Result<String, FromUtf16Error> {
+        self.as_ptr().to_string()
+    }
+
+    /// Decode this string, replacing invalid data with
+    /// [char::REPLACEMENT_CHARACTER].
+    pub fn to_string_lossy(&self) -> String {
+        self.as_ptr().to_string_lossy()
+    }
+
+    /// Copy this string into an [OsString].
+    #[cfg(windows)]
+    pub fn to_os_string(&self) -> OsString {
+        self.as_ptr().to_os_string()
+    }
+}
+impl Debug for FfiWideString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.as_ptr(), f)
+    }
+}
+impl PartialEq<&str> for FfiWideString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_slice().iter().copied().eq(other.encode_utf16())
+    }
+}
+impl fmt::Write for FfiWideString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+impl From<&str> for FfiWideString {
+    fn from(value: &str) -> Self {
+        value.encode_utf16().collect::<Vec<_>>().into()
+    }
+}
+impl From<String> for FfiWideString {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+impl From<&[u16]> for FfiWideString {
+    fn from(value: &[u16]) -> Self {
+        Self(value.into())
+    }
+}
+impl From<Vec<u16>> for FfiWideString {
+    fn from(value: Vec<u16>) -> Self {
+        Self(value.into())
+    }
+}
+impl From<FfiWideStr<'_>> for FfiWideString {
+    fn from(value: FfiWideStr<'_>) -> Self {
+        value.to_ffi_wide_string()
+    }
+}
+#[cfg(windows)]
+impl From<&OsStr> for FfiWideString {
+    fn from(value: &OsStr) -> Self {
+        value.encode_wide().collect::<Vec<_>>().into()
+    }
+}
+#[cfg(windows)]
+impl From<FfiWideString> for OsString {
+    fn from(value: FfiWideString) -> Self {
+        value.to_os_string()
+    }
+}
+impl TryFrom<FfiWideString> for String {
+    type Error = FromUtf16Error;
+
+    fn try_from(value: FfiWideString) -> Result<Self, Self::Error> {
+        value.to_string()
+    }
+}
+impl AsRef<[u16]> for FfiWideString {
+    fn as_ref(&self) -> &[u16] {
+        self.as_slice()
+    }
+}
+impl Borrow<[u16]> for FfiWideString {
+    fn borrow(&self) -> &[u16] {
+        self.as_slice()
+    }
+}
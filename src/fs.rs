@@ -1,5 +1,11 @@
 use std::path::PathBuf;
 
+#[cfg(feature = "dirs")]
+use std::{
+    fs::DirBuilder,
+    io::{self, ErrorKind},
+};
+
 #[cfg(feature = "dirs")]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DirRel {
@@ -11,10 +17,25 @@ pub enum DirRel {
 #[cfg(feature = "dirs")]
 impl DirRel {
     pub fn dir(self, ty: DirType) -> Option<PathBuf> {
-        #[cfg(unix)]
+        #[cfg(target_os = "macos")]
+        {
+            crate::os::macos::dirs::dir(self, ty)
+        }
+        #[cfg(all(unix, not(target_os = "macos")))]
         {
             crate::os::unix::dirs::dir(self, ty)
         }
+        #[cfg(windows)]
+        {
+            crate::os::windows::dirs::dir(self, ty)
+        }
+    }
+
+    /// Like [DirRel::dir], but create the directory if it's missing.
+    ///
+    /// See [DirType::dir_create].
+    pub fn dir_create(self, ty: DirType) -> io::Result<PathBuf> {
+        ty.dir_create(self)
     }
 }
 
@@ -46,9 +67,146 @@ pub enum DirType {
 #[cfg(feature = "dirs")]
 impl DirType {
     pub fn dir(self, rel: DirRel) -> Option<PathBuf> {
-        #[cfg(unix)]
+        #[cfg(target_os = "macos")]
+        {
+            crate::os::macos::dirs::dir(rel, self)
+        }
+        #[cfg(all(unix, not(target_os = "macos")))]
         {
             crate::os::unix::dirs::dir(rel, self)
         }
+        #[cfg(windows)]
+        {
+            crate::os::windows::dirs::dir(rel, self)
+        }
+    }
+
+    /// Like [DirType::dir], but create the directory if it's missing.
+    ///
+    /// [DirType::Runtime] dirs are only accessible by their owner.
+    ///
+    /// ## Errors
+    /// Fails with [ErrorKind::NotFound] if there's no such dir on this
+    /// platform, or with the error of creating the directory.
+    pub fn dir_create(self, rel: DirRel) -> io::Result<PathBuf> {
+        create(self.dir(rel), self == DirType::Runtime)
+    }
+}
+
+/// Directories of an application.
+///
+/// Appends an application-specific path to user [DirType] dirs,
+/// following each platform's conventions:
+///
+/// - Windows: `{org}\{app}`, with a subdirectory for each kind of data,
+///   since some of them share the same base dir.
+/// - macOS: `{qualifier}.{org}.{app}`, with spaces replaced by `-`.
+/// - Other platforms: `{app}`, lowercase and without spaces.
+///
+/// ```
+/// use libcommons::fs::AppDirs;
+///
+/// let dirs = AppDirs::new("com", "Example Corp", "My App");
+/// if cfg!(target_os = "linux") {
+///     assert!(dirs.config_dir().unwrap().ends_with(".config/myapp"));
+/// }
+/// ```
+#[cfg(feature = "dirs")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppDirs {
+    path: PathBuf,
+}
+#[cfg(feature = "dirs")]
+impl AppDirs {
+    /// Create dirs for an application.
+    ///
+    /// `qualifier` is the top-level domain of the organization, like
+    /// `com` or `org`.
+    #[allow(unused_variables)]
+    pub fn new(qualifier: &str, org: &str, app: &str) -> Self {
+        #[cfg(windows)]
+        let path = PathBuf::from(org).join(app);
+        #[cfg(target_os = "macos")]
+        let path = PathBuf::from(format!("{qualifier}.{org}.{app}").replace(' ', "-"));
+        #[cfg(not(any(windows, target_os = "macos")))]
+        let path = PathBuf::from(
+            app.chars()
+                .filter(|x| !x.is_whitespace())
+                .flat_map(char::to_lowercase)
+                .collect::<String>(),
+        );
+
+        Self { path }
     }
+
+    /// Get the application configuration directory.
+    pub fn config_dir(&self) -> Option<PathBuf> {
+        self.dir(DirType::Config, "config")
+    }
+
+    /// Like [AppDirs::config_dir], but create the directory if it's
+    /// missing.
+    pub fn config_dir_create(&self) -> io::Result<PathBuf> {
+        create(self.config_dir(), false)
+    }
+
+    /// Get the temporary application data directory.
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        self.dir(DirType::Cache, "cache")
+    }
+
+    /// Like [AppDirs::cache_dir], but create the directory if it's
+    /// missing.
+    pub fn cache_dir_create(&self) -> io::Result<PathBuf> {
+        create(self.cache_dir(), false)
+    }
+
+    /// Get the user data directory.
+    pub fn data_dir(&self) -> Option<PathBuf> {
+        self.dir(DirType::Share, "data")
+    }
+
+    /// Like [AppDirs::data_dir], but create the directory if it's
+    /// missing.
+    pub fn data_dir_create(&self) -> io::Result<PathBuf> {
+        create(self.data_dir(), false)
+    }
+
+    /// Get the permanent application data directory.
+    pub fn state_dir(&self) -> Option<PathBuf> {
+        self.dir(DirType::State, "state")
+    }
+
+    /// Like [AppDirs::state_dir], but create the directory if it's
+    /// missing.
+    pub fn state_dir_create(&self) -> io::Result<PathBuf> {
+        create(self.state_dir(), false)
+    }
+
+    fn dir(&self, ty: DirType, _windows_subdir: &str) -> Option<PathBuf> {
+        let dir = ty.dir(DirRel::User)?.join(&self.path);
+        #[cfg(windows)]
+        let dir = dir.join(_windows_subdir);
+        Some(dir)
+    }
+}
+
+/// Create a directory, returning its path.
+#[cfg(feature = "dirs")]
+#[allow(unused_variables)]
+fn create(dir: Option<PathBuf>, private: bool) -> io::Result<PathBuf> {
+    let dir =
+        dir.ok_or_else(|| io::Error::new(ErrorKind::NotFound, "no such dir on this platform"))?;
+
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::DirBuilderExt;
+
+        builder.mode(0o700);
+    }
+    builder.create(&dir)?;
+
+    Ok(dir)
 }
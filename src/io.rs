@@ -1,7 +1,153 @@
-use std::io::{self, BufReader, Read, Write};
+use std::{
+    io::{self, BufReader, IoSlice, IoSliceMut, Read, Write},
+    mem::ManuallyDrop,
+    ops::ControlFlow,
+    ptr,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
+use chunks::Chunks;
+use find::StreamFind;
+use newline::NormalizeNewlines;
+use progress::{PipeProgress, ProgressTracker};
+use retry::{Backoff, RetryInterrupted, RetryPolicy, RetryWouldBlock, WriteAllError};
+use take::TakeStrict;
+use tee::TeeReader;
+use throttle::Throttle;
+use timeout::{ReadTimeout, TimeoutReader};
+
+pub mod base64;
+pub mod chain;
+pub mod chunks;
+pub mod crc;
+pub mod duplex;
+pub mod find;
+pub mod flaky;
+#[cfg(feature = "async")]
+pub mod futures;
+pub mod hash;
+pub mod hexdump;
+pub mod iter;
+#[cfg(feature = "str")]
+pub mod lines;
+#[cfg(all(feature = "mmap", any(unix, windows)))]
+pub mod mmap;
+pub mod newline;
+pub mod progress;
+pub mod record;
+pub mod retry;
+pub mod take;
+pub mod tee;
+pub mod throttle;
+pub mod timeout;
+
+pub use duplex::{pipe, pipe_bounded};
+
+macro_rules! endian_types {
+    ($mac:ident) => {
+        $mac! {
+            u16 => read_u16_le read_u16_be write_u16_le write_u16_be;
+            u32 => read_u32_le read_u32_be write_u32_le write_u32_be;
+            u64 => read_u64_le read_u64_be write_u64_le write_u64_be;
+            u128 => read_u128_le read_u128_be write_u128_le write_u128_be;
+            i16 => read_i16_le read_i16_be write_i16_le write_i16_be;
+            i32 => read_i32_le read_i32_be write_i32_le write_i32_be;
+            i64 => read_i64_le read_i64_be write_i64_le write_i64_be;
+            i128 => read_i128_le read_i128_be write_i128_le write_i128_be;
+            f32 => read_f32_le read_f32_be write_f32_le write_f32_be;
+            f64 => read_f64_le read_f64_be write_f64_le write_f64_be;
+        }
+    };
+}
+macro_rules! decl_read {
+    ($($ty:ty => $read_le:ident $read_be:ident $_write_le:ident $_write_be:ident;)*) => {$(
+        #[doc = concat!("Read a little-endian `", stringify!($ty), "`.")]
+        fn $read_le(&mut self) -> io::Result<$ty>;
+        #[doc = concat!("Read a big-endian `", stringify!($ty), "`.")]
+        fn $read_be(&mut self) -> io::Result<$ty>;
+    )*};
+}
+macro_rules! impl_read {
+    ($($ty:ty => $read_le:ident $read_be:ident $_write_le:ident $_write_be:ident;)*) => {$(
+        fn $read_le(&mut self) -> io::Result<$ty> {
+            let mut buf = [0u8; size_of::<$ty>()];
+            self.read_exact(&mut buf)?;
+            Ok(<$ty>::from_le_bytes(buf))
+        }
+        fn $read_be(&mut self) -> io::Result<$ty> {
+            let mut buf = [0u8; size_of::<$ty>()];
+            self.read_exact(&mut buf)?;
+            Ok(<$ty>::from_be_bytes(buf))
+        }
+    )*};
+}
+macro_rules! decl_write {
+    ($($ty:ty => $_read_le:ident $_read_be:ident $write_le:ident $write_be:ident;)*) => {$(
+        #[doc = concat!("Write a little-endian `", stringify!($ty), "`.")]
+        fn $write_le(&mut self, value: $ty) -> io::Result<()>;
+        #[doc = concat!("Write a big-endian `", stringify!($ty), "`.")]
+        fn $write_be(&mut self, value: $ty) -> io::Result<()>;
+    )*};
+}
+macro_rules! impl_write {
+    ($($ty:ty => $_read_le:ident $_read_be:ident $write_le:ident $write_be:ident;)*) => {$(
+        fn $write_le(&mut self, value: $ty) -> io::Result<()> {
+            self.write_all(&value.to_le_bytes())
+        }
+        fn $write_be(&mut self, value: $ty) -> io::Result<()> {
+            self.write_all(&value.to_be_bytes())
+        }
+    )*};
+}
+
+/// Extension methods for readers.
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::io::Cursor;
+///
+/// let mut header = Cursor::new([0x89, 0x50, 0x4E, 0x47, 0x01, 0x00, 0x00, 0x00, 0x2A]);
+/// assert_eq!(header.read_u32_be().unwrap(), 0x89504E47);
+/// assert_eq!(header.read_u32_le().unwrap(), 1);
+/// assert_eq!(header.read_i8().unwrap(), 42);
+/// assert!(header.read_u8().is_err());
+/// ```
 pub trait ReadExt: Read {
     /// Pipe all contents of self into provided writer.
+    ///
+    /// Data is kept in a ring buffer of `BUF` bytes. When it wraps around,
+    /// both parts are passed to [Write::write_vectored] and
+    /// [Read::read_vectored] at once.
+    ///
+    /// ```
+    /// use libcommons::prelude::*;
+    /// use std::io::{self, Cursor, IoSlice, Write};
+    ///
+    /// // Accepts at most 3 bytes per call.
+    /// struct Slow(Vec<u8>);
+    /// impl Write for Slow {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         let len = buf.len().min(3);
+    ///         self.0.extend_from_slice(&buf[..len]);
+    ///         Ok(len)
+    ///     }
+    ///     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+    ///         let mut len = 0;
+    ///         for buf in bufs {
+    ///             len += self.write(&buf[..buf.len().min(3 - len)])?;
+    ///         }
+    ///         Ok(len)
+    ///     }
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut out = Slow(Vec::new());
+    /// Cursor::new(b"hello, world").pipe::<4, _>(&mut out).unwrap();
+    /// assert_eq!(out.0, b"hello, world");
+    /// ```
     fn pipe<const BUF: usize, W>(&mut self, write: W) -> io::Result<()>
     where
         W: Write;
@@ -12,6 +158,123 @@ pub trait ReadExt: Read {
         W: Write,
         F: FnMut(u64);
 
+    /// Pipe contents of self into provided writer until `cb` says to stop.
+    ///
+    /// `cb` is called with the number of transferred bytes after every
+    /// write. Returning [ControlFlow::Break] stops the transfer.
+    ///
+    /// Returns the number of transferred bytes.
+    ///
+    /// ```
+    /// use libcommons::prelude::*;
+    /// use std::{io::Cursor, ops::ControlFlow};
+    ///
+    /// let mut out = Vec::new();
+    /// let len = Cursor::new([1u8; 100])
+    ///     .pipe_until::<16, _, _>(&mut out, |len| {
+    ///         if len >= 32 {
+    ///             ControlFlow::Break(())
+    ///         } else {
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(len, 32);
+    /// assert_eq!(out.len(), 32);
+    /// ```
+    fn pipe_until<const BUF: usize, W, F>(&mut self, write: W, cb: F) -> io::Result<u64>
+    where
+        W: Write,
+        F: FnMut(u64) -> ControlFlow<()>;
+
+    /// Pipe all contents of self into provided writer, using `buf` as
+    /// the buffer.
+    ///
+    /// Useful when the buffer size is only known at runtime.
+    ///
+    /// Returns the number of transferred bytes.
+    ///
+    /// ```
+    /// use libcommons::prelude::*;
+    /// use std::io::Cursor;
+    ///
+    /// let mut out = Vec::new();
+    /// let mut buf = vec![0u8; 3];
+    /// let len = Cursor::new(b"hello").pipe_buf(&mut out, &mut buf).unwrap();
+    /// assert_eq!(len, 5);
+    /// assert_eq!(out, b"hello");
+    /// ```
+    ///
+    /// ## Errors
+    /// Returns [io::ErrorKind::InvalidInput] if `buf` is empty.
+    fn pipe_buf<W>(&mut self, write: W, buf: &mut [u8]) -> io::Result<u64>
+    where
+        W: Write;
+
+    /// Pipe all contents of self into provided writer, using a heap buffer
+    /// of `capacity` bytes.
+    ///
+    /// See [ReadExt::pipe_buf].
+    fn pipe_vec<W>(&mut self, write: W, capacity: usize) -> io::Result<u64>
+    where
+        W: Write;
+
+    /// Pipe all contents of self into provided writer, reporting progress.
+    ///
+    /// `cb` is called after every write with [PipeProgress], which
+    /// includes a smoothed transfer rate.
+    ///
+    /// Returns the number of transferred bytes.
+    ///
+    /// ```
+    /// use libcommons::prelude::*;
+    /// use std::io::Cursor;
+    ///
+    /// let mut last = None;
+    /// let len = Cursor::new([0u8; 100])
+    ///     .pipe_progress::<16, _, _>(std::io::sink(), |x| last = Some(x))
+    ///     .unwrap();
+    ///
+    /// let last = last.unwrap();
+    /// assert_eq!(len, 100);
+    /// assert_eq!(last.transferred, 100);
+    /// assert_eq!(last.last_chunk, 4);
+    /// ```
+    fn pipe_progress<const BUF: usize, W, F>(&mut self, write: W, cb: F) -> io::Result<u64>
+    where
+        W: Write,
+        F: FnMut(PipeProgress);
+
+    /// Pipe contents of self into provided writer until `cancel` is set.
+    ///
+    /// `cancel` is checked before the transfer and after every write,
+    /// so it can be set from another thread to stop a long copy.
+    ///
+    /// Returns the number of transferred bytes.
+    ///
+    /// ```
+    /// use libcommons::prelude::*;
+    /// use std::{io::Cursor, sync::atomic::AtomicBool};
+    ///
+    /// let mut out = Vec::new();
+    /// let len = Cursor::new(b"data")
+    ///     .pipe_cancellable::<16, _>(&mut out, &AtomicBool::new(true))
+    ///     .unwrap();
+    /// assert_eq!(len, 0);
+    ///
+    /// let len = Cursor::new(b"data")
+    ///     .pipe_cancellable::<16, _>(&mut out, &AtomicBool::new(false))
+    ///     .unwrap();
+    /// assert_eq!(len, 4);
+    /// ```
+    fn pipe_cancellable<const BUF: usize, W>(
+        &mut self,
+        write: W,
+        cancel: &AtomicBool,
+    ) -> io::Result<u64>
+    where
+        W: Write;
+
     /// Convert this reader into [std::io::BufReader] with default capacity.
     ///
     /// This does the same thing as `BufReader::new(self)`.
@@ -27,6 +290,96 @@ pub trait ReadExt: Read {
     /// This does the same thing as `PreRead::new(self)`.
     fn pre<const LEN: usize>(self) -> PreRead<LEN, Self>;
 
+    /// Read at most `limit` bytes, failing if there is more.
+    ///
+    /// See [TakeStrict].
+    fn take_strict(self, limit: u64) -> TakeStrict<Self>
+    where
+        Self: Sized;
+
+    /// Iterate over chunks of `N` bytes.
+    ///
+    /// See [Chunks].
+    fn read_chunks<const N: usize>(self) -> Chunks<N, Self>
+    where
+        Self: Sized;
+
+    /// Read all bytes until EOF, failing if there are more than `max`.
+    ///
+    /// ```
+    /// use libcommons::prelude::*;
+    /// use std::io::{Cursor, ErrorKind};
+    ///
+    /// assert_eq!(Cursor::new(b"data").read_to_vec_limited(4).unwrap(), b"data");
+    ///
+    /// let err = Cursor::new(b"more data").read_to_vec_limited(4).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidData);
+    /// ```
+    ///
+    /// ## Errors
+    /// Returns [io::ErrorKind::InvalidData] if the stream is longer than
+    /// `max`. See [TakeStrict].
+    fn read_to_vec_limited(&mut self, max: usize) -> io::Result<Vec<u8>>;
+
+    /// Find all occurrences of `pattern` in this reader.
+    ///
+    /// See [StreamFind].
+    fn find(self, pattern: impl AsRef<[u8]>) -> StreamFind<Self>
+    where
+        Self: Sized;
+
+    /// Copy everything read from this reader into a writer.
+    ///
+    /// See [TeeReader].
+    fn tee<W>(self, write: W) -> TeeReader<Self, W>
+    where
+        W: Write,
+        Self: Sized;
+
+    /// Limit throughput of this reader to `bytes_per_sec`.
+    ///
+    /// See [Throttle].
+    fn throttle(self, bytes_per_sec: u64) -> Throttle<Self>
+    where
+        Self: Sized;
+
+    /// Convert `\r\n` and `\r` line endings into `\n`.
+    ///
+    /// See [NormalizeNewlines].
+    fn normalize_newlines(self) -> NormalizeNewlines<Self>
+    where
+        Self: Sized;
+
+    /// Fail reads that take longer than `timeout`.
+    ///
+    /// See [TimeoutReader].
+    fn timeout(self, timeout: Duration) -> TimeoutReader<Self>
+    where
+        Self: Sized + ReadTimeout;
+
+    /// Retry reads that fail with [io::ErrorKind::Interrupted].
+    ///
+    /// See [RetryInterrupted].
+    fn retry_interrupted(self) -> RetryInterrupted<Self>
+    where
+        Self: Sized;
+
+    /// Retry reads that fail with [io::ErrorKind::WouldBlock], sleeping
+    /// in-between.
+    ///
+    /// See [RetryWouldBlock].
+    fn retry_would_block(self, backoff: impl Into<Backoff>) -> RetryWouldBlock<Self>
+    where
+        Self: Sized;
+
+    /// Iterate over lines of this reader without allocating.
+    ///
+    /// See [lines::Lines].
+    #[cfg(feature = "str")]
+    fn stack_lines<const CAP: usize>(self) -> lines::Lines<CAP, Self>
+    where
+        Self: Sized;
+
     /// Convert this reader into [crate::str::utf8::Utf8].
     ///
     /// ## Non-blocking IO
@@ -37,7 +390,8 @@ pub trait ReadExt: Read {
     /// of replacement characters where should not have been.
     ///
     /// Consider using [.pre::<4>()](crate::io::PreRead) to make sure it doesn't
-    /// happen.
+    /// happen, or [.retry_would_block()](crate::io::retry::RetryWouldBlock) to
+    /// wait for data instead.
     ///
     /// ## Buffering
     /// While this is implemented for non-buffered readers, this
@@ -48,77 +402,123 @@ pub trait ReadExt: Read {
     fn into_utf8(self) -> crate::str::utf8::Utf8<Self>
     where
         Self: Sized;
+
+    /// Convert this reader into [crate::str::utf16::Utf16], detecting
+    /// byte order from the byte order mark.
+    #[cfg(feature = "str")]
+    fn into_utf16(self) -> crate::str::utf16::Utf16<Self>
+    where
+        Self: Sized;
+
+    /// Read a single byte.
+    fn read_u8(&mut self) -> io::Result<u8>;
+    /// Read a single signed byte.
+    fn read_i8(&mut self) -> io::Result<i8>;
+    endian_types!(decl_read);
+
+    /// Read an unsigned LEB128 varint, as used by protobuf.
+    ///
+    /// ```
+    /// use libcommons::prelude::*;
+    /// use std::io::{Cursor, ErrorKind};
+    ///
+    /// let mut data = Cursor::new([0xAC, 0x02, 0x7F]);
+    /// assert_eq!(data.read_varint_u64().unwrap(), 300);
+    /// assert_eq!(data.read_varint_u64().unwrap(), 127);
+    ///
+    /// let mut data = Cursor::new([0xFF; 11]);
+    /// let err = data.read_varint_u64().unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidData);
+    /// ```
+    ///
+    /// ## Errors
+    /// Returns [io::ErrorKind::InvalidData] if the value does not fit into
+    /// `u64`, and [io::ErrorKind::UnexpectedEof] if data ends mid-value.
+    fn read_varint_u64(&mut self) -> io::Result<u64>;
+    /// Read a zigzag-encoded signed LEB128 varint, as used by protobuf's
+    /// `sint64`.
+    ///
+    /// See [ReadExt::read_varint_u64].
+    fn read_varint_i64(&mut self) -> io::Result<i64>;
 }
 impl<T> ReadExt for T
 where
     T: Read,
 {
-    fn pipe<const BUF: usize, W>(&mut self, mut write: W) -> io::Result<()>
+    fn pipe<const BUF: usize, W>(&mut self, write: W) -> io::Result<()>
     where
         W: Write,
     {
-        let mut reader = Some(self);
-        let mut buf = [0u8; BUF];
-        let mut len = 0usize;
-        while len > 0 || reader.is_some() {
-            if len < buf.len()
-                && let Some(x) = &mut reader
-            {
-                match x.read(&mut buf[len..])? {
-                    0 => drop(reader.take()),
-                    l => len += l,
-                }
-            }
-            if len > 0 {
-                match write.write(&buf[..len])? {
-                    0 => {
-                        return Err(io::Error::new(
-                            io::ErrorKind::UnexpectedEof,
-                            "unexpected eof",
-                        ));
-                    }
-                    l => len -= l,
-                }
-            }
-        }
-        Ok(())
+        self.pipe_until::<BUF, _, _>(write, |_| ControlFlow::Continue(()))
+            .map(drop)
     }
 
-    fn pipe_with<const BUF: usize, W, F>(&mut self, mut write: W, mut cb: F) -> io::Result<()>
+    fn pipe_with<const BUF: usize, W, F>(&mut self, write: W, mut cb: F) -> io::Result<()>
     where
         W: Write,
         F: FnMut(u64),
     {
-        let mut reader = Some(self);
-        let mut buf = [0u8; BUF];
-        let mut len = 0usize;
-        let mut download = 0u64;
-        while len > 0 || reader.is_some() {
-            if len < buf.len()
-                && let Some(x) = &mut reader
-            {
-                match x.read(&mut buf[len..])? {
-                    0 => drop(reader.take()),
-                    l => len += l,
-                }
-            }
-            if len > 0 {
-                match write.write(&buf[..len])? {
-                    0 => {
-                        return Err(io::Error::new(
-                            io::ErrorKind::UnexpectedEof,
-                            "unexpected eof",
-                        ));
-                    }
-                    l => {
-                        len -= l;
-                        download += l as u64;
-                        cb(download);
-                    }
-                }
-            }
+        self.pipe_until::<BUF, _, _>(write, |len| {
+            cb(len);
+            ControlFlow::Continue(())
+        })
+        .map(drop)
+    }
+
+    fn pipe_until<const BUF: usize, W, F>(&mut self, write: W, cb: F) -> io::Result<u64>
+    where
+        W: Write,
+        F: FnMut(u64) -> ControlFlow<()>,
+    {
+        pipe_loop(self, write, &mut [0u8; BUF], cb)
+    }
+
+    fn pipe_buf<W>(&mut self, write: W, buf: &mut [u8]) -> io::Result<u64>
+    where
+        W: Write,
+    {
+        pipe_loop(self, write, buf, |_| ControlFlow::Continue(()))
+    }
+
+    fn pipe_vec<W>(&mut self, write: W, capacity: usize) -> io::Result<u64>
+    where
+        W: Write,
+    {
+        pipe_loop(self, write, &mut vec![0u8; capacity], |_| {
+            ControlFlow::Continue(())
+        })
+    }
+
+    fn pipe_progress<const BUF: usize, W, F>(&mut self, write: W, mut cb: F) -> io::Result<u64>
+    where
+        W: Write,
+        F: FnMut(PipeProgress),
+    {
+        let mut tracker = ProgressTracker::new();
+        self.pipe_until::<BUF, _, _>(write, |len| {
+            cb(tracker.update(len));
+            ControlFlow::Continue(())
+        })
+    }
+
+    fn pipe_cancellable<const BUF: usize, W>(
+        &mut self,
+        write: W,
+        cancel: &AtomicBool,
+    ) -> io::Result<u64>
+    where
+        W: Write,
+    {
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(0);
         }
-        Ok(())
+        self.pipe_until::<BUF, _, _>(write, |_| {
+            if cancel.load(Ordering::Relaxed) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
     }
 
     fn buf_default(self) -> BufReader<Self> {
@@ -133,6 +533,84 @@ where
         PreRead::new(self)
     }
 
+    fn take_strict(self, limit: u64) -> TakeStrict<Self>
+    where
+        Self: Sized,
+    {
+        TakeStrict::new(self, limit)
+    }
+
+    fn read_chunks<const N: usize>(self) -> Chunks<N, Self>
+    where
+        Self: Sized,
+    {
+        Chunks::new(self)
+    }
+
+    fn read_to_vec_limited(&mut self, max: usize) -> io::Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        TakeStrict::new(self, max as u64).read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn find(self, pattern: impl AsRef<[u8]>) -> StreamFind<Self>
+    where
+        Self: Sized,
+    {
+        StreamFind::new(self, pattern)
+    }
+
+    fn tee<W>(self, write: W) -> TeeReader<Self, W>
+    where
+        W: Write,
+        Self: Sized,
+    {
+        TeeReader::new(self, write)
+    }
+
+    fn throttle(self, bytes_per_sec: u64) -> Throttle<Self>
+    where
+        Self: Sized,
+    {
+        Throttle::new(self, bytes_per_sec)
+    }
+
+    fn normalize_newlines(self) -> NormalizeNewlines<Self>
+    where
+        Self: Sized,
+    {
+        NormalizeNewlines::new(self)
+    }
+
+    fn timeout(self, timeout: Duration) -> TimeoutReader<Self>
+    where
+        Self: Sized + ReadTimeout,
+    {
+        TimeoutReader::new(self, timeout)
+    }
+
+    fn retry_interrupted(self) -> RetryInterrupted<Self>
+    where
+        Self: Sized,
+    {
+        RetryInterrupted::new(self)
+    }
+
+    fn retry_would_block(self, backoff: impl Into<Backoff>) -> RetryWouldBlock<Self>
+    where
+        Self: Sized,
+    {
+        RetryWouldBlock::new(self, backoff)
+    }
+
+    #[cfg(feature = "str")]
+    fn stack_lines<const CAP: usize>(self) -> lines::Lines<CAP, Self>
+    where
+        Self: Sized,
+    {
+        lines::Lines::new(self)
+    }
+
     #[cfg(feature = "str")]
     fn into_utf8(self) -> crate::str::utf8::Utf8<Self>
     where
@@ -140,6 +618,245 @@ where
     {
         crate::str::utf8::Utf8::new(self)
     }
+
+    #[cfg(feature = "str")]
+    fn into_utf16(self) -> crate::str::utf16::Utf16<Self>
+    where
+        Self: Sized,
+    {
+        crate::str::utf16::Utf16::new(self)
+    }
+
+    fn read_u8(&mut self) -> io::Result<u8> {
+        let mut buf = [0u8];
+        self.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+    fn read_i8(&mut self) -> io::Result<i8> {
+        self.read_u8().map(|x| x as i8)
+    }
+    endian_types!(impl_read);
+
+    fn read_varint_u64(&mut self) -> io::Result<u64> {
+        let mut value = 0u64;
+        for i in 0..10 {
+            let byte = self.read_u8()?;
+            if i == 9 && byte > 1 {
+                break;
+            }
+            value |= ((byte & 0x7F) as u64) << (i * 7);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "varint is too long",
+        ))
+    }
+    fn read_varint_i64(&mut self) -> io::Result<i64> {
+        let value = self.read_varint_u64()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+}
+
+/// Extension methods for writers.
+///
+/// ```
+/// use libcommons::prelude::*;
+///
+/// let mut header = Vec::new();
+/// header.write_u32_be(0x89504E47).unwrap();
+/// header.write_u16_le(1).unwrap();
+/// header.write_f32_be(1.0).unwrap();
+/// assert_eq!(header, [0x89, 0x50, 0x4E, 0x47, 0x01, 0x00, 0x3F, 0x80, 0x00, 0x00]);
+/// ```
+pub trait WriteExt: Write {
+    /// Convert this writer into [PreWrite].
+    fn pre_write<const LEN: usize>(self) -> PreWrite<LEN, Self>
+    where
+        Self: Sized;
+
+    /// Write the whole buffer, retrying short writes and transient errors.
+    ///
+    /// See [RetryPolicy].
+    ///
+    /// ```
+    /// use libcommons::{
+    ///     io::retry::{Backoff, RetryPolicy},
+    ///     prelude::*,
+    /// };
+    /// use std::{
+    ///     io::{self, Write},
+    ///     time::Duration,
+    /// };
+    ///
+    /// // Blocks on every other write.
+    /// struct Busy(Vec<u8>, bool);
+    /// impl Write for Busy {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         self.1 = !self.1;
+    ///         if self.1 {
+    ///             return Err(io::ErrorKind::WouldBlock.into());
+    ///         }
+    ///         self.0.push(buf[0]);
+    ///         Ok(1)
+    ///     }
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let policy = RetryPolicy::new(1, Backoff::fixed(Duration::ZERO));
+    /// let mut writer = Busy(Vec::new(), false);
+    /// writer.write_all_retrying(b"hello", policy).unwrap();
+    /// assert_eq!(writer.0, b"hello");
+    ///
+    /// let policy = RetryPolicy::new(0, Backoff::fixed(Duration::ZERO));
+    /// let mut writer = Busy(Vec::new(), true);
+    /// let err = writer.write_all_retrying(b"hello", policy).unwrap_err();
+    /// assert_eq!(err.written, 1);
+    /// ```
+    ///
+    /// ## Errors
+    /// On failure, returns the error together with the number of bytes that
+    /// were written.
+    fn write_all_retrying(&mut self, buf: &[u8], policy: RetryPolicy) -> Result<(), WriteAllError>;
+
+    /// Write a single byte.
+    fn write_u8(&mut self, value: u8) -> io::Result<()>;
+    /// Write a single signed byte.
+    fn write_i8(&mut self, value: i8) -> io::Result<()>;
+    endian_types!(decl_write);
+
+    /// Write an unsigned LEB128 varint, as used by protobuf.
+    ///
+    /// ```
+    /// use libcommons::prelude::*;
+    ///
+    /// let mut data = Vec::new();
+    /// data.write_varint_u64(300).unwrap();
+    /// data.write_varint_i64(-2).unwrap();
+    /// assert_eq!(data, [0xAC, 0x02, 0x03]);
+    /// ```
+    fn write_varint_u64(&mut self, value: u64) -> io::Result<()>;
+    /// Write a zigzag-encoded signed LEB128 varint, as used by protobuf's
+    /// `sint64`.
+    fn write_varint_i64(&mut self, value: i64) -> io::Result<()>;
+}
+impl<T> WriteExt for T
+where
+    T: Write,
+{
+    fn pre_write<const LEN: usize>(self) -> PreWrite<LEN, Self>
+    where
+        Self: Sized,
+    {
+        PreWrite::new(self)
+    }
+
+    fn write_all_retrying(&mut self, buf: &[u8], policy: RetryPolicy) -> Result<(), WriteAllError> {
+        retry::write_all_retrying(self, buf, policy)
+    }
+
+    fn write_u8(&mut self, value: u8) -> io::Result<()> {
+        self.write_all(&[value])
+    }
+    fn write_i8(&mut self, value: i8) -> io::Result<()> {
+        self.write_all(&[value as u8])
+    }
+    endian_types!(impl_write);
+
+    fn write_varint_u64(&mut self, mut value: u64) -> io::Result<()> {
+        let mut buf = [0u8; 10];
+        let mut len = 0;
+        loop {
+            buf[len] = (value & 0x7F) as u8;
+            value >>= 7;
+            len += 1;
+            if value == 0 {
+                break;
+            }
+            buf[len - 1] |= 0x80;
+        }
+        self.write_all(&buf[..len])
+    }
+    fn write_varint_i64(&mut self, value: i64) -> io::Result<()> {
+        self.write_varint_u64(((value << 1) ^ (value >> 63)) as u64)
+    }
+}
+
+/// Shared loop of all pipe methods.
+fn pipe_loop<R, W, F>(read: &mut R, mut write: W, buf: &mut [u8], mut cb: F) -> io::Result<u64>
+where
+    R: Read + ?Sized,
+    W: Write,
+    F: FnMut(u64) -> ControlFlow<()>,
+{
+    if buf.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "pipe buffer is empty",
+        ));
+    }
+
+    // Data is stored in a ring buffer, starting at `head`.
+    let cap = buf.len();
+    let mut reader = Some(read);
+    let mut head = 0usize;
+    let mut len = 0usize;
+    let mut download = 0u64;
+    while len > 0 || reader.is_some() {
+        if len < cap
+            && let Some(x) = &mut reader
+        {
+            let tail = (head + len) % cap;
+            let result = if tail >= head {
+                let (start, end) = buf.split_at_mut(tail);
+                x.read_vectored(&mut [IoSliceMut::new(end), IoSliceMut::new(&mut start[..head])])
+            } else {
+                x.read(&mut buf[tail..head])
+            };
+            match result {
+                Ok(0) => drop(reader.take()),
+                Ok(l) => len += l,
+                Err(why) if why.kind() == io::ErrorKind::Interrupted => {}
+                Err(why) => return Err(why),
+            }
+        }
+        if len > 0 {
+            let end = head + len;
+            let result = if end <= cap {
+                write.write(&buf[head..end])
+            } else {
+                // Writers without vectored support only write the first slice.
+                let (start, data) = buf.split_at(head);
+                write.write_vectored(&[IoSlice::new(data), IoSlice::new(&start[..end - cap])])
+            };
+            match result {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "unexpected eof",
+                    ));
+                }
+                Ok(l) => {
+                    head = (head + l) % cap;
+                    len -= l;
+                    if len == 0 {
+                        head = 0;
+                    }
+                    download += l as u64;
+                    if cb(download).is_break() {
+                        break;
+                    }
+                }
+                Err(why) if why.kind() == io::ErrorKind::Interrupted => {}
+                Err(why) => return Err(why),
+            }
+        }
+    }
+    Ok(download)
 }
 
 /// Prefetched reader.
@@ -246,6 +963,7 @@ where
         if LEN != 0 {
             while read.len < LEN {
                 match read.read.read(&mut read.buffer[read.len..]) {
+                    Ok(0) => break,
                     Ok(len) => read.len += len,
                     Err(why) => {
                         read.error = Some(why);
@@ -302,6 +1020,91 @@ where
         Ok(())
     }
 
+    /// Copy buffered bytes into `buf` without consuming them.
+    ///
+    /// If there are fewer bytes buffered than requested, this will try
+    /// fetching more until `buf.len()` bytes (but at most `LEN`) are
+    /// buffered. Errors that occur while fetching are
+    /// stashed like with [Read::read].
+    ///
+    /// Returns the number of bytes copied.
+    ///
+    /// ```
+    /// use libcommons::prelude::*;
+    /// use std::io::{Cursor, Read};
+    ///
+    /// let mut reader = Cursor::new(b"%PDF-1.7 ...").pre::<8>();
+    /// let mut magic = [0u8; 5];
+    /// reader.peek_exact(&mut magic).unwrap();
+    /// assert_eq!(&magic, b"%PDF-");
+    ///
+    /// let mut data = String::new();
+    /// reader.read_to_string(&mut data).unwrap();
+    /// assert_eq!(data, "%PDF-1.7 ...");
+    ///
+    /// let mut short = Cursor::new(b"ab").pre::<8>();
+    /// assert_eq!(short.peek(&mut [0u8; 4]).unwrap(), 2);
+    /// assert!(short.peek_exact(&mut [0u8; 4]).is_err());
+    /// ```
+    ///
+    /// ## Errors
+    /// If the buffer is empty, the stashed error is returned.
+    pub fn peek(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.len < buf.len() && self.error.is_none() {
+            while self.len < buf.len().min(LEN) {
+                match self.read.read(&mut self.buffer[self.len..]) {
+                    Ok(0) => break,
+                    Ok(read_len) => {
+                        self.len += read_len;
+                    }
+                    Err(why) => {
+                        self.error = Some(why);
+                        break;
+                    }
+                }
+            }
+        }
+
+        if self.len == 0
+            && !buf.is_empty()
+            && let Some(why) = self.error.take()
+        {
+            return Err(why);
+        }
+
+        let copy_len = buf.len().min(self.len);
+        buf[..copy_len].copy_from_slice(&self.buffer[..copy_len]);
+        Ok(copy_len)
+    }
+
+    /// Fill `buf` with buffered bytes without consuming them.
+    ///
+    /// See [peek()](Self::peek).
+    ///
+    /// ## Errors
+    /// [io::ErrorKind::InvalidInput] is returned if `buf` is longer than `LEN`.
+    ///
+    /// If not enough bytes could be fetched, the stashed error is returned,
+    /// or [io::ErrorKind::UnexpectedEof] if there was none.
+    pub fn peek_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        if buf.len() > LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "peek is longer than PreRead buffer",
+            ));
+        }
+        if self.peek(buf)? < buf.len() {
+            return match self.error.take() {
+                Some(why) => Err(why),
+                None => Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "failed to fill the whole buffer",
+                )),
+            };
+        }
+        Ok(())
+    }
+
     /// Flush the ligering error and refetch.
     ///
     /// If an error has occured when fetching extra data,
@@ -432,3 +1235,186 @@ where
         Ok(())
     }
 }
+
+/// Buffered writer.
+///
+/// Collects writes in a stack-allocated buffer and writes them to the
+/// inner writer once the buffer is full. Buffered data is written out
+/// on [flush](Write::flush) and on drop.
+///
+/// If writing out the buffer fails after data was accepted, the error
+/// is stashed and returned from the next call instead.
+///
+/// ## 0-lengthed prewriter
+/// If `LEN` is 0, this has no effect and simply calls [std::io::Write::write] on the writer.
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::io::Write;
+///
+/// let mut out = Vec::new();
+/// {
+///     let mut writer = (&mut out).pre_write::<4>();
+///     writer.write_all(b"abc").unwrap();
+///     assert!(writer.inner().is_empty());
+///     writer.write_all(b"def").unwrap();
+///     assert_eq!(writer.inner().as_slice(), b"abcd");
+/// }
+/// assert_eq!(out, b"abcdef");
+/// ```
+pub struct PreWrite<const LEN: usize, W>
+where
+    W: Write,
+{
+    buffer: [u8; LEN],
+    len: usize,
+    error: Option<io::Error>,
+    write: W,
+}
+impl<const LEN: usize, W> PreWrite<LEN, W>
+where
+    W: Write,
+{
+    /// Create a new [PreWrite].
+    pub fn new(write: W) -> Self {
+        Self {
+            buffer: [0; LEN],
+            len: 0,
+            error: None,
+            write,
+        }
+    }
+
+    /// Flush the buffer and get the inner writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.flush()?;
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so `write` is read only once.
+        // Other fields hold no resources after a successful flush.
+        Ok(unsafe { ptr::read(&this.write) })
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn inner(&self) -> &W {
+        &self.write
+    }
+
+    /// Get a mutable reference to the inner writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+
+    /// Get data that was not written out yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+
+    /// Return the lingering error, if there is one.
+    pub fn flush_error(&mut self) -> io::Result<()> {
+        match self.error.take() {
+            None => Ok(()),
+            Some(why) => Err(why),
+        }
+    }
+
+    fn drain(&mut self) -> io::Result<()> {
+        while self.len > 0 {
+            match self.write.write(&self.buffer[..self.len]) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write the buffered data",
+                    ));
+                }
+                Ok(write_len) => {
+                    self.buffer.copy_within(write_len..self.len, 0);
+                    self.len -= write_len;
+                }
+                Err(why) if why.kind() == io::ErrorKind::Interrupted => {}
+                Err(why) => return Err(why),
+            }
+        }
+        Ok(())
+    }
+}
+impl<const LEN: usize, W> Write for PreWrite<LEN, W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if LEN == 0 {
+            return self.write.write(buf);
+        }
+
+        self.flush_error()?;
+        if self.len == LEN {
+            self.drain()?;
+        }
+
+        let copy_len = buf.len().min(LEN - self.len);
+        self.buffer[self.len..self.len + copy_len].copy_from_slice(&buf[..copy_len]);
+        self.len += copy_len;
+
+        if self.len == LEN
+            && let Err(why) = self.drain()
+        {
+            self.error = Some(why);
+        }
+
+        Ok(copy_len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_error()?;
+        self.drain()?;
+        self.write.flush()
+    }
+}
+impl<const LEN: usize, W> Drop for PreWrite<LEN, W>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        if self.drain().is_ok() {
+            let _ = self.write.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+
+    use super::ReadExt;
+
+    /// A reader returning data in fixed pieces, as a socket would.
+    ///
+    /// Empty pieces are returned as the end of the stream. Once all pieces
+    /// are read, the reader would block forever.
+    struct Pieces(Vec<&'static [u8]>);
+    impl Read for Pieces {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                panic!("read would block");
+            }
+            let piece = self.0.remove(0);
+            buf[..piece.len()].copy_from_slice(piece);
+            Ok(piece.len())
+        }
+    }
+
+    #[test]
+    fn peek_stops_at_requested_len() {
+        let mut reader = Pieces(vec![b"ab", b"", b"%P", b"DF"]).pre::<4096>();
+
+        let mut head = [0u8; 2];
+        reader.read_exact(&mut head).unwrap();
+        assert_eq!(&head, b"ab");
+
+        let mut magic = [0u8; 4];
+        reader.peek_exact(&mut magic).unwrap();
+        assert_eq!(&magic, b"%PDF");
+        reader.read_exact(&mut magic).unwrap();
+        assert_eq!(&magic, b"%PDF");
+    }
+}
@@ -0,0 +1,291 @@
+//! Streaming base64 encoding and decoding.
+//!
+//! ```
+//! use libcommons::{
+//!     io::base64::{Alphabet, Base64Decoder, Base64Encoder},
+//!     prelude::*,
+//! };
+//! use std::io::{Cursor, Read};
+//!
+//! let mut encoder = Base64Encoder::new(Vec::new(), Alphabet::Standard);
+//! Cursor::new(b"hello, world").pipe::<4, _>(&mut encoder).unwrap();
+//! let encoded = encoder.finish().unwrap();
+//! assert_eq!(encoded, b"aGVsbG8sIHdvcmxk");
+//!
+//! let mut decoded = String::new();
+//! Base64Decoder::new(Cursor::new(encoded), Alphabet::Standard)
+//!     .read_to_string(&mut decoded)
+//!     .unwrap();
+//! assert_eq!(decoded, "hello, world");
+//! ```
+
+use std::io::{self, Read, Write};
+
+/// Base64 alphabet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Alphabet {
+    /// Standard alphabet with `+` and `/`, padded with `=`.
+    #[default]
+    Standard,
+    /// URL and filename safe alphabet with `-` and `_`, without padding.
+    UrlSafe,
+}
+impl Alphabet {
+    const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    /// Get all 64 characters of this alphabet.
+    pub const fn chars(self) -> &'static [u8; 64] {
+        match self {
+            Self::Standard => Self::STANDARD,
+            Self::UrlSafe => Self::URL_SAFE,
+        }
+    }
+
+    /// Get value of a character, or [None] if it's not in this alphabet.
+    pub const fn decode(self, char: u8) -> Option<u8> {
+        match char {
+            b'A'..=b'Z' => Some(char - b'A'),
+            b'a'..=b'z' => Some(char - b'a' + 26),
+            b'0'..=b'9' => Some(char - b'0' + 52),
+            b'+' if matches!(self, Self::Standard) => Some(62),
+            b'/' if matches!(self, Self::Standard) => Some(63),
+            b'-' if matches!(self, Self::UrlSafe) => Some(62),
+            b'_' if matches!(self, Self::UrlSafe) => Some(63),
+            _ => None,
+        }
+    }
+
+    const fn padded(self) -> bool {
+        matches!(self, Self::Standard)
+    }
+}
+
+/// A writer that base64-encodes everything written into it.
+///
+/// Bytes are encoded in groups of 3. Call [Base64Encoder::finish] to
+/// write the last group.
+pub struct Base64Encoder<W: Write> {
+    write: W,
+    alphabet: Alphabet,
+    pending: [u8; 3],
+    len: usize,
+}
+impl<W: Write> Base64Encoder<W> {
+    /// Create a new [Base64Encoder].
+    pub fn new(write: W, alphabet: Alphabet) -> Self {
+        Self {
+            write,
+            alphabet,
+            pending: [0; 3],
+            len: 0,
+        }
+    }
+
+    /// Get the inner writer, discarding the last group.
+    pub fn into_inner(self) -> W {
+        self.write
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn inner(&self) -> &W {
+        &self.write
+    }
+
+    /// Get a mutable reference to the inner writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+
+    /// Write the last group with padding and get the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let chars = self.alphabet.chars();
+        self.pending[self.len..].fill(0);
+        let [a, b, _] = self.pending;
+        let mut out = [b'='; 4];
+        out[0] = chars[(a >> 2) as usize];
+        out[1] = chars[((a & 0b11) << 4 | b >> 4) as usize];
+        if self.len == 2 {
+            out[2] = chars[((b & 0b1111) << 2) as usize];
+        }
+
+        let len = match (self.len, self.alphabet.padded()) {
+            (0, _) => 0,
+            (_, true) => 4,
+            (x, false) => x + 1,
+        };
+        self.write.write_all(&out[..len])?;
+        self.write.flush()?;
+        Ok(self.write)
+    }
+}
+impl<W: Write> Write for Base64Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let chars = self.alphabet.chars();
+        let mut out = [0u8; 1024];
+        let mut written = 0;
+        let mut pending = self.pending;
+        let mut len = self.len;
+        let mut used = 0;
+
+        for &x in buf {
+            if written == out.len() {
+                break;
+            }
+            pending[len] = x;
+            len += 1;
+            used += 1;
+            if len == 3 {
+                let [a, b, c] = pending;
+                out[written] = chars[(a >> 2) as usize];
+                out[written + 1] = chars[((a & 0b11) << 4 | b >> 4) as usize];
+                out[written + 2] = chars[((b & 0b1111) << 2 | c >> 6) as usize];
+                out[written + 3] = chars[(c & 0b111111) as usize];
+                written += 4;
+                len = 0;
+            }
+        }
+
+        self.write.write_all(&out[..written])?;
+        self.pending = pending;
+        self.len = len;
+        Ok(used)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write.flush()
+    }
+}
+
+/// A reader that decodes base64 from the inner reader.
+///
+/// ASCII whitespace is ignored and padding is optional. Everything after
+/// padding is ignored.
+///
+/// ## Errors
+/// Characters outside of the alphabet cause [io::ErrorKind::InvalidData].
+pub struct Base64Decoder<R: Read> {
+    read: R,
+    alphabet: Alphabet,
+    input: [u8; 512],
+    start: usize,
+    end: usize,
+    output: [u8; 3],
+    pos: usize,
+    len: usize,
+    done: bool,
+}
+impl<R: Read> Base64Decoder<R> {
+    /// Create a new [Base64Decoder].
+    pub fn new(read: R, alphabet: Alphabet) -> Self {
+        Self {
+            read,
+            alphabet,
+            input: [0; 512],
+            start: 0,
+            end: 0,
+            output: [0; 3],
+            pos: 0,
+            len: 0,
+            done: false,
+        }
+    }
+
+    /// Get the inner reader.
+    ///
+    /// Data that was already read from it but not decoded is lost.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    fn next_char(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            if self.start == self.end {
+                self.start = 0;
+                self.end = self.read.read(&mut self.input)?;
+                if self.end == 0 {
+                    return Ok(None);
+                }
+            }
+            let char = self.input[self.start];
+            self.start += 1;
+            if !char.is_ascii_whitespace() {
+                return Ok(Some(char));
+            }
+        }
+    }
+
+    /// Decode next group into output. Returns `false` at the end of data.
+    fn decode_group(&mut self) -> io::Result<bool> {
+        let mut values = [0u8; 4];
+        let mut count = 0;
+        while count < 4 {
+            match self.next_char()? {
+                None => break,
+                Some(b'=') => {
+                    self.done = true;
+                    break;
+                }
+                Some(x) => match self.alphabet.decode(x) {
+                    Some(x) => {
+                        values[count] = x;
+                        count += 1;
+                    }
+                    None => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "invalid base64 character",
+                        ));
+                    }
+                },
+            }
+        }
+        if count < 4 {
+            self.done = true;
+        }
+
+        let [a, b, c, d] = values;
+        self.output = [a << 2 | b >> 4, b << 4 | c >> 2, c << 6 | d];
+        self.pos = 0;
+        self.len = match count {
+            0 => return Ok(false),
+            1 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "truncated base64 data",
+                ));
+            }
+            x => x - 1,
+        };
+        Ok(true)
+    }
+}
+impl<R: Read> Read for Base64Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut written = 0;
+        loop {
+            let len = (self.len - self.pos).min(buf.len() - written);
+            buf[written..written + len].copy_from_slice(&self.output[self.pos..self.pos + len]);
+            self.pos += len;
+            written += len;
+
+            if written == buf.len() || self.done || (written > 0 && self.start == self.end) {
+                break;
+            }
+            if !self.decode_group()? {
+                break;
+            }
+        }
+        Ok(written)
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read},
+};
+
+/// A reader that reads multiple readers in sequence.
+///
+/// Unlike [std::io::Chain], this can hold any number of readers
+/// without nesting generic types.
+///
+/// ```
+/// use libcommons::io::chain::ChainReader;
+/// use std::io::{Cursor, Read};
+///
+/// let mut reader: ChainReader = [b"hello".as_slice(), b", ", b"world"]
+///     .into_iter()
+///     .collect();
+/// reader.push(Cursor::new(b"!"));
+///
+/// let mut text = String::new();
+/// reader.read_to_string(&mut text).unwrap();
+/// assert_eq!(text, "hello, world!");
+/// ```
+#[derive(Default)]
+pub struct ChainReader<'a> {
+    readers: VecDeque<Box<dyn Read + 'a>>,
+}
+impl<'a> ChainReader<'a> {
+    /// Create a new [ChainReader].
+    pub fn new(readers: Vec<Box<dyn Read + 'a>>) -> Self {
+        Self {
+            readers: readers.into(),
+        }
+    }
+
+    /// Add a reader to the end of the chain.
+    pub fn push<R: Read + 'a>(&mut self, read: R) {
+        self.readers.push_back(Box::new(read));
+    }
+
+    /// Get readers that were not read to the end yet.
+    pub fn into_inner(self) -> Vec<Box<dyn Read + 'a>> {
+        self.readers.into()
+    }
+
+    /// Get number of readers that were not read to the end yet.
+    pub fn len(&self) -> usize {
+        self.readers.len()
+    }
+
+    /// Check if all readers were read to the end.
+    pub fn is_empty(&self) -> bool {
+        self.readers.is_empty()
+    }
+}
+impl<'a, R: Read + 'a> FromIterator<R> for ChainReader<'a> {
+    fn from_iter<T: IntoIterator<Item = R>>(iter: T) -> Self {
+        Self {
+            readers: iter
+                .into_iter()
+                .map(|x| Box::new(x) as Box<dyn Read + 'a>)
+                .collect(),
+        }
+    }
+}
+impl<'a, R: Read + 'a> Extend<R> for ChainReader<'a> {
+    fn extend<T: IntoIterator<Item = R>>(&mut self, iter: T) {
+        self.readers
+            .extend(iter.into_iter().map(|x| Box::new(x) as Box<dyn Read + 'a>));
+    }
+}
+impl Read for ChainReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        while let Some(x) = self.readers.front_mut() {
+            match x.read(buf)? {
+                0 => drop(self.readers.pop_front()),
+                len => return Ok(len),
+            }
+        }
+        Ok(0)
+    }
+}
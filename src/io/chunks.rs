@@ -0,0 +1,78 @@
+use std::io::{self, Read};
+
+/// An iterator over fixed-size chunks of a reader.
+///
+/// Created with [ReadExt::read_chunks](crate::io::ReadExt::read_chunks).
+///
+/// Every chunk is filled completely, except for the last one. Items are
+/// chunks with the number of filled bytes.
+///
+/// ```
+/// use libcommons::prelude::*;
+///
+/// let mut chunks = b"abcdefg".as_slice().read_chunks::<3>();
+/// assert_eq!(chunks.next().unwrap().unwrap(), (*b"abc", 3));
+/// assert_eq!(chunks.next().unwrap().unwrap(), (*b"def", 3));
+/// let (last, len) = chunks.next().unwrap().unwrap();
+/// assert_eq!(&last[..len], b"g");
+/// assert!(chunks.next().is_none());
+/// ```
+///
+/// ## Errors
+/// If reading fails, the error is returned and bytes read so far are kept,
+/// so the next call continues filling the same chunk.
+pub struct Chunks<const N: usize, R: Read> {
+    read: R,
+    buf: [u8; N],
+    len: usize,
+    done: bool,
+}
+impl<const N: usize, R: Read> Chunks<N, R> {
+    /// Create a new [Chunks].
+    pub fn new(read: R) -> Self {
+        Self {
+            read,
+            buf: [0; N],
+            len: 0,
+            done: false,
+        }
+    }
+
+    /// Get the inner reader.
+    ///
+    /// Bytes of an incomplete chunk are lost.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+}
+impl<const N: usize, R: Read> Iterator for Chunks<N, R> {
+    type Item = io::Result<([u8; N], usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done && self.len < N {
+            match self.read.read(&mut self.buf[self.len..]) {
+                Ok(0) => self.done = true,
+                Ok(len) => self.len += len,
+                Err(why) if why.kind() == io::ErrorKind::Interrupted => {}
+                Err(why) => return Some(Err(why)),
+            }
+        }
+
+        if self.len == 0 {
+            return None;
+        }
+        let len = self.len;
+        self.len = 0;
+        Some(Ok((self.buf, len)))
+    }
+}
@@ -0,0 +1,159 @@
+//! Cyclic redundancy checks.
+//!
+//! Both checksums implement [Hasher], so they can be used with
+//! [HashingReader] and [HashingWriter].
+//!
+//! ```
+//! use libcommons::{io::crc::Crc32Reader, prelude::*};
+//! use std::io::Cursor;
+//!
+//! let mut reader = Crc32Reader::new(Cursor::new(b"123456789"), Default::default());
+//! reader.pipe::<16, _>(std::io::sink()).unwrap();
+//! assert_eq!(reader.hasher().value(), 0xCBF43926);
+//! ```
+
+use std::hash::Hasher;
+
+use super::hash::{HashingReader, HashingWriter};
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+const CRC16_TABLE: [u16; 256] = {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u16;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xA001
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC-32 (IEEE 802.3), as used by zip, png and ethernet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Crc32(u32);
+impl Crc32 {
+    /// Create a new [Crc32].
+    pub const fn new() -> Self {
+        Self(!0)
+    }
+
+    /// Feed more data into the checksum.
+    pub const fn update(&mut self, data: &[u8]) {
+        let mut i = 0;
+        while i < data.len() {
+            self.0 = CRC32_TABLE[((self.0 ^ data[i] as u32) & 0xFF) as usize] ^ (self.0 >> 8);
+            i += 1;
+        }
+    }
+
+    /// Get checksum of all data fed so far.
+    pub const fn value(&self) -> u32 {
+        !self.0
+    }
+}
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Hasher for Crc32 {
+    fn finish(&self) -> u64 {
+        self.value() as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+/// CRC-16 (ARC, also known as CRC-16/IBM), as used by ARC and LHA archives.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Crc16(u16);
+impl Crc16 {
+    /// Create a new [Crc16].
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Feed more data into the checksum.
+    pub const fn update(&mut self, data: &[u8]) {
+        let mut i = 0;
+        while i < data.len() {
+            self.0 = CRC16_TABLE[((self.0 ^ data[i] as u16) & 0xFF) as usize] ^ (self.0 >> 8);
+            i += 1;
+        }
+    }
+
+    /// Get checksum of all data fed so far.
+    pub const fn value(&self) -> u16 {
+        self.0
+    }
+}
+impl Hasher for Crc16 {
+    fn finish(&self) -> u64 {
+        self.value() as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+/// Calculate CRC-32 of data.
+///
+/// ```
+/// assert_eq!(libcommons::io::crc::crc32(b"123456789"), 0xCBF43926);
+/// ```
+pub const fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(data);
+    crc.value()
+}
+
+/// Calculate CRC-16 of data.
+///
+/// ```
+/// assert_eq!(libcommons::io::crc::crc16(b"123456789"), 0xBB3D);
+/// ```
+pub const fn crc16(data: &[u8]) -> u16 {
+    let mut crc = Crc16::new();
+    crc.update(data);
+    crc.value()
+}
+
+/// A reader that calculates CRC-32 of everything it reads.
+pub type Crc32Reader<R> = HashingReader<R, Crc32>;
+/// A writer that calculates CRC-32 of everything it writes.
+pub type Crc32Writer<W> = HashingWriter<W, Crc32>;
+/// A reader that calculates CRC-16 of everything it reads.
+pub type Crc16Reader<R> = HashingReader<R, Crc16>;
+/// A writer that calculates CRC-16 of everything it writes.
+pub type Crc16Writer<W> = HashingWriter<W, Crc16>;
@@ -0,0 +1,162 @@
+//! In-memory pipes.
+//!
+//! ```
+//! use libcommons::io;
+//! use std::{
+//!     io::{Read, Write},
+//!     thread,
+//! };
+//!
+//! let (mut reader, mut writer) = io::pipe_bounded(4);
+//! let producer = thread::spawn(move || {
+//!     writer.write_all(b"hello, world").unwrap();
+//! });
+//!
+//! let mut text = String::new();
+//! reader.read_to_string(&mut text).unwrap();
+//! producer.join().unwrap();
+//! assert_eq!(text, "hello, world");
+//! ```
+
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+};
+
+struct State {
+    buffer: VecDeque<u8>,
+    capacity: Option<usize>,
+    reader: bool,
+    writer: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|x| x.into_inner())
+    }
+
+    fn wait<'a>(&self, guard: MutexGuard<'a, State>) -> MutexGuard<'a, State> {
+        self.changed.wait(guard).unwrap_or_else(|x| x.into_inner())
+    }
+}
+
+/// Create a connected pair of reader and writer with unlimited buffer.
+///
+/// Everything written into [PipeWriter] can be read from [PipeReader].
+/// Both ends can be sent to other threads.
+pub fn pipe() -> (PipeReader, PipeWriter) {
+    new(None)
+}
+
+/// Create a connected pair of reader and writer with a buffer of at most
+/// `capacity` bytes.
+///
+/// Writes block while the buffer is full.
+///
+/// ## Panics
+/// Panics if `capacity` is 0.
+pub fn pipe_bounded(capacity: usize) -> (PipeReader, PipeWriter) {
+    assert!(capacity > 0, "pipe capacity must not be 0");
+    new(Some(capacity))
+}
+
+fn new(capacity: Option<usize>) -> (PipeReader, PipeWriter) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            buffer: VecDeque::with_capacity(capacity.unwrap_or(0)),
+            capacity,
+            reader: true,
+            writer: true,
+        }),
+        changed: Condvar::new(),
+    });
+    (
+        PipeReader {
+            shared: shared.clone(),
+        },
+        PipeWriter { shared },
+    )
+}
+
+/// Reading end of a [pipe].
+///
+/// Reads block until data is available. Once [PipeWriter] is dropped and
+/// all data is read, reads return `0`.
+pub struct PipeReader {
+    shared: Arc<Shared>,
+}
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let mut state = self.shared.lock();
+        while state.buffer.is_empty() && state.writer {
+            state = self.shared.wait(state);
+        }
+
+        let len = state.buffer.read(buf)?;
+        drop(state);
+        self.shared.changed.notify_all();
+        Ok(len)
+    }
+}
+impl Drop for PipeReader {
+    fn drop(&mut self) {
+        self.shared.lock().reader = false;
+        self.shared.changed.notify_all();
+    }
+}
+
+/// Writing end of a [pipe].
+///
+/// ## Errors
+/// Once [PipeReader] is dropped, writes fail with [io::ErrorKind::BrokenPipe].
+pub struct PipeWriter {
+    shared: Arc<Shared>,
+}
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let mut state = self.shared.lock();
+        loop {
+            if !state.reader {
+                return Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "pipe reader was dropped",
+                ));
+            }
+            let space = match state.capacity {
+                Some(x) => x - state.buffer.len(),
+                None => buf.len(),
+            };
+            if space > 0 {
+                let len = space.min(buf.len());
+                state.buffer.extend(&buf[..len]);
+                drop(state);
+                self.shared.changed.notify_all();
+                return Ok(len);
+            }
+            state = self.shared.wait(state);
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+impl Drop for PipeWriter {
+    fn drop(&mut self) {
+        self.shared.lock().writer = false;
+        self.shared.changed.notify_all();
+    }
+}
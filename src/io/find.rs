@@ -0,0 +1,138 @@
+use std::io::{self, Read};
+
+/// An iterator over offsets of a pattern in a reader.
+///
+/// Created with [ReadExt::find](crate::io::ReadExt::find).
+///
+/// Uses Knuth-Morris-Pratt algorithm, so data is read once in chunks and
+/// matches spanning across reads are found. Matches may overlap. An
+/// empty pattern matches once at offset 0.
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::io::Cursor;
+///
+/// let offsets: Vec<_> = Cursor::new("needle in a haystack of needles")
+///     .find("needle")
+///     .map(|x| x.unwrap())
+///     .collect();
+/// assert_eq!(offsets, [0, 24]);
+///
+/// let offsets: Vec<_> = Cursor::new("aaaa").find("aa").map(|x| x.unwrap()).collect();
+/// assert_eq!(offsets, [0, 1, 2]);
+/// ```
+///
+/// ## Errors
+/// Errors from the inner reader are returned as is, and searching can
+/// continue afterwards.
+pub struct StreamFind<R: Read> {
+    read: R,
+    pattern: Box<[u8]>,
+    /// Length of the longest proper prefix of `pattern[..=i]` that is
+    /// also its suffix.
+    failure: Box<[usize]>,
+    matched: usize,
+    offset: u64,
+    buf: Box<[u8]>,
+    pos: usize,
+    len: usize,
+}
+impl<R: Read> StreamFind<R> {
+    /// Create a new [StreamFind].
+    pub fn new(read: R, pattern: impl AsRef<[u8]>) -> Self {
+        let pattern: Box<[u8]> = pattern.as_ref().into();
+        let mut failure = vec![0; pattern.len()].into_boxed_slice();
+        let mut len = 0;
+        for i in 1..pattern.len() {
+            while len > 0 && pattern[i] != pattern[len] {
+                len = failure[len - 1];
+            }
+            if pattern[i] == pattern[len] {
+                len += 1;
+            }
+            failure[i] = len;
+        }
+
+        Self {
+            read,
+            pattern,
+            failure,
+            matched: 0,
+            offset: 0,
+            buf: vec![0; 8 * 1024].into_boxed_slice(),
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Get the pattern being searched for.
+    pub fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    /// Get number of bytes searched so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Get the inner reader.
+    ///
+    /// Data that was read but not searched yet is lost.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+}
+impl<R: Read> Iterator for StreamFind<R> {
+    type Item = io::Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pattern.is_empty() {
+            // `matched` is unused otherwise, so it marks that the match was returned.
+            return match self.matched {
+                0 => {
+                    self.matched = 1;
+                    Some(Ok(0))
+                }
+                _ => None,
+            };
+        }
+
+        loop {
+            while self.pos < self.len {
+                let byte = self.buf[self.pos];
+                self.pos += 1;
+                self.offset += 1;
+
+                while self.matched > 0 && self.pattern[self.matched] != byte {
+                    self.matched = self.failure[self.matched - 1];
+                }
+                if self.pattern[self.matched] == byte {
+                    self.matched += 1;
+                }
+                if self.matched == self.pattern.len() {
+                    self.matched = self.failure[self.matched - 1];
+                    return Some(Ok(self.offset - self.pattern.len() as u64));
+                }
+            }
+
+            match self.read.read(&mut self.buf) {
+                Ok(0) => return None,
+                Ok(len) => {
+                    self.pos = 0;
+                    self.len = len;
+                }
+                Err(why) => return Some(Err(why)),
+            }
+        }
+    }
+}
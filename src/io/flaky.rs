@@ -0,0 +1,122 @@
+use std::io::{self, Read};
+
+/// A fault injected by [FlakyReader].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fault {
+    /// Read normally.
+    Pass,
+    /// Fail with [io::ErrorKind::WouldBlock].
+    WouldBlock,
+    /// Fail with [io::ErrorKind::Interrupted].
+    Interrupted,
+    /// Read at most this many bytes. `0` is treated as `1`.
+    Short(usize),
+}
+
+enum Source {
+    Schedule(std::vec::IntoIter<Fault>),
+    Random(u64),
+}
+
+/// A reader that injects faults into reads, for testing [Read] consumers.
+///
+/// Faults are taken either from a schedule, after which reads pass through
+/// unchanged, or from a pseudo-random sequence defined by a seed. The same
+/// seed always produces the same faults.
+///
+/// ```
+/// use libcommons::{
+///     io::flaky::{Fault, FlakyReader},
+///     prelude::*,
+/// };
+/// use std::{
+///     io::{Cursor, ErrorKind, Read},
+///     time::Duration,
+/// };
+///
+/// let mut reader = FlakyReader::with_schedule(
+///     Cursor::new(b"hello"),
+///     [Fault::WouldBlock, Fault::Short(2)],
+/// );
+/// let mut buf = [0u8; 8];
+/// assert_eq!(reader.read(&mut buf).unwrap_err().kind(), ErrorKind::WouldBlock);
+/// assert_eq!(reader.read(&mut buf).unwrap(), 2);
+/// assert_eq!(reader.read(&mut buf).unwrap(), 3);
+///
+/// let mut text = String::new();
+/// FlakyReader::with_seed(Cursor::new(b"hello, world"), 42)
+///     .retry_would_block(Duration::ZERO)
+///     .read_to_string(&mut text)
+///     .unwrap();
+/// assert_eq!(text, "hello, world");
+/// ```
+pub struct FlakyReader<R: Read> {
+    read: R,
+    source: Source,
+}
+impl<R: Read> FlakyReader<R> {
+    /// Inject faults from `schedule`, one per read.
+    pub fn with_schedule(read: R, schedule: impl IntoIterator<Item = Fault>) -> Self {
+        Self {
+            read,
+            source: Source::Schedule(schedule.into_iter().collect::<Vec<_>>().into_iter()),
+        }
+    }
+
+    /// Inject pseudo-random faults.
+    ///
+    /// Every read has an equal chance of passing, failing with
+    /// [Fault::WouldBlock] or [Fault::Interrupted], or being short.
+    pub fn with_seed(read: R, seed: u64) -> Self {
+        Self {
+            read,
+            // Xorshift state must not be zero.
+            source: Source::Random(seed | 1),
+        }
+    }
+
+    /// Get the inner reader.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    fn next_fault(&mut self, len: usize) -> Fault {
+        match &mut self.source {
+            Source::Schedule(x) => x.next().unwrap_or(Fault::Pass),
+            Source::Random(state) => {
+                *state ^= *state << 13;
+                *state ^= *state >> 7;
+                *state ^= *state << 17;
+                match *state % 4 {
+                    0 => Fault::Pass,
+                    1 => Fault::WouldBlock,
+                    2 => Fault::Interrupted,
+                    _ => Fault::Short((*state >> 2) as usize % len.max(1) + 1),
+                }
+            }
+        }
+    }
+}
+impl<R: Read> Read for FlakyReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.next_fault(buf.len()) {
+            Fault::Pass => self.read.read(buf),
+            Fault::WouldBlock => Err(io::Error::new(io::ErrorKind::WouldBlock, "injected fault")),
+            Fault::Interrupted => Err(io::Error::new(io::ErrorKind::Interrupted, "injected fault")),
+            Fault::Short(len) => {
+                let len = len.max(1).min(buf.len());
+                self.read.read(&mut buf[..len])
+            }
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! Extensions for asynchronous IO from `futures-io`.
+//!
+//! ```
+//! use libcommons::io::futures::AsyncReadExt;
+//! use std::{
+//!     future::Future,
+//!     pin::pin,
+//!     task::{Context, Poll, Waker},
+//! };
+//!
+//! fn block_on<F: Future>(future: F) -> F::Output {
+//!     let mut future = pin!(future);
+//!     let mut cx = Context::from_waker(Waker::noop());
+//!     loop {
+//!         if let Poll::Ready(x) = future.as_mut().poll(&mut cx) {
+//!             return x;
+//!         }
+//!     }
+//! }
+//!
+//! let mut out = Vec::new();
+//! let mut reader = b"hello, world".as_slice().counting();
+//! block_on(reader.pipe::<4, _>(&mut out)).unwrap();
+//! assert_eq!(out, b"hello, world");
+//! assert_eq!(reader.count(), 12);
+//! ```
+
+use std::{
+    future::{Future, poll_fn},
+    io,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use futures_core::Stream;
+use futures_io::{AsyncRead, AsyncWrite};
+
+async fn read<R>(read: &mut R, buf: &mut [u8]) -> io::Result<usize>
+where
+    R: AsyncRead + Unpin + ?Sized,
+{
+    poll_fn(|cx| Pin::new(&mut *read).poll_read(cx, buf)).await
+}
+
+async fn write_all<W>(write: &mut W, mut buf: &[u8]) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    while !buf.is_empty() {
+        match poll_fn(|cx| Pin::new(&mut *write).poll_write(cx, buf)).await? {
+            0 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ));
+            }
+            len => buf = &buf[len..],
+        }
+    }
+    Ok(())
+}
+
+/// Libcommons extensions for [AsyncRead].
+pub trait AsyncReadExt: AsyncRead {
+    /// Pipe all contents of self into provided writer.
+    fn pipe<const BUF: usize, W>(&mut self, write: W) -> impl Future<Output = io::Result<()>>
+    where
+        W: AsyncWrite + Unpin,
+        Self: Unpin;
+
+    /// Pipe all contents of self into provided writer.
+    ///
+    /// `cb` is called with the number of transferred bytes after every write.
+    fn pipe_with<const BUF: usize, W, F>(
+        &mut self,
+        write: W,
+        cb: F,
+    ) -> impl Future<Output = io::Result<()>>
+    where
+        W: AsyncWrite + Unpin,
+        F: FnMut(u64),
+        Self: Unpin;
+
+    /// Copy everything read from this reader into a writer.
+    ///
+    /// See [AsyncTeeReader].
+    fn tee<W>(self, write: W) -> AsyncTeeReader<Self, W>
+    where
+        W: AsyncWrite + Unpin,
+        Self: Sized + Unpin;
+
+    /// Count bytes read from this reader.
+    ///
+    /// See [AsyncCountingReader].
+    fn counting(self) -> AsyncCountingReader<Self>
+    where
+        Self: Sized + Unpin;
+
+    /// Convert this reader into [AsyncUtf8].
+    fn into_utf8(self) -> AsyncUtf8<Self>
+    where
+        Self: Sized + Unpin;
+}
+impl<T> AsyncReadExt for T
+where
+    T: AsyncRead + ?Sized,
+{
+    async fn pipe<const BUF: usize, W>(&mut self, write: W) -> io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+        Self: Unpin,
+    {
+        self.pipe_with::<BUF, _, _>(write, |_| ()).await
+    }
+
+    async fn pipe_with<const BUF: usize, W, F>(&mut self, mut write: W, mut cb: F) -> io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+        F: FnMut(u64),
+        Self: Unpin,
+    {
+        let mut buf = [0u8; BUF];
+        let mut download = 0u64;
+        loop {
+            let len = read(self, &mut buf).await?;
+            if len == 0 {
+                return Ok(());
+            }
+            write_all(&mut write, &buf[..len]).await?;
+            download += len as u64;
+            cb(download);
+        }
+    }
+
+    fn tee<W>(self, write: W) -> AsyncTeeReader<Self, W>
+    where
+        W: AsyncWrite + Unpin,
+        Self: Sized + Unpin,
+    {
+        AsyncTeeReader::new(self, write)
+    }
+
+    fn counting(self) -> AsyncCountingReader<Self>
+    where
+        Self: Sized + Unpin,
+    {
+        AsyncCountingReader::new(self)
+    }
+
+    fn into_utf8(self) -> AsyncUtf8<Self>
+    where
+        Self: Sized + Unpin,
+    {
+        AsyncUtf8::new(self)
+    }
+}
+
+/// An asynchronous reader that copies everything it reads into a writer.
+///
+/// Data is written right after it's read. If the writer is not ready,
+/// the rest is written before the next read, or with
+/// [AsyncTeeReader::flush_pending].
+///
+/// ## Errors
+/// If writing fails, the error is returned from the next read.
+pub struct AsyncTeeReader<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> {
+    read: R,
+    write: W,
+    pending: Vec<u8>,
+    pos: usize,
+}
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> AsyncTeeReader<R, W> {
+    /// Create a new [AsyncTeeReader].
+    pub fn new(read: R, write: W) -> Self {
+        Self {
+            read,
+            write,
+            pending: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Get the inner reader and writer, discarding data that was not
+    /// written yet.
+    pub fn into_inner(self) -> (R, W) {
+        (self.read, self.write)
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Get a reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.write
+    }
+
+    /// Get a mutable reference to the writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+
+    /// Write all data that was read but not written yet.
+    pub async fn flush_pending(&mut self) -> io::Result<()> {
+        poll_fn(|cx| self.poll_pending(cx)).await
+    }
+
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.pos < self.pending.len() {
+            match ready!(Pin::new(&mut self.write).poll_write(cx, &self.pending[self.pos..]))? {
+                0 => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    )));
+                }
+                len => self.pos += len,
+            }
+        }
+        self.pending.clear();
+        self.pos = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+impl<R: AsyncRead + Unpin, W: AsyncWrite + Unpin> AsyncRead for AsyncTeeReader<R, W> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx))?;
+
+        let len = ready!(Pin::new(&mut this.read).poll_read(cx, buf))?;
+        this.pending.extend_from_slice(&buf[..len]);
+        // Data is already read, so the error is returned on the next read.
+        let _ = this.poll_pending(cx);
+        Poll::Ready(Ok(len))
+    }
+}
+
+/// An asynchronous reader that counts read bytes.
+pub struct AsyncCountingReader<R: AsyncRead + Unpin> {
+    read: R,
+    count: u64,
+}
+impl<R: AsyncRead + Unpin> AsyncCountingReader<R> {
+    /// Create a new [AsyncCountingReader].
+    pub fn new(read: R) -> Self {
+        Self { read, count: 0 }
+    }
+
+    /// Get the inner reader.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Get number of bytes read so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncCountingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let len = ready!(Pin::new(&mut this.read).poll_read(cx, buf))?;
+        this.count += len as u64;
+        Poll::Ready(Ok(len))
+    }
+}
+
+/// A stream of UTF-8 characters from an [AsyncRead].
+///
+/// Unlike [crate::str::utf8::Utf8], a character split between reads or
+/// interrupted by an error is not lost, and is continued on the next poll.
+/// Invalid sequences are replaced with [char::REPLACEMENT_CHARACTER].
+pub struct AsyncUtf8<R: AsyncRead + Unpin> {
+    read: R,
+    buf: [u8; 4],
+    len: usize,
+}
+impl<R: AsyncRead + Unpin> AsyncUtf8<R> {
+    /// Create a new [AsyncUtf8].
+    pub fn new(read: R) -> Self {
+        Self {
+            read,
+            buf: [0; 4],
+            len: 0,
+        }
+    }
+
+    /// Get the inner reader.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+}
+impl<R: AsyncRead + Unpin> Stream for AsyncUtf8<R> {
+    type Item = io::Result<char>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let need = match this.buf[0].leading_ones() {
+                _ if this.len == 0 => 1,
+                0 => return Poll::Ready(Some(Ok(this.take()))),
+                x @ 2..=4 => x as usize,
+                _ => {
+                    this.len = 0;
+                    return Poll::Ready(Some(Ok(char::REPLACEMENT_CHARACTER)));
+                }
+            };
+            if this.len == need {
+                return Poll::Ready(Some(Ok(this.take())));
+            }
+
+            match ready!(Pin::new(&mut this.read).poll_read(cx, &mut this.buf[this.len..need])) {
+                Ok(0) if this.len == 0 => return Poll::Ready(None),
+                Ok(0) => {
+                    this.len = 0;
+                    return Poll::Ready(Some(Ok(char::REPLACEMENT_CHARACTER)));
+                }
+                Ok(len) => this.len += len,
+                Err(why) => return Poll::Ready(Some(Err(why))),
+            }
+        }
+    }
+}
+impl<R: AsyncRead + Unpin> AsyncUtf8<R> {
+    /// Decode the complete character in the buffer and clear it.
+    fn take(&mut self) -> char {
+        let len = self.len;
+        self.len = 0;
+        str::from_utf8(&self.buf[..len])
+            .ok()
+            .and_then(|x| x.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+}
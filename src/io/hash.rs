@@ -0,0 +1,130 @@
+use std::{
+    hash::Hasher,
+    io::{self, Read, Write},
+};
+
+/// A reader that feeds everything it reads into a hasher.
+///
+/// ```
+/// use libcommons::{io::hash::HashingReader, prelude::*};
+/// use std::{
+///     hash::{DefaultHasher, Hasher},
+///     io::Cursor,
+/// };
+///
+/// let mut copy = Vec::new();
+/// let mut reader = HashingReader::new(Cursor::new(b"hello"), DefaultHasher::new());
+/// reader.pipe::<16, _>(&mut copy).unwrap();
+///
+/// let mut expected = DefaultHasher::new();
+/// expected.write(b"hello");
+/// assert_eq!(reader.finish(), expected.finish());
+/// assert_eq!(copy, b"hello");
+/// ```
+pub struct HashingReader<R: Read, H: Hasher> {
+    read: R,
+    hasher: H,
+}
+impl<R: Read, H: Hasher> HashingReader<R, H> {
+    /// Create a new [HashingReader].
+    pub fn new(read: R, hasher: H) -> Self {
+        Self { read, hasher }
+    }
+
+    /// Get the inner reader and the hasher.
+    pub fn into_inner(self) -> (R, H) {
+        (self.read, self.hasher)
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Get a reference to the hasher.
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Get hash of everything read so far.
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+}
+impl<R: Read, H: Hasher> Read for HashingReader<R, H> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.read.read(buf)?;
+        self.hasher.write(&buf[..len]);
+        Ok(len)
+    }
+}
+
+/// A writer that feeds everything it writes into a hasher.
+///
+/// Only bytes accepted by the inner writer are hashed.
+///
+/// ```
+/// use libcommons::io::hash::HashingWriter;
+/// use std::{
+///     hash::{DefaultHasher, Hasher},
+///     io::Write,
+/// };
+///
+/// let mut writer = HashingWriter::new(Vec::new(), DefaultHasher::new());
+/// writer.write_all(b"hello").unwrap();
+///
+/// let mut expected = DefaultHasher::new();
+/// expected.write(b"hello");
+/// assert_eq!(writer.finish(), expected.finish());
+/// ```
+pub struct HashingWriter<W: Write, H: Hasher> {
+    write: W,
+    hasher: H,
+}
+impl<W: Write, H: Hasher> HashingWriter<W, H> {
+    /// Create a new [HashingWriter].
+    pub fn new(write: W, hasher: H) -> Self {
+        Self { write, hasher }
+    }
+
+    /// Get the inner writer and the hasher.
+    pub fn into_inner(self) -> (W, H) {
+        (self.write, self.hasher)
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn inner(&self) -> &W {
+        &self.write
+    }
+
+    /// Get a mutable reference to the inner writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+
+    /// Get a reference to the hasher.
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Get hash of everything written so far.
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+}
+impl<W: Write, H: Hasher> Write for HashingWriter<W, H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.write.write(buf)?;
+        self.hasher.write(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write.flush()
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::{self, Write};
+
+/// A writer that formats bytes as a hex dump into the inner writer.
+///
+/// Output matches `hexdump -C`: every line has an offset, 16 bytes in hex
+/// and the same bytes as ASCII, with non-printable characters replaced
+/// by dots. The dump ends with the total length. Unlike `hexdump -C`,
+/// repeated lines are not collapsed into `*`.
+///
+/// Lines are written out once more bytes arrive after them. Call
+/// [HexDumpWriter::finish] to write the last line and the total length.
+///
+/// ```
+/// use libcommons::{io::hexdump::HexDumpWriter, prelude::*};
+/// use std::io::Cursor;
+///
+/// let mut dump = HexDumpWriter::new(Vec::new());
+/// Cursor::new(b"hello, world\nand more text")
+///     .pipe::<16, _>(&mut dump)
+///     .unwrap();
+/// let dump = String::from_utf8(dump.finish().unwrap()).unwrap();
+///
+/// assert_eq!(
+///     dump,
+///     "00000000  68 65 6c 6c 6f 2c 20 77  6f 72 6c 64 0a 61 6e 64  |hello, world.and|\n\
+///      00000010  20 6d 6f 72 65 20 74 65  78 74                    | more text|\n\
+///      0000001a\n"
+/// );
+/// ```
+pub struct HexDumpWriter<W: Write> {
+    write: W,
+    line: [u8; 16],
+    len: usize,
+    offset: u64,
+}
+impl<W: Write> HexDumpWriter<W> {
+    /// Create a new [HexDumpWriter].
+    pub fn new(write: W) -> Self {
+        Self {
+            write,
+            line: [0; 16],
+            len: 0,
+            offset: 0,
+        }
+    }
+
+    /// Get the inner writer, discarding the last line and the total length.
+    pub fn into_inner(self) -> W {
+        self.write
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn inner(&self) -> &W {
+        &self.write
+    }
+
+    /// Get a mutable reference to the inner writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+
+    /// Write the last line and the total length, and get the inner writer.
+    ///
+    /// Nothing is written if no bytes were dumped.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.len > 0 {
+            self.write_line()?;
+        }
+        if self.offset > 0 {
+            writeln!(self.write, "{:08x}", self.offset)?;
+        }
+        self.write.flush()?;
+        Ok(self.write)
+    }
+
+    fn write_line(&mut self) -> io::Result<()> {
+        write!(self.write, "{:08x}  ", self.offset)?;
+        for i in 0..16 {
+            match self.line[..self.len].get(i) {
+                Some(x) => write!(self.write, "{x:02x} ")?,
+                None => self.write.write_all(b"   ")?,
+            }
+            if i == 7 {
+                self.write.write_all(b" ")?;
+            }
+        }
+        self.write.write_all(b" |")?;
+        for &x in &self.line[..self.len] {
+            let x = if x.is_ascii_graphic() || x == b' ' {
+                x
+            } else {
+                b'.'
+            };
+            self.write.write_all(&[x])?;
+        }
+        self.write.write_all(b"|\n")?;
+
+        self.offset += self.len as u64;
+        self.len = 0;
+        Ok(())
+    }
+}
+impl<W: Write> Write for HexDumpWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.len == self.line.len() {
+            self.write_line()?;
+        }
+        let len = buf.len().min(self.line.len() - self.len);
+        self.line[self.len..self.len + len].copy_from_slice(&buf[..len]);
+        self.len += len;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write.flush()
+    }
+}
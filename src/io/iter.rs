@@ -0,0 +1,48 @@
+use std::io::{self, Read};
+
+/// A reader that reads bytes from an iterator.
+///
+/// ```
+/// use libcommons::io::iter::IterRead;
+/// use std::io::Read;
+///
+/// let mut text = String::new();
+/// IterRead::new("hello, 🦀".bytes())
+///     .read_to_string(&mut text)
+///     .unwrap();
+/// assert_eq!(text, "hello, 🦀");
+/// ```
+pub struct IterRead<I: Iterator<Item = u8>> {
+    iter: I,
+}
+impl<I: Iterator<Item = u8>> IterRead<I> {
+    /// Create a new [IterRead].
+    pub fn new(iter: I) -> Self {
+        Self { iter }
+    }
+
+    /// Get the inner iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    /// Get a reference to the inner iterator.
+    pub fn inner(&self) -> &I {
+        &self.iter
+    }
+
+    /// Get a mutable reference to the inner iterator.
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+}
+impl<I: Iterator<Item = u8>> Read for IterRead<I> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut len = 0;
+        for (x, byte) in buf.iter_mut().zip(&mut self.iter) {
+            *x = byte;
+            len += 1;
+        }
+        Ok(len)
+    }
+}
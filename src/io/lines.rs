@@ -0,0 +1,140 @@
+use std::io::{self, Read};
+
+use crate::str::stack::StackString;
+
+/// An iterator over lines of a reader, stored on the stack.
+///
+/// Created with [ReadExt::stack_lines](crate::io::ReadExt::stack_lines).
+///
+/// Lines are split on `\n`, with `\r\n` also accepted. Line terminators
+/// are not included. Data is read into a buffer of `CAP` bytes, so no
+/// allocations are made.
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::io::{Cursor, ErrorKind};
+///
+/// let mut lines =
+///     Cursor::new("short\r\nexactly8\r\nthis line is too long\nend").stack_lines::<8>();
+/// assert_eq!(lines.next().unwrap().unwrap(), "short");
+/// assert_eq!(lines.next().unwrap().unwrap(), "exactly8");
+/// assert_eq!(lines.next().unwrap().unwrap_err().kind(), ErrorKind::InvalidData);
+/// assert_eq!(lines.next().unwrap().unwrap(), "end");
+/// assert!(lines.next().is_none());
+/// ```
+///
+/// ## Errors
+/// Lines longer than `CAP` bytes and lines that are not valid UTF-8
+/// produce [io::ErrorKind::InvalidData]. Iteration then continues from the
+/// next line.
+pub struct Lines<const CAP: usize, R: Read> {
+    read: R,
+    buf: [u8; CAP],
+    len: usize,
+    skip: bool,
+    done: bool,
+}
+impl<const CAP: usize, R: Read> Lines<CAP, R> {
+    /// Create a new [Lines].
+    pub fn new(read: R) -> Self {
+        Self {
+            read,
+            buf: [0; CAP],
+            len: 0,
+            skip: false,
+            done: false,
+        }
+    }
+
+    /// Get the inner reader.
+    ///
+    /// Data that was already read from it but not returned is lost.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Take `len` bytes from the buffer as a line, dropping `extra` bytes
+    /// of terminator after it.
+    fn take(&mut self, len: usize, extra: usize) -> io::Result<StackString<CAP>> {
+        let line = &self.buf[..len];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let line = str::from_utf8(line)
+            .map_err(|x| io::Error::new(io::ErrorKind::InvalidData, x))
+            .and_then(|x| {
+                StackString::try_from(x).map_err(|x| io::Error::new(io::ErrorKind::InvalidData, x))
+            });
+        self.buf.copy_within(len + extra..self.len, 0);
+        self.len -= len + extra;
+        line
+    }
+}
+impl<const CAP: usize, R: Read> Iterator for Lines<CAP, R> {
+    type Item = io::Result<StackString<CAP>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(i) = self.buf[..self.len].iter().position(|x| *x == b'\n') {
+                if self.skip {
+                    self.skip = false;
+                    self.buf.copy_within(i + 1..self.len, 0);
+                    self.len -= i + 1;
+                    continue;
+                }
+                return Some(self.take(i, 1));
+            }
+            if self.skip {
+                self.len = 0;
+            }
+
+            if self.len == CAP && CAP != 0 {
+                // The line may only continue with its terminator.
+                let mut cr = false;
+                loop {
+                    let mut next = [0u8];
+                    match self.read.read(&mut next) {
+                        Ok(0) => {
+                            self.done = true;
+                            return Some(self.take(CAP, 0));
+                        }
+                        Ok(_) if next[0] == b'\n' => return Some(self.take(CAP, 0)),
+                        Ok(_) if next[0] == b'\r' && !cr => cr = true,
+                        Ok(_) => {
+                            self.len = 0;
+                            self.skip = true;
+                            return Some(Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "line is too long",
+                            )));
+                        }
+                        Err(why) => return Some(Err(why)),
+                    }
+                }
+            }
+
+            if self.done {
+                if self.len == 0 {
+                    return None;
+                }
+                let len = self.len;
+                return Some(self.take(len, 0));
+            }
+
+            match self.read.read(&mut self.buf[self.len..]) {
+                Ok(0) => self.done = true,
+                Ok(len) => self.len += len,
+                Err(why) if why.kind() == io::ErrorKind::Interrupted => {}
+                Err(why) => return Some(Err(why)),
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Memory-mapped files.
+//!
+//! ```
+//! use libcommons::io::mmap::Mmap;
+//! use std::{fs::File, io::Read};
+//!
+//! let path = std::env::temp_dir().join("libcommons-mmap-doctest");
+//! std::fs::write(&path, b"hello, world").unwrap();
+//!
+//! let file = File::open(&path).unwrap();
+//! // SAFETY: nothing else modifies the file while it's mapped.
+//! let map = unsafe { Mmap::map(&file) }.unwrap();
+//! assert_eq!(&map[..5], b"hello");
+//!
+//! let mut text = String::new();
+//! map.reader().read_to_string(&mut text).unwrap();
+//! assert_eq!(text, "hello, world");
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::{self, Cursor},
+    ops::Deref,
+    ptr::NonNull,
+    slice,
+};
+
+#[cfg(unix)]
+mod sys {
+    use std::{
+        ffi::{c_int, c_long, c_void},
+        fs::File,
+        io,
+        os::fd::AsRawFd,
+        ptr::{self, NonNull},
+    };
+
+    const PROT_READ: c_int = 1;
+    const MAP_PRIVATE: c_int = 2;
+
+    unsafe extern "C" {
+        fn mmap(
+            addr: *mut c_void,
+            len: usize,
+            prot: c_int,
+            flags: c_int,
+            fd: c_int,
+            offset: c_long,
+        ) -> *mut c_void;
+        fn munmap(addr: *mut c_void, len: usize) -> c_int;
+    }
+
+    pub fn map(file: &File, len: usize) -> io::Result<NonNull<u8>> {
+        let ptr = unsafe {
+            mmap(
+                ptr::null_mut(),
+                len,
+                PROT_READ,
+                MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr as isize == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        NonNull::new(ptr.cast()).ok_or_else(io::Error::last_os_error)
+    }
+
+    pub unsafe fn unmap(ptr: NonNull<u8>, len: usize) {
+        unsafe { munmap(ptr.as_ptr().cast(), len) };
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::{
+        ffi::c_void,
+        fs::File,
+        io,
+        os::windows::io::AsRawHandle,
+        ptr::{self, NonNull},
+    };
+
+    const PAGE_READONLY: u32 = 0x02;
+    const FILE_MAP_READ: u32 = 0x04;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn CreateFileMappingW(
+            file: *mut c_void,
+            attributes: *const c_void,
+            protect: u32,
+            max_size_high: u32,
+            max_size_low: u32,
+            name: *const u16,
+        ) -> *mut c_void;
+        fn MapViewOfFile(
+            mapping: *mut c_void,
+            access: u32,
+            offset_high: u32,
+            offset_low: u32,
+            len: usize,
+        ) -> *mut c_void;
+        fn UnmapViewOfFile(addr: *const c_void) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    pub fn map(file: &File, len: usize) -> io::Result<NonNull<u8>> {
+        unsafe {
+            let mapping = CreateFileMappingW(
+                file.as_raw_handle(),
+                ptr::null(),
+                PAGE_READONLY,
+                0,
+                0,
+                ptr::null(),
+            );
+            if mapping.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            // The view keeps the mapping alive after its handle is closed.
+            let ptr = MapViewOfFile(mapping, FILE_MAP_READ, 0, 0, len);
+            let error = io::Error::last_os_error();
+            CloseHandle(mapping);
+            NonNull::new(ptr.cast()).ok_or(error)
+        }
+    }
+
+    pub unsafe fn unmap(ptr: NonNull<u8>, _len: usize) {
+        unsafe { UnmapViewOfFile(ptr.as_ptr().cast()) };
+    }
+}
+
+/// A read-only memory-mapped file.
+///
+/// Dereferences into file contents.
+pub struct Mmap {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+// SAFETY: the mapping is read-only and owned by this value.
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+impl Mmap {
+    /// Map the whole file into memory.
+    ///
+    /// ## Safety
+    /// The file must not be modified or truncated while it's mapped, by this
+    /// or any other process. Doing so changes memory behind an immutable
+    /// slice or causes a crash on access.
+    ///
+    /// ## Errors
+    /// Fails if file length can't be read or doesn't fit into memory, or if
+    /// mapping fails.
+    pub unsafe fn map(file: &File) -> io::Result<Self> {
+        let len = file.metadata()?.len();
+        let len = usize::try_from(len)
+            .map_err(|_| io::Error::new(io::ErrorKind::OutOfMemory, "file is too large to map"))?;
+        if len == 0 {
+            return Ok(Self {
+                ptr: NonNull::dangling(),
+                len,
+            });
+        }
+        Ok(Self {
+            ptr: sys::map(file, len)?,
+            len,
+        })
+    }
+
+    /// Get a reader over file contents.
+    pub fn reader(self) -> MmapReader {
+        Cursor::new(self)
+    }
+}
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: `ptr` points to `len` mapped bytes, or is dangling with `len` 0.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+impl AsRef<[u8]> for Mmap {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+impl Debug for Mmap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mmap")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            // SAFETY: the mapping was created by `sys::map` and is not used anymore.
+            unsafe { sys::unmap(self.ptr, self.len) };
+        }
+    }
+}
+
+/// A reader over a memory-mapped file.
+pub type MmapReader = Cursor<Mmap>;
@@ -0,0 +1,138 @@
+use std::io::{self, Read, Write};
+
+/// A reader that converts `\r\n` and `\r` line endings into `\n`.
+///
+/// Created with [ReadExt::normalize_newlines](crate::io::ReadExt::normalize_newlines).
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::io::{Cursor, Read};
+///
+/// let mut text = String::new();
+/// Cursor::new("windows\r\nold mac\runix\n")
+///     .normalize_newlines()
+///     .read_to_string(&mut text)
+///     .unwrap();
+/// assert_eq!(text, "windows\nold mac\nunix\n");
+/// ```
+pub struct NormalizeNewlines<R: Read> {
+    read: R,
+    cr: bool,
+}
+impl<R: Read> NormalizeNewlines<R> {
+    /// Create a new [NormalizeNewlines].
+    pub fn new(read: R) -> Self {
+        Self { read, cr: false }
+    }
+
+    /// Get the inner reader.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+}
+impl<R: Read> Read for NormalizeNewlines<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let len = self.read.read(buf)?;
+            if len == 0 {
+                return Ok(0);
+            }
+
+            let mut out = 0;
+            for i in 0..len {
+                let x = buf[i];
+                if x == b'\n' && self.cr {
+                    self.cr = false;
+                    continue;
+                }
+                self.cr = x == b'\r';
+                buf[out] = if self.cr { b'\n' } else { x };
+                out += 1;
+            }
+
+            // A lone `\n` after `\r` from the previous read produces nothing.
+            if out > 0 {
+                return Ok(out);
+            }
+        }
+    }
+}
+
+/// A writer that converts `\n` line endings into `\r\n`.
+///
+/// Existing `\r\n` sequences are left as is.
+///
+/// ```
+/// use libcommons::io::newline::CrlfWriter;
+/// use std::io::Write;
+///
+/// let mut writer = CrlfWriter::new(Vec::new());
+/// writer.write_all(b"one\ntwo\r\nthree\n").unwrap();
+/// assert_eq!(writer.into_inner(), b"one\r\ntwo\r\nthree\r\n");
+/// ```
+pub struct CrlfWriter<W: Write> {
+    write: W,
+    cr: bool,
+}
+impl<W: Write> CrlfWriter<W> {
+    /// Create a new [CrlfWriter].
+    pub fn new(write: W) -> Self {
+        Self { write, cr: false }
+    }
+
+    /// Get the inner writer.
+    pub fn into_inner(self) -> W {
+        self.write
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn inner(&self) -> &W {
+        &self.write
+    }
+
+    /// Get a mutable reference to the inner writer.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+}
+impl<W: Write> Write for CrlfWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let lone = buf.iter().enumerate().position(|(i, x)| {
+            *x == b'\n'
+                && if i == 0 {
+                    !self.cr
+                } else {
+                    buf[i - 1] != b'\r'
+                }
+        });
+
+        match lone {
+            Some(0) => {
+                self.write.write_all(b"\r\n")?;
+                self.cr = false;
+                Ok(1)
+            }
+            x => {
+                let len = self.write.write(&buf[..x.unwrap_or(buf.len())])?;
+                if len > 0 {
+                    self.cr = buf[len - 1] == b'\r';
+                }
+                Ok(len)
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write.flush()
+    }
+}
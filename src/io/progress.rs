@@ -0,0 +1,65 @@
+use std::time::{Duration, Instant};
+
+/// State of a running pipe, passed to [ReadExt::pipe_progress](crate::io::ReadExt::pipe_progress).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PipeProgress {
+    /// Total number of transferred bytes.
+    pub transferred: u64,
+    /// Smoothed transfer rate in bytes per second.
+    pub rate: f64,
+    /// Time since the transfer started.
+    pub elapsed: Duration,
+    /// Number of bytes transferred by the last write.
+    pub last_chunk: usize,
+}
+
+/// Time over which the rate is averaged.
+const RATE_WINDOW: f64 = 1.0;
+
+pub(crate) struct ProgressTracker {
+    start: Instant,
+    last: Instant,
+    /// Bytes transferred at `last`.
+    transferred_at_last: u64,
+    progress: PipeProgress,
+}
+impl ProgressTracker {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            start: now,
+            last: now,
+            transferred_at_last: 0,
+            progress: PipeProgress {
+                transferred: 0,
+                rate: 0.0,
+                elapsed: Duration::ZERO,
+                last_chunk: 0,
+            },
+        }
+    }
+
+    pub fn update(&mut self, transferred: u64) -> PipeProgress {
+        let now = Instant::now();
+        let chunk = transferred - self.progress.transferred;
+        let dt = now.duration_since(self.last).as_secs_f64();
+
+        if dt > 0.0 {
+            // Writes within the same instant are measured together.
+            let rate = (transferred - self.transferred_at_last) as f64 / dt;
+            self.progress.rate = if self.transferred_at_last == 0 {
+                rate
+            } else {
+                let alpha = 1.0 - (-dt / RATE_WINDOW).exp();
+                self.progress.rate + alpha * (rate - self.progress.rate)
+            };
+            self.last = now;
+            self.transferred_at_last = transferred;
+        }
+
+        self.progress.transferred = transferred;
+        self.progress.elapsed = now.duration_since(self.start);
+        self.progress.last_chunk = chunk as usize;
+        self.progress
+    }
+}
@@ -0,0 +1,213 @@
+//! Recording and replaying reads.
+//!
+//! ```
+//! use libcommons::{
+//!     io::{
+//!         flaky::FlakyReader,
+//!         record::{RecordingReader, ReplayReader, Trace},
+//!     },
+//!     prelude::*,
+//! };
+//! use std::io::{Cursor, Read};
+//!
+//! let mut reader = RecordingReader::new(FlakyReader::with_seed(Cursor::new(b"hello"), 7));
+//! let mut buf = [0u8; 4];
+//! let results: Vec<_> = (0..8).map(|_| reader.read(&mut buf).map_err(|x| x.kind())).collect();
+//!
+//! let mut saved = Vec::new();
+//! reader.trace().write_to(&mut saved).unwrap();
+//!
+//! let mut replay = ReplayReader::new(Trace::read_from(saved.as_slice()).unwrap());
+//! let replayed: Vec<_> = (0..8).map(|_| replay.read(&mut buf).map_err(|x| x.kind())).collect();
+//! assert_eq!(results, replayed);
+//! ```
+
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+};
+
+use super::{ReadExt, WriteExt};
+
+/// Error kinds that survive serialization. Others become [io::ErrorKind::Other].
+const KINDS: [io::ErrorKind; 20] = [
+    io::ErrorKind::Other,
+    io::ErrorKind::NotFound,
+    io::ErrorKind::PermissionDenied,
+    io::ErrorKind::ConnectionRefused,
+    io::ErrorKind::ConnectionReset,
+    io::ErrorKind::ConnectionAborted,
+    io::ErrorKind::NotConnected,
+    io::ErrorKind::AddrInUse,
+    io::ErrorKind::AddrNotAvailable,
+    io::ErrorKind::BrokenPipe,
+    io::ErrorKind::AlreadyExists,
+    io::ErrorKind::WouldBlock,
+    io::ErrorKind::InvalidInput,
+    io::ErrorKind::InvalidData,
+    io::ErrorKind::TimedOut,
+    io::ErrorKind::WriteZero,
+    io::ErrorKind::Interrupted,
+    io::ErrorKind::Unsupported,
+    io::ErrorKind::UnexpectedEof,
+    io::ErrorKind::OutOfMemory,
+];
+
+/// A single recorded read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadCall {
+    /// Length of the buffer passed to [Read::read].
+    pub requested: usize,
+    /// Bytes that were read, or kind of the returned error.
+    pub result: Result<Vec<u8>, io::ErrorKind>,
+}
+
+/// A sequence of recorded reads.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    /// Reads in the order they were made.
+    pub calls: Vec<ReadCall>,
+}
+impl Trace {
+    /// Serialize this trace.
+    ///
+    /// Error messages are not preserved, only their kinds.
+    pub fn write_to<W: Write>(&self, mut write: W) -> io::Result<()> {
+        write.write_varint_u64(self.calls.len() as u64)?;
+        for call in &self.calls {
+            write.write_varint_u64(call.requested as u64)?;
+            match &call.result {
+                Ok(data) => {
+                    write.write_u8(0)?;
+                    write.write_varint_u64(data.len() as u64)?;
+                    write.write_all(data)?;
+                }
+                Err(kind) => {
+                    write.write_u8(1)?;
+                    write.write_u8(KINDS.iter().position(|x| x == kind).unwrap_or(0) as u8)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Deserialize a trace written with [Trace::write_to].
+    ///
+    /// ## Errors
+    /// Returns [io::ErrorKind::InvalidData] if data is not a valid trace.
+    pub fn read_from<R: Read>(mut read: R) -> io::Result<Self> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid trace");
+        let len = read.read_varint_u64()?;
+        let mut calls = Vec::new();
+        for _ in 0..len {
+            let requested = read.read_varint_u64()? as usize;
+            let result = match read.read_u8()? {
+                0 => {
+                    let len = read.read_varint_u64()?;
+                    let mut data = Vec::new();
+                    if (&mut read).take(len).read_to_end(&mut data)? as u64 != len {
+                        return Err(invalid());
+                    }
+                    Ok(data)
+                }
+                1 => Err(*KINDS.get(read.read_u8()? as usize).ok_or_else(invalid)?),
+                _ => return Err(invalid()),
+            };
+            calls.push(ReadCall { requested, result });
+        }
+        Ok(Self { calls })
+    }
+}
+
+/// A reader that records every read into a [Trace].
+pub struct RecordingReader<R: Read> {
+    read: R,
+    trace: Trace,
+}
+impl<R: Read> RecordingReader<R> {
+    /// Create a new [RecordingReader].
+    pub fn new(read: R) -> Self {
+        Self {
+            read,
+            trace: Trace::default(),
+        }
+    }
+
+    /// Get the inner reader and the recorded trace.
+    pub fn into_inner(self) -> (R, Trace) {
+        (self.read, self.trace)
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Get reads recorded so far.
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+}
+impl<R: Read> Read for RecordingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let result = self.read.read(buf);
+        self.trace.calls.push(ReadCall {
+            requested: buf.len(),
+            result: match &result {
+                Ok(len) => Ok(buf[..*len].to_vec()),
+                Err(why) => Err(why.kind()),
+            },
+        });
+        result
+    }
+}
+
+/// A reader that reproduces a recorded [Trace].
+///
+/// Every read returns the result of the next recorded call. If the buffer
+/// is smaller than recorded data, the rest is returned by the following
+/// reads before moving on. Once the trace ends, reads return `0`.
+pub struct ReplayReader {
+    calls: VecDeque<ReadCall>,
+}
+impl ReplayReader {
+    /// Create a new [ReplayReader].
+    pub fn new(trace: Trace) -> Self {
+        Self {
+            calls: trace.calls.into(),
+        }
+    }
+
+    /// Get number of calls left to replay.
+    pub fn remaining(&self) -> usize {
+        self.calls.len()
+    }
+}
+impl Read for ReplayReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(call) = self.calls.front_mut() else {
+            return Ok(0);
+        };
+        match &mut call.result {
+            Ok(data) => {
+                let len = buf.len().min(data.len());
+                buf[..len].copy_from_slice(&data[..len]);
+                data.drain(..len);
+                if data.is_empty() {
+                    self.calls.pop_front();
+                }
+                Ok(len)
+            }
+            Err(kind) => {
+                let kind = *kind;
+                self.calls.pop_front();
+                Err(io::Error::new(kind, "replayed error"))
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, Read, Write},
+    thread,
+    time::Duration,
+};
+
+/// A reader that retries reads failing with [io::ErrorKind::Interrupted].
+///
+/// Created with [ReadExt::retry_interrupted](crate::io::ReadExt::retry_interrupted).
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::io::{self, Read};
+///
+/// struct Flaky(bool);
+/// impl Read for Flaky {
+///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+///         self.0 = !self.0;
+///         if self.0 {
+///             Err(io::ErrorKind::Interrupted.into())
+///         } else {
+///             buf[0] = b'a';
+///             Ok(1)
+///         }
+///     }
+/// }
+///
+/// assert!(Flaky(false).read(&mut [0u8; 1]).is_err());
+/// assert_eq!(Flaky(false).retry_interrupted().read(&mut [0u8; 1]).unwrap(), 1);
+/// ```
+pub struct RetryInterrupted<R: Read> {
+    read: R,
+}
+impl<R: Read> RetryInterrupted<R> {
+    /// Create a new [RetryInterrupted].
+    pub fn new(read: R) -> Self {
+        Self { read }
+    }
+
+    /// Get the inner reader.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+}
+impl<R: Read> Read for RetryInterrupted<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.read.read(buf) {
+                Err(why) if why.kind() == io::ErrorKind::Interrupted => {}
+                x => return x,
+            }
+        }
+    }
+}
+
+/// Delay between retries.
+///
+/// Delay starts at `initial` and doubles after every retry until it
+/// reaches `max`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+}
+impl Backoff {
+    /// Always wait the same amount of time.
+    pub const fn fixed(delay: Duration) -> Self {
+        Self {
+            initial: delay,
+            max: delay,
+        }
+    }
+
+    /// Start with `initial` delay and double it up to `max`.
+    pub const fn exponential(initial: Duration, max: Duration) -> Self {
+        Self { initial, max }
+    }
+}
+impl From<Duration> for Backoff {
+    fn from(value: Duration) -> Self {
+        Self::fixed(value)
+    }
+}
+
+/// A reader that retries reads failing with [io::ErrorKind::WouldBlock]
+/// or [io::ErrorKind::Interrupted].
+///
+/// Created with [ReadExt::retry_would_block](crate::io::ReadExt::retry_would_block).
+///
+/// Before retrying after [io::ErrorKind::WouldBlock], the thread sleeps
+/// according to [Backoff]. This makes a non-blocking reader behave like
+/// a blocking one.
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::{
+///     io::{self, Read},
+///     time::Duration,
+/// };
+///
+/// struct Slow(usize);
+/// impl Read for Slow {
+///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+///         if self.0 < 3 {
+///             self.0 += 1;
+///             Err(io::ErrorKind::WouldBlock.into())
+///         } else {
+///             buf[0] = b'a';
+///             Ok(1)
+///         }
+///     }
+/// }
+///
+/// let mut reader = Slow(0).retry_would_block(Duration::from_millis(1));
+/// assert_eq!(reader.read(&mut [0u8; 1]).unwrap(), 1);
+/// ```
+pub struct RetryWouldBlock<R: Read> {
+    read: R,
+    backoff: Backoff,
+}
+impl<R: Read> RetryWouldBlock<R> {
+    /// Create a new [RetryWouldBlock].
+    pub fn new(read: R, backoff: impl Into<Backoff>) -> Self {
+        Self {
+            read,
+            backoff: backoff.into(),
+        }
+    }
+
+    /// Get the inner reader.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Get the delay between retries.
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+
+    /// Set the delay between retries.
+    pub fn set_backoff(&mut self, backoff: impl Into<Backoff>) {
+        self.backoff = backoff.into();
+    }
+}
+impl<R: Read> Read for RetryWouldBlock<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut delay = self.backoff.initial;
+        loop {
+            match self.read.read(buf) {
+                Err(why) if why.kind() == io::ErrorKind::Interrupted => {}
+                Err(why) if why.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(delay);
+                    delay = delay.saturating_mul(2).min(self.backoff.max);
+                }
+                x => return x,
+            }
+        }
+    }
+}
+
+/// How [WriteExt::write_all_retrying](crate::io::WriteExt::write_all_retrying)
+/// handles transient errors.
+///
+/// [io::ErrorKind::Interrupted] is always retried immediately.
+/// [io::ErrorKind::WouldBlock] is retried after sleeping according to
+/// `backoff`, up to `retries` times in a row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    pub retries: usize,
+    pub backoff: Backoff,
+}
+impl RetryPolicy {
+    /// Create a new [RetryPolicy].
+    pub const fn new(retries: usize, backoff: Backoff) -> Self {
+        Self { retries, backoff }
+    }
+}
+
+/// Error of [WriteExt::write_all_retrying](crate::io::WriteExt::write_all_retrying).
+#[derive(Debug)]
+pub struct WriteAllError {
+    /// Number of bytes written before the failure.
+    pub written: usize,
+    pub error: io::Error,
+}
+impl Display for WriteAllError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (after writing {} bytes)", self.error, self.written)
+    }
+}
+impl Error for WriteAllError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+impl From<WriteAllError> for io::Error {
+    fn from(value: WriteAllError) -> Self {
+        value.error
+    }
+}
+
+pub(crate) fn write_all_retrying<W>(
+    write: &mut W,
+    buf: &[u8],
+    policy: RetryPolicy,
+) -> Result<(), WriteAllError>
+where
+    W: Write + ?Sized,
+{
+    let mut written = 0;
+    let mut retries = 0;
+    let mut delay = policy.backoff.initial;
+    while written < buf.len() {
+        match write.write(&buf[written..]) {
+            Ok(0) => {
+                return Err(WriteAllError {
+                    written,
+                    error: io::Error::new(io::ErrorKind::WriteZero, "failed to write whole buffer"),
+                });
+            }
+            Ok(len) => {
+                written += len;
+                retries = 0;
+                delay = policy.backoff.initial;
+            }
+            Err(why) if why.kind() == io::ErrorKind::Interrupted => {}
+            Err(why) if why.kind() == io::ErrorKind::WouldBlock && retries < policy.retries => {
+                retries += 1;
+                thread::sleep(delay);
+                delay = delay.saturating_mul(2).min(policy.backoff.max);
+            }
+            Err(error) => return Err(WriteAllError { written, error }),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::io::{self, Read};
+
+/// A reader that reads at most a specified number of bytes and fails if
+/// there is more.
+///
+/// Created with [ReadExt::take_strict](crate::io::ReadExt::take_strict).
+///
+/// Unlike [std::io::Take], once the limit is reached this checks whether the
+/// inner reader has ended, and returns [io::ErrorKind::InvalidData] if it
+/// didn't.
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::io::{Cursor, ErrorKind, Read};
+///
+/// let mut text = String::new();
+/// Cursor::new(b"short").take_strict(8).read_to_string(&mut text).unwrap();
+/// assert_eq!(text, "short");
+///
+/// let mut text = String::new();
+/// let err = Cursor::new(b"way too long")
+///     .take_strict(8)
+///     .read_to_string(&mut text)
+///     .unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::InvalidData);
+/// ```
+///
+/// ## Errors
+/// To check for the end, a single byte is read from the inner reader. If
+/// there was more data, that byte is lost.
+pub struct TakeStrict<R: Read> {
+    read: R,
+    limit: u64,
+}
+impl<R: Read> TakeStrict<R> {
+    /// Create a new [TakeStrict].
+    pub fn new(read: R, limit: u64) -> Self {
+        Self { read, limit }
+    }
+
+    /// Get the inner reader.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Get number of bytes that can still be read.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+}
+impl<R: Read> Read for TakeStrict<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.limit == 0 {
+            return match self.read.read(&mut [0u8])? {
+                0 => Ok(0),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "read limit exceeded",
+                )),
+            };
+        }
+
+        let max = buf.len().min(self.limit.try_into().unwrap_or(usize::MAX));
+        let len = self.read.read(&mut buf[..max])?;
+        self.limit -= len as u64;
+        Ok(len)
+    }
+}
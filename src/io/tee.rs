@@ -0,0 +1,68 @@
+use std::io::{self, Read, Write};
+
+/// A reader that copies everything it reads into a writer.
+///
+/// Created with [ReadExt::tee](crate::io::ReadExt::tee).
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::io::{Cursor, Read};
+///
+/// let mut reader = Cursor::new(b"hello, world").tee(Vec::new());
+///
+/// let mut hello = [0u8; 5];
+/// reader.read_exact(&mut hello).unwrap();
+/// assert_eq!(&hello, b"hello");
+/// assert_eq!(reader.writer(), b"hello");
+///
+/// let mut rest = String::new();
+/// reader.read_to_string(&mut rest).unwrap();
+/// assert_eq!(rest, ", world");
+/// assert_eq!(reader.into_inner().1, b"hello, world");
+/// ```
+///
+/// ## Errors
+/// If writing fails, the error is returned from [Read::read] and data
+/// that was read from the inner reader is lost.
+pub struct TeeReader<R: Read, W: Write> {
+    read: R,
+    write: W,
+}
+impl<R: Read, W: Write> TeeReader<R, W> {
+    /// Create a new [TeeReader].
+    pub fn new(read: R, write: W) -> Self {
+        Self { read, write }
+    }
+
+    /// Get the inner reader and the writer.
+    pub fn into_inner(self) -> (R, W) {
+        (self.read, self.write)
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Get a reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.write
+    }
+
+    /// Get a mutable reference to the writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+}
+impl<R: Read, W: Write> Read for TeeReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.read.read(buf)?;
+        self.write.write_all(&buf[..len])?;
+        Ok(len)
+    }
+}
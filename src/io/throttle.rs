@@ -0,0 +1,95 @@
+use std::{
+    io::{self, Read},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A reader that caps its throughput.
+///
+/// Created with [ReadExt::throttle](crate::io::ReadExt::throttle).
+///
+/// Reads are limited to at most a second worth of data, and the calling
+/// thread is put to sleep whenever more data was read than the limit allows
+/// for the time passed since the reader was created. A limit of `0` disables
+/// throttling.
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::{io::Cursor, time::Instant};
+///
+/// let start = Instant::now();
+/// let mut out = Vec::new();
+/// Cursor::new([0u8; 150])
+///     .throttle(1000)
+///     .pipe::<64, _>(&mut out)
+///     .unwrap();
+///
+/// assert_eq!(out.len(), 150);
+/// assert!(start.elapsed().as_millis() >= 140);
+/// ```
+pub struct Throttle<R: Read> {
+    read: R,
+    bytes_per_sec: u64,
+    start: Instant,
+    total: u64,
+}
+impl<R: Read> Throttle<R> {
+    /// Create a new [Throttle].
+    pub fn new(read: R, bytes_per_sec: u64) -> Self {
+        Self {
+            read,
+            bytes_per_sec,
+            start: Instant::now(),
+            total: 0,
+        }
+    }
+
+    /// Get the inner reader.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Get current limit in bytes per second.
+    pub fn limit(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    /// Change the limit.
+    ///
+    /// This restarts the measurement, so data read before does not
+    /// affect the new limit.
+    pub fn set_limit(&mut self, bytes_per_sec: u64) {
+        self.bytes_per_sec = bytes_per_sec;
+        self.start = Instant::now();
+        self.total = 0;
+    }
+}
+impl<R: Read> Read for Throttle<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.bytes_per_sec == 0 {
+            return self.read.read(buf);
+        }
+
+        let max = buf
+            .len()
+            .min(self.bytes_per_sec.try_into().unwrap_or(usize::MAX));
+        let len = self.read.read(&mut buf[..max])?;
+        self.total += len as u64;
+
+        let expected = Duration::from_secs_f64(self.total as f64 / self.bytes_per_sec as f64);
+        if let Some(wait) = expected.checked_sub(self.start.elapsed()) {
+            thread::sleep(wait);
+        }
+        Ok(len)
+    }
+}
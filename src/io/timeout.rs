@@ -0,0 +1,171 @@
+use std::{
+    io::{self, Read},
+    mem::ManuallyDrop,
+    net::TcpStream,
+    ptr,
+    time::{Duration, Instant},
+};
+
+/// A reader with a settable read timeout.
+pub trait ReadTimeout {
+    /// Get timeout for blocking reads. `None` means no timeout.
+    fn read_timeout(&self) -> io::Result<Option<Duration>>;
+
+    /// Set timeout for blocking reads. `None` means no timeout.
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+}
+impl ReadTimeout for TcpStream {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        TcpStream::read_timeout(self)
+    }
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+}
+#[cfg(unix)]
+impl ReadTimeout for std::os::unix::net::UnixStream {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        std::os::unix::net::UnixStream::read_timeout(self)
+    }
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        std::os::unix::net::UnixStream::set_read_timeout(self, timeout)
+    }
+}
+impl<T: ReadTimeout> ReadTimeout for &T {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        (*self).read_timeout()
+    }
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        (*self).set_read_timeout(timeout)
+    }
+}
+
+/// A reader that fails with [io::ErrorKind::TimedOut] if a read
+/// takes too long.
+///
+/// Created with [ReadExt::timeout](crate::io::ReadExt::timeout).
+///
+/// Every read is limited by the configured timeout and, if one is set,
+/// by time left until the deadline. Once the deadline has passed, all
+/// reads fail.
+///
+/// Reads change the read timeout of the inner reader. The previous one
+/// is restored when this reader is dropped or unwrapped with
+/// [into_inner](Self::into_inner).
+///
+/// ```
+/// use libcommons::prelude::*;
+/// use std::{
+///     io::{ErrorKind, Read},
+///     net::{TcpListener, TcpStream},
+///     time::Duration,
+/// };
+///
+/// let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+/// let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+///
+/// let mut reader = stream.timeout(Duration::from_millis(50));
+/// let err = reader.read(&mut [0u8; 16]).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::TimedOut);
+///
+/// let stream = reader.into_inner();
+/// assert_eq!(stream.read_timeout().unwrap(), None);
+/// ```
+pub struct TimeoutReader<R: Read + ReadTimeout> {
+    read: R,
+    timeout: Duration,
+    deadline: Option<Instant>,
+    /// Read timeout of `read` before it was first changed.
+    previous: Option<Option<Duration>>,
+}
+impl<R: Read + ReadTimeout> TimeoutReader<R> {
+    /// Create a new [TimeoutReader].
+    pub fn new(read: R, timeout: Duration) -> Self {
+        Self {
+            read,
+            timeout,
+            deadline: None,
+            previous: None,
+        }
+    }
+
+    /// Also fail all reads after `deadline`.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Restore the read timeout of the inner reader and get it.
+    pub fn into_inner(mut self) -> R {
+        self.restore();
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so `read` is read only once.
+        // Other fields hold no resources.
+        unsafe { ptr::read(&this.read) }
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Get the timeout of a single read.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Set the timeout of a single read.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Get the deadline after which all reads fail.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Set the deadline after which all reads fail.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    fn restore(&mut self) {
+        if let Some(x) = self.previous.take() {
+            let _ = self.read.set_read_timeout(x);
+        }
+    }
+}
+impl<R: Read + ReadTimeout> Read for TimeoutReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let timeout = match self.deadline {
+            Some(x) => self
+                .timeout
+                .min(x.saturating_duration_since(Instant::now())),
+            None => self.timeout,
+        };
+        if timeout.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        }
+
+        if self.previous.is_none() {
+            self.previous = Some(self.read.read_timeout()?);
+        }
+        self.read.set_read_timeout(Some(timeout))?;
+        match self.read.read(buf) {
+            Err(x) if x.kind() == io::ErrorKind::WouldBlock => {
+                Err(io::Error::new(io::ErrorKind::TimedOut, x))
+            }
+            x => x,
+        }
+    }
+}
+impl<R: Read + ReadTimeout> Drop for TimeoutReader<R> {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
@@ -1,4 +1,4 @@
-use std::{
+use core::{
     iter::FusedIterator,
     mem::{MaybeUninit, swap},
 };
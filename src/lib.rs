@@ -1,12 +1,21 @@
 //! # Libcommons
 //!
 //! Utilities I don't want to write again.
+//!
+//! ## `no_std`
+//! Without the `std` feature (enabled by default), the crate is `no_std`.
+//! `StackString`, `PreIter`, `Matrix` and push-based decoders are still
+//! available. The `alloc` feature enables parts that need a heap, like
+//! `MatrixDyn`.
+//!
+//! Modules that need an OS (`io`, `dirs`, `ffi`) enable `std`.
 
 #![allow(incomplete_features)]
-#![cfg_attr(
-    feature = "nightly",
-    feature(generic_const_exprs, maybe_uninit_array_assume_init, array_try_map)
-)]
+#![cfg_attr(feature = "nightly", feature(generic_const_exprs))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 #[cfg(feature = "ffi")]
 pub mod ffi;
@@ -16,7 +25,7 @@ pub mod fs;
 pub mod io;
 #[cfg(feature = "iter")]
 pub mod iter;
-#[cfg(all(feature = "matrix", feature = "nightly"))]
+#[cfg(feature = "matrix")]
 pub mod matrix;
 #[cfg(feature = "dirs")]
 pub mod os;
@@ -25,15 +34,12 @@ pub mod str;
 #[cfg(any(feature = "extra_traits", feature = "result"))]
 pub mod util;
 
-#[cfg(all(feature = "matrix", not(feature = "nightly")))]
-compile_error!("'matrix' feature requires 'nightly'!");
-
 pub mod prelude {
     #[cfg(feature = "io")]
-    pub use crate::io::ReadExt;
+    pub use crate::io::{ReadExt, WriteExt};
     #[cfg(feature = "iter")]
     pub use crate::iter::IterExt;
-    #[cfg(feature = "str")]
+    #[cfg(all(feature = "str", feature = "std"))]
     pub use crate::str::AsUtf8;
     #[cfg(feature = "extra_traits")]
     pub use crate::util::{Fun, ResultExt};
@@ -1,13 +1,27 @@
-use std::{
-    fmt::Debug,
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{
+    error::Error,
+    fmt::{self, Debug, Display},
     hint::black_box,
-    mem::MaybeUninit,
+    mem::{self, ManuallyDrop, MaybeUninit},
     ops::{
         Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Rem, RemAssign, Sub,
         SubAssign,
     },
+    ptr,
 };
 
+pub mod complex;
+#[cfg(feature = "alloc")]
+pub mod dynamic;
+pub mod fixed;
+#[cfg(any(feature = "glam", feature = "nalgebra"))]
+mod interop;
+pub mod linalg;
+#[cfg(feature = "rand")]
+mod random;
+
 /// Matrix creation shortcut.
 #[macro_export]
 macro_rules! mat {
@@ -50,64 +64,29 @@ pub trait Num:
     const ZERO: Self;
     const ONE: Self;
 }
+
+/// A floating point number type.
+///
+/// Used by algorithms that need to compare magnitudes of numbers
+/// (i.e. pivoting in [Matrix::lu]).
+///
+/// Implementations for `f32` and `f64` need the `std` feature.
+pub trait Float: Num + PartialOrd + Copy {
+    /// Difference between `1.0` and the next representable number.
+    const EPSILON: Self;
+
+    /// Absolute value.
+    fn abs(self) -> Self;
+
+    /// Square root.
+    fn sqrt(self) -> Self;
+}
 macro_rules! impl_num_i {
     ($($ty:ty)*) => {$(
         impl Num for $ty {
             const ZERO: Self = 0;
             const ONE: Self = 1;
         }
-
-        impl Matrix<$ty, 2, 2> {
-            /// Identity matrix.
-            ///
-            /// Multiplying a matrix by identity results in that matrix.
-            ///
-            /// ## Future compatibility.
-            /// As of right now, identity matrixes are hardcoded for
-            /// each [Num] type.
-            ///
-            /// When generating a matrix at compile time will stop
-            /// crashing the compiler, that will be used instead.
-            pub const IDENTITY: Self = mat! {
-                1, 0,
-                0, 1,
-            };
-        }
-        impl Matrix<$ty, 3, 3> {
-            /// Identity matrix.
-            ///
-            /// Multiplying a matrix by identity results in that matrix.
-            ///
-            /// ## Future compatibility.
-            /// As of right now, identity matrixes are hardcoded for
-            /// each [Num] type.
-            ///
-            /// When generating a matrix at compile time will stop
-            /// crashing the compiler, that will be used instead.
-            pub const IDENTITY: Self = mat! {
-                1, 0, 0,
-                0, 1, 0,
-                0, 0, 1,
-            };
-        }
-        impl Matrix<$ty, 4, 4> {
-            /// Identity matrix.
-            ///
-            /// Multiplying a matrix by identity results in that matrix.
-            ///
-            /// ## Future compatibility.
-            /// As of right now, identity matrixes are hardcoded for
-            /// each [Num] type.
-            ///
-            /// When generating a matrix at compile time will stop
-            /// crashing the compiler, that will be used instead.
-            pub const IDENTITY: Self = mat! {
-                1, 0, 0, 0,
-                0, 1, 0, 0,
-                0, 0, 1, 0,
-                0, 0, 0, 1,
-            };
-        }
     )*};
 }
 macro_rules! impl_num_f {
@@ -116,57 +95,17 @@ macro_rules! impl_num_f {
             const ZERO: Self = 0.0;
             const ONE: Self = 1.0;
         }
+        #[cfg(feature = "std")]
+        impl Float for $ty {
+            const EPSILON: Self = <$ty>::EPSILON;
 
-        impl Matrix<$ty, 2, 2> {
-            /// Identity matrix.
-            ///
-            /// Multiplying a matrix by identity results in that matrix.
-            ///
-            /// ## Future compatibility.
-            /// As of right now, identity matrixes are hardcoded for
-            /// each [Num] type.
-            ///
-            /// When generating a matrix at compile time will stop
-            /// crashing the compiler, that will be used instead.
-            pub const IDENTITY: Self = mat! {
-                1.0, 0.0,
-                0.0, 1.0,
-            };
-        }
-        impl Matrix<$ty, 3, 3> {
-            /// Identity matrix.
-            ///
-            /// Multiplying a matrix by identity results in that matrix.
-            ///
-            /// ## Future compatibility.
-            /// As of right now, identity matrixes are hardcoded for
-            /// each [Num] type.
-            ///
-            /// When generating a matrix at compile time will stop
-            /// crashing the compiler, that will be used instead.
-            pub const IDENTITY: Self = mat! {
-                1.0, 0.0, 0.0,
-                0.0, 1.0, 0.0,
-                0.0, 0.0, 1.0,
-            };
-        }
-        impl Matrix<$ty, 4, 4> {
-            /// Identity matrix.
-            ///
-            /// Multiplying a matrix by identity results in that matrix.
-            ///
-            /// ## Future compatibility.
-            /// As of right now, identity matrixes are hardcoded for
-            /// each [Num] type.
-            ///
-            /// When generating a matrix at compile time will stop
-            /// crashing the compiler, that will be used instead.
-            pub const IDENTITY: Self = mat! {
-                1.0, 0.0, 0.0, 0.0,
-                0.0, 1.0, 0.0, 0.0,
-                0.0, 0.0, 1.0, 0.0,
-                0.0, 0.0, 0.0, 1.0,
-            };
+            fn abs(self) -> Self {
+                <$ty>::abs(self)
+            }
+
+            fn sqrt(self) -> Self {
+                <$ty>::sqrt(self)
+            }
         }
     )*};
 }
@@ -178,40 +117,201 @@ impl_num_f! {
     f32 f64
 }
 
+/// Conversion between primitive number types with `as` semantics.
+///
+/// Unlike [From], this is implemented for all pairs of primitive
+/// number types, including lossy ones (i.e. `f32` to `u8`).
+pub trait Cast<T> {
+    /// Convert this number using `as`.
+    fn cast(self) -> T;
+}
+macro_rules! impl_cast {
+    ($($ty:ty)*) => {
+        impl_cast!(@from [$($ty)*] $($ty)*);
+    };
+    (@from $all:tt $($from:ty)*) => {$(
+        impl_cast!(@to $from => $all);
+    )*};
+    (@to $from:ty => [$($to:ty)*]) => {$(
+        impl Cast<$to> for $from {
+            fn cast(self) -> $to {
+                self as $to
+            }
+        }
+    )*};
+}
+impl_cast! {
+    i8 i16 i32 i64 i128 isize
+    u8 u16 u32 u64 u128 usize
+    f32 f64
+}
+
+/// Wrong number of elements was provided to create a matrix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthError;
+impl Display for LengthError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("number of elements does not match matrix size")
+    }
+}
+impl Error for LengthError {}
+
+/// Counts characters written into it.
+struct CharCount(usize);
+impl fmt::Write for CharCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Print a cell with formatter's precision.
+fn fmt_cell<T: Display>(
+    cell: &T,
+    precision: Option<usize>,
+    f: &mut impl fmt::Write,
+) -> fmt::Result {
+    match precision {
+        Some(p) => write!(f, "{cell:.p$}"),
+        None => write!(f, "{cell}"),
+    }
+}
+
+/// Print a flattened array of rows as aligned rows.
+///
+/// `widths` must have an element for each cell in a row.
+fn fmt_cells<T: Display>(
+    cells: &[T],
+    widths: &mut [usize],
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let rows = widths.len();
+    let precision = f.precision();
+    widths.fill(f.width().unwrap_or(0));
+    for (i, cell) in cells.iter().enumerate() {
+        let mut count = CharCount(0);
+        fmt_cell(cell, precision, &mut count)?;
+        widths[i % rows] = widths[i % rows].max(count.0);
+    }
+
+    for (y, row) in cells.chunks(rows.max(1)).enumerate() {
+        if y != 0 {
+            f.write_str("\n")?;
+        }
+        f.write_str("[")?;
+        for (x, cell) in row.iter().enumerate() {
+            if x != 0 {
+                f.write_str(", ")?;
+            }
+            let mut count = CharCount(0);
+            fmt_cell(cell, precision, &mut count)?;
+            for _ in count.0..widths[x] {
+                f.write_str(" ")?;
+            }
+            fmt_cell(cell, precision, f)?;
+        }
+        f.write_str("]")?;
+    }
+    Ok(())
+}
+
+/// Compile-time check of the number of elements passed to [Matrix::new].
+struct Len<const LEN: usize, const ROWS: usize, const COLUMNS: usize>;
+impl<const LEN: usize, const ROWS: usize, const COLUMNS: usize> Len<LEN, ROWS, COLUMNS> {
+    const CHECK: () = assert!(
+        LEN == ROWS * COLUMNS,
+        "number of elements does not match matrix size"
+    );
+}
+
+/// Reinterpret a value as another type with the same layout.
+///
+/// ## Safety
+/// `A` and `B` must have the same size and `value` must be a valid `B`.
+const unsafe fn cast<A, B>(value: A) -> B {
+    let value = ManuallyDrop::new(value);
+    unsafe { ptr::read(&value as *const ManuallyDrop<A> as *const B) }
+}
+
+/// Create an array of uninitialized rows.
+const fn uninit_rows<T, const ROWS: usize, const COLUMNS: usize>()
+-> [[MaybeUninit<T>; ROWS]; COLUMNS] {
+    // An array of `MaybeUninit` does not require initialization.
+    unsafe { MaybeUninit::<[[MaybeUninit<T>; ROWS]; COLUMNS]>::uninit().assume_init() }
+}
+
 /// A matrix.
 ///
 /// Rows and columns represent the y and x coordinates respectively.
+///
+/// `ROWS` is the length of a single row and `COLUMNS` is the length
+/// of a single column, so `Matrix<T, 3, 2>` is written as 2 rows
+/// of 3 values in [mat!]. Column vectors are `Matrix<T, 1, N>`.
 #[repr(transparent)]
 #[derive(Clone, Copy, Debug, Hash)]
-pub struct Matrix<T, const ROWS: usize, const COLUMNS: usize>([T; ROWS * COLUMNS])
-where
-    [T; ROWS * COLUMNS]:;
+pub struct Matrix<T, const ROWS: usize, const COLUMNS: usize>([[T; ROWS]; COLUMNS]);
 impl<T, const SIZE: usize> Matrix<T, SIZE, SIZE>
 where
-    [T; SIZE * SIZE]:,
     T: Num,
 {
-    /// Obtain the identity matrix of this size.
+    /// Identity matrix.
     ///
-    /// ## Future compatibility
-    /// This function may get converted to a const fn when
-    /// Rust will stop crashing when compiling this.
-    pub fn identity() -> Self {
+    /// Multiplying a matrix by identity results in that matrix.
+    pub const IDENTITY: Self = {
         let mut matrix = Self::ZERO;
         let mut i = 0usize;
         while i < SIZE {
-            let mut t = T::ONE;
-            std::mem::swap(&mut t, &mut matrix.0[i]);
-            std::mem::forget(t);
+            mem::forget(mem::replace(&mut matrix.0[i][i], T::ONE));
             i += 1;
         }
         matrix
+    };
+
+    /// Obtain the identity matrix of this size.
+    ///
+    /// Same as [IDENTITY](Self::IDENTITY).
+    pub const fn identity() -> Self {
+        Self::IDENTITY
+    }
+
+    /// Sum of the elements on the main diagonal.
+    pub fn trace(&self) -> T {
+        let mut sum = T::ZERO;
+        for x in self.diagonal_ref() {
+            sum += x.clone();
+        }
+        sum
     }
 }
-impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<MaybeUninit<T>, ROWS, COLUMNS>
-where
-    [MaybeUninit<T>; ROWS * COLUMNS]:,
-{
+impl<T, const SIZE: usize> Matrix<T, SIZE, SIZE> {
+    /// Get references to elements on the main diagonal.
+    pub fn diagonal_ref(&self) -> [&T; SIZE] {
+        core::array::from_fn(|i| &self.0[i][i])
+    }
+
+    /// Get mutable references to elements on the main diagonal.
+    pub fn diagonal_mut(&mut self) -> [&mut T; SIZE] {
+        let ptr = self.0.as_flattened_mut().as_mut_ptr();
+        // Every index is visited exactly once, so references never alias.
+        core::array::from_fn(|i| unsafe { &mut *ptr.add(i + i * SIZE) })
+    }
+
+    /// Copy elements on the main diagonal.
+    pub fn diagonal(&self) -> [T; SIZE]
+    where
+        T: Clone,
+    {
+        self.diagonal_ref().map(T::clone)
+    }
+
+    /// Replace elements on the main diagonal.
+    pub fn set_diagonal(&mut self, diagonal: [T; SIZE]) {
+        for (x, v) in self.diagonal_mut().into_iter().zip(diagonal) {
+            *x = v;
+        }
+    }
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<MaybeUninit<T>, ROWS, COLUMNS> {
     /// Assume that this matrix is initialized.
     ///
     /// ## Safety
@@ -220,94 +320,97 @@ where
     ///
     /// Calling this on a matrix with uninitialized cells
     /// is undefined behavior.
-    pub unsafe fn assume_init(self) -> Matrix<T, ROWS, COLUMNS>
-    where
-        [T; ROWS * COLUMNS]:,
-    {
-        unsafe { Matrix::new(MaybeUninit::array_assume_init(self.0)) }
+    pub const unsafe fn assume_init(self) -> Matrix<T, ROWS, COLUMNS> {
+        unsafe { cast(self) }
     }
 }
-impl<'a, T, const ROWS: usize, const COLUMNS: usize> Matrix<&'a T, ROWS, COLUMNS>
+impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<&T, ROWS, COLUMNS>
 where
-    [T; ROWS * COLUMNS]:,
-    [&'a T; ROWS * COLUMNS]:,
     T: Copy,
 {
     /// Create a copy of this matrix with all values copied.
     ///
     /// Requires [Copy] to be implemented for all matrix elements.
     pub fn copied(&self) -> Matrix<T, ROWS, COLUMNS> {
-        unsafe {
-            let mut mat: [MaybeUninit<T>; ROWS * COLUMNS] =
-                [const { MaybeUninit::uninit() }; ROWS * COLUMNS];
-            for (i, x) in self.0.iter().copied().copied().enumerate() {
-                mat[i].write(x);
-            }
-            Matrix::new(MaybeUninit::array_assume_init(mat))
-        }
+        Matrix(self.0.map(|row| row.map(|x| *x)))
     }
 }
-impl<'a, T, const ROWS: usize, const COLUMNS: usize> Matrix<&'a T, ROWS, COLUMNS>
+impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<&T, ROWS, COLUMNS>
 where
-    [T; ROWS * COLUMNS]:,
-    [&'a T; ROWS * COLUMNS]:,
     T: Clone,
 {
     /// Create a copy of this matrix with all values cloned.
     ///
     /// Requires [Clone] to be implemented for all matrix elements.
     pub fn cloned(&self) -> Matrix<T, ROWS, COLUMNS> {
-        unsafe {
-            let mut mat: [MaybeUninit<T>; ROWS * COLUMNS] =
-                [const { MaybeUninit::uninit() }; ROWS * COLUMNS];
-            for (i, x) in self.0.iter().copied().cloned().enumerate() {
-                mat[i].write(x);
-            }
-            Matrix::new(MaybeUninit::array_assume_init(mat))
-        }
+        Matrix(self.0.map(|row| row.map(T::clone)))
     }
 }
-impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<T, ROWS, COLUMNS>
-where
-    [T; ROWS * COLUMNS]:,
-{
+impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<T, ROWS, COLUMNS> {
     /// Create a new matrix.
     ///
-    /// Matrix layout is a flattened array of rows.
-    pub const fn new(value: [T; ROWS * COLUMNS]) -> Self {
-        Self(value)
+    /// Matrix layout is a flattened array of rows. Fails to compile
+    /// if the array does not have exactly `ROWS * COLUMNS` elements.
+    pub const fn new<const LEN: usize>(value: [T; LEN]) -> Self {
+        let () = Len::<LEN, ROWS, COLUMNS>::CHECK;
+        unsafe { cast(value) }
+    }
+
+    /// Create a new matrix from an array of rows.
+    pub const fn from_rows(rows: [[T; ROWS]; COLUMNS]) -> Self {
+        Self(rows)
     }
 
     /// Create a new uninitialized matrix.
-    pub fn new_uninit() -> Matrix<MaybeUninit<T>, ROWS, COLUMNS>
-    where
-        [MaybeUninit<T>; ROWS * COLUMNS]:,
-    {
-        Matrix::new([const { MaybeUninit::<T>::uninit() }; ROWS * COLUMNS])
+    pub const fn new_uninit() -> Matrix<MaybeUninit<T>, ROWS, COLUMNS> {
+        Matrix(uninit_rows())
     }
 
-    /// Obtain transposed matrix.
+    /// Create a new matrix from an iterator.
     ///
-    /// Might be converted to a const fn in the future.
-    pub const fn transposed<'a>(&'a self) -> Matrix<&'a T, COLUMNS, ROWS>
+    /// Matrix layout is a flattened array of rows. Iterator must yield
+    /// exactly `ROWS * COLUMNS` elements, otherwise [LengthError]
+    /// is returned.
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, LengthError>
     where
-        [&'a T; COLUMNS * ROWS]:,
-        [MaybeUninit<&'a T>; COLUMNS * ROWS]:,
+        I: IntoIterator<Item = T>,
     {
-        unsafe {
-            let mut mat: [MaybeUninit<&'a T>; COLUMNS * ROWS] =
-                [MaybeUninit::uninit(); COLUMNS * ROWS];
-            let mut x = 0usize;
-            while x < COLUMNS {
-                let mut y = 0usize;
-                while y < ROWS {
-                    mat[x + y * COLUMNS].write(self.get(y, x).unwrap_unchecked());
-                    y += 1;
+        let mut iter = iter.into_iter();
+        let mut new = Self::new_uninit();
+        let cells = new.0.as_flattened_mut();
+        for i in 0..ROWS * COLUMNS {
+            match iter.next() {
+                Some(x) => {
+                    cells[i].write(x);
+                }
+                None => {
+                    for x in &mut cells[..i] {
+                        unsafe { x.assume_init_drop() };
+                    }
+                    return Err(LengthError);
                 }
-                x += 1;
             }
-            Matrix::new(MaybeUninit::array_assume_init(mat))
         }
+        let new = unsafe { new.assume_init() };
+        if iter.next().is_some() {
+            return Err(LengthError);
+        }
+        Ok(new)
+    }
+
+    /// Obtain transposed matrix.
+    pub const fn transposed(&self) -> Matrix<&T, COLUMNS, ROWS> {
+        let mut mat = uninit_rows::<&T, COLUMNS, ROWS>();
+        let mut x = 0usize;
+        while x < ROWS {
+            let mut y = 0usize;
+            while y < COLUMNS {
+                mat[x][y].write(&self.0[y][x]);
+                y += 1;
+            }
+            x += 1;
+        }
+        unsafe { Matrix(mat).assume_init() }
     }
 
     /// Get a reference to a cell.
@@ -315,7 +418,7 @@ where
         if row >= ROWS || col >= COLUMNS {
             None
         } else {
-            Some(&self.0[row + col * ROWS])
+            Some(&self.0[col][row])
         }
     }
 
@@ -324,7 +427,7 @@ where
         if row >= ROWS || col >= COLUMNS {
             None
         } else {
-            Some(&mut self.0[row + col * ROWS])
+            Some(&mut self.0[col][row])
         }
     }
 
@@ -334,7 +437,7 @@ where
     /// Caller must ensure that provided cell does not exceed
     /// the bounds of the matrix.
     pub unsafe fn get_unchecked(&self, row: usize, col: usize) -> &T {
-        unsafe { self.0.get_unchecked(row + col * ROWS) }
+        unsafe { self.0.get_unchecked(col).get_unchecked(row) }
     }
 
     /// Get a mutable reference to a cell without bounds checks.
@@ -343,58 +446,467 @@ where
     /// Caller must ensure that provided cell does not exceed
     /// the bounds of the matrix.
     pub unsafe fn get_unchecked_mut(&mut self, row: usize, col: usize) -> &mut T {
-        unsafe { self.0.get_unchecked_mut(row + col * ROWS) }
+        unsafe { self.0.get_unchecked_mut(col).get_unchecked_mut(row) }
     }
 
     /// Map all elements of this matrix to a new matrix.
-    pub fn map<Y, F>(self, map: F) -> Matrix<Y, ROWS, COLUMNS>
+    pub fn map<Y, F>(self, mut map: F) -> Matrix<Y, ROWS, COLUMNS>
     where
-        [Y; ROWS * COLUMNS]:,
         F: FnMut(T) -> Y,
     {
-        Matrix::new(self.0.map(map))
+        Matrix(self.0.map(|row| row.map(&mut map)))
     }
 
-    /// Get a matrix of references to elements of this matrix
-    pub const fn as_ref<'a>(&'a self) -> Matrix<&'a T, ROWS, COLUMNS>
+    /// Convert elements of this matrix to another type using `as`.
+    ///
+    /// See [Cast].
+    pub fn cast<U>(self) -> Matrix<U, ROWS, COLUMNS>
     where
-        [&'a T; ROWS * COLUMNS]:,
+        T: Cast<U>,
     {
-        unsafe {
-            let mut mat: [MaybeUninit<&'a T>; ROWS * COLUMNS] =
-                [const { MaybeUninit::uninit() }; ROWS * COLUMNS];
-            let mut i = 0usize;
-            while i < ROWS * COLUMNS {
-                mat[i].write(&self.0[i]);
-                i += 1;
+        self.map(T::cast)
+    }
+
+    /// Convert elements of this matrix to another type using [From].
+    pub fn convert<U>(self) -> Matrix<U, ROWS, COLUMNS>
+    where
+        T: Into<U>,
+    {
+        self.map(T::into)
+    }
+
+    /// Convert elements of this matrix to another type using [TryFrom].
+    ///
+    /// Returns the first error encountered.
+    pub fn try_convert<U>(self) -> Result<Matrix<U, ROWS, COLUMNS>, T::Error>
+    where
+        T: TryInto<U>,
+    {
+        Matrix::try_from_results(self.0.into_iter().flatten().map(T::try_into))
+    }
+
+    /// Create a new matrix from an iterator of exactly `ROWS * COLUMNS`
+    /// results, stopping at the first error.
+    fn try_from_results<E>(iter: impl Iterator<Item = Result<T, E>>) -> Result<Self, E> {
+        let mut new = Self::new_uninit();
+        let cells = new.0.as_flattened_mut();
+        for (i, x) in iter.enumerate() {
+            match x {
+                Ok(x) => {
+                    cells[i].write(x);
+                }
+                Err(e) => {
+                    for x in &mut cells[..i] {
+                        unsafe { x.assume_init_drop() };
+                    }
+                    return Err(e);
+                }
             }
-            Matrix::new(MaybeUninit::array_assume_init(mat))
         }
+        Ok(unsafe { new.assume_init() })
+    }
+
+    /// Map all elements of this matrix to a new matrix, also passing
+    /// the position of each element.
+    ///
+    /// `row` and `col` have the same meaning as in [get()](Self::get).
+    pub fn map_indexed<Y, F>(self, mut map: F) -> Matrix<Y, ROWS, COLUMNS>
+    where
+        F: FnMut(usize, usize, T) -> Y,
+    {
+        let mut col = 0usize;
+        Matrix(self.0.map(|cells| {
+            let mut row = 0usize;
+            let cells = cells.map(|x| {
+                let x = map(row, col, x);
+                row += 1;
+                x
+            });
+            col += 1;
+            cells
+        }))
+    }
+
+    /// Iterate over cells of this matrix along with their positions.
+    ///
+    /// Yields `(row, col, value)`, where `row` and `col` have the same
+    /// meaning as in [get()](Self::get). Cells are visited row by row.
+    pub fn iter_cells(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (usize, usize, &T)> + ExactSizeIterator + '_ {
+        self.0
+            .as_flattened()
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (i % ROWS, i / ROWS, x))
+    }
+
+    /// Get a matrix of references to elements of this matrix
+    pub const fn as_ref(&self) -> Matrix<&T, ROWS, COLUMNS> {
+        let mut mat = uninit_rows::<&T, ROWS, COLUMNS>();
+        let mut y = 0usize;
+        while y < COLUMNS {
+            let mut x = 0usize;
+            while x < ROWS {
+                mat[y][x].write(&self.0[y][x]);
+                x += 1;
+            }
+            y += 1;
+        }
+        unsafe { Matrix(mat).assume_init() }
     }
 
     /// Get a matrix of mutable references to elements of this matrix
-    pub const fn as_mut<'a>(&'a mut self) -> Matrix<&'a mut T, ROWS, COLUMNS>
+    pub const fn as_mut(&mut self) -> Matrix<&mut T, ROWS, COLUMNS> {
+        let mut mat = uninit_rows::<&mut T, ROWS, COLUMNS>();
+        let ptr = self.0.as_flattened_mut().as_mut_ptr();
+        let mut i = 0usize;
+        while i < ROWS * COLUMNS {
+            // Every index is visited exactly once, so references never alias.
+            mat[i / ROWS][i % ROWS].write(unsafe { &mut *ptr.add(i) });
+            i += 1;
+        }
+        unsafe { Matrix(mat).assume_init() }
+    }
+
+    /// Iterate over rows of this matrix.
+    pub fn rows(&self) -> core::slice::Iter<'_, [T; ROWS]> {
+        self.0.iter()
+    }
+
+    /// Iterate over mutable rows of this matrix.
+    pub fn rows_mut(&mut self) -> core::slice::IterMut<'_, [T; ROWS]> {
+        self.0.iter_mut()
+    }
+
+    /// Iterate over columns of this matrix.
+    ///
+    /// Since columns are not laid out continuously in memory, each
+    /// column is an array of references to its cells.
+    pub fn columns(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = [&T; COLUMNS]> + ExactSizeIterator + '_ {
+        (0..ROWS).map(|x| core::array::from_fn(|y| &self.0[y][x]))
+    }
+
+    /// Get a reference to a row.
+    pub fn row(&self, n: usize) -> Option<&[T; ROWS]> {
+        self.0.get(n)
+    }
+
+    /// Get a mutable reference to a row.
+    pub fn row_mut(&mut self, n: usize) -> Option<&mut [T; ROWS]> {
+        self.0.get_mut(n)
+    }
+
+    /// Get references to cells of a column.
+    pub fn col(&self, n: usize) -> Option<[&T; COLUMNS]> {
+        if n >= ROWS {
+            None
+        } else {
+            Some(core::array::from_fn(|y| &self.0[y][n]))
+        }
+    }
+
+    /// Swap two rows of this matrix.
+    ///
+    /// ## Panics
+    /// Panics if any of the rows is out of bounds.
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        self.0.swap(a, b);
+    }
+
+    /// Swap two columns of this matrix.
+    ///
+    /// ## Panics
+    /// Panics if any of the columns is out of bounds.
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        for row in &mut self.0 {
+            row.swap(a, b);
+        }
+    }
+
+    /// Multiply every cell of a row by `factor`.
+    ///
+    /// ## Panics
+    /// Panics if the row is out of bounds.
+    pub fn scale_row(&mut self, n: usize, factor: T)
     where
-        [&'a mut T; ROWS * COLUMNS]:,
+        T: MulAssign + Clone,
     {
-        unsafe {
-            let mut mat: [MaybeUninit<&'a mut T>; ROWS * COLUMNS] =
-                [const { MaybeUninit::uninit() }; ROWS * COLUMNS];
-            let mut i = 0usize;
-            while i < ROWS * COLUMNS {
-                mat[i].write((&mut self.0[i] as *mut T).as_mut().unwrap_unchecked());
-                i += 1;
+        for x in &mut self.0[n] {
+            *x *= factor.clone();
+        }
+    }
+
+    /// Add row `source` multiplied by `factor` to row `target`.
+    ///
+    /// ## Panics
+    /// Panics if any of the rows is out of bounds.
+    pub fn add_scaled_row(&mut self, target: usize, source: usize, factor: T)
+    where
+        T: Mul<Output = T> + AddAssign + Clone,
+    {
+        for x in 0..ROWS {
+            let add = self.0[source][x].clone() * factor.clone();
+            self.0[target][x] += add;
+        }
+    }
+
+    /// Get a view into a block of this matrix.
+    ///
+    /// `row` and `col` specify the offset of the block and have
+    /// the same meaning as in [get()](Self::get). Returns [None]
+    /// if the block does not fit into this matrix.
+    pub fn submatrix_ref<const SUB_ROWS: usize, const SUB_COLUMNS: usize>(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> Option<Matrix<&T, SUB_ROWS, SUB_COLUMNS>> {
+        if row.checked_add(SUB_ROWS).is_none_or(|x| x > ROWS)
+            || col.checked_add(SUB_COLUMNS).is_none_or(|x| x > COLUMNS)
+        {
+            return None;
+        }
+        Some(Matrix(core::array::from_fn(|y| {
+            core::array::from_fn(|x| &self.0[col + y][row + x])
+        })))
+    }
+
+    /// Get a mutable view into a block of this matrix.
+    ///
+    /// See [submatrix_ref()](Self::submatrix_ref).
+    pub fn submatrix_mut<const SUB_ROWS: usize, const SUB_COLUMNS: usize>(
+        &mut self,
+        row: usize,
+        col: usize,
+    ) -> Option<Matrix<&mut T, SUB_ROWS, SUB_COLUMNS>> {
+        if row.checked_add(SUB_ROWS).is_none_or(|x| x > ROWS)
+            || col.checked_add(SUB_COLUMNS).is_none_or(|x| x > COLUMNS)
+        {
+            return None;
+        }
+        let ptr = self.0.as_flattened_mut().as_mut_ptr();
+        // Every index is visited exactly once, so references never alias.
+        Some(Matrix(core::array::from_fn(|y| {
+            core::array::from_fn(|x| unsafe { &mut *ptr.add(row + x + (col + y) * ROWS) })
+        })))
+    }
+
+    /// Copy a block of this matrix into a new matrix.
+    ///
+    /// See [submatrix_ref()](Self::submatrix_ref).
+    pub fn submatrix<const SUB_ROWS: usize, const SUB_COLUMNS: usize>(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> Option<Matrix<T, SUB_ROWS, SUB_COLUMNS>>
+    where
+        T: Clone,
+    {
+        self.submatrix_ref(row, col).map(|x| x.cloned())
+    }
+
+    /// Place another matrix to the right of this matrix.
+    ///
+    /// Both matrices must have the same number of rows.
+    #[cfg(feature = "nightly")]
+    pub fn hstack<const OTHER: usize>(
+        self,
+        other: Matrix<T, OTHER, COLUMNS>,
+    ) -> Matrix<T, { ROWS + OTHER }, COLUMNS>
+    where
+        [(); ROWS + OTHER]:,
+    {
+        let mut new = Matrix::<T, { ROWS + OTHER }, COLUMNS>::new_uninit();
+        for (cells, (left, right)) in new.0.iter_mut().zip(self.0.into_iter().zip(other.0)) {
+            for (cell, x) in cells.iter_mut().zip(left.into_iter().chain(right)) {
+                cell.write(x);
             }
-            Matrix::new(MaybeUninit::array_assume_init(mat))
         }
+        unsafe { new.assume_init() }
+    }
+
+    /// Place another matrix below this matrix.
+    ///
+    /// Both matrices must have the same number of columns.
+    #[cfg(feature = "nightly")]
+    pub fn vstack<const OTHER: usize>(
+        self,
+        other: Matrix<T, ROWS, OTHER>,
+    ) -> Matrix<T, ROWS, { COLUMNS + OTHER }>
+    where
+        [(); COLUMNS + OTHER]:,
+    {
+        let mut new = Matrix::<T, ROWS, { COLUMNS + OTHER }>::new_uninit();
+        for (cells, row) in new.0.iter_mut().zip(self.0.into_iter().chain(other.0)) {
+            for (cell, x) in cells.iter_mut().zip(row) {
+                cell.write(x);
+            }
+        }
+        unsafe { new.assume_init() }
+    }
+
+    /// Calculate the Kronecker product of this and another matrix.
+    ///
+    /// Every cell of this matrix is replaced with the other matrix
+    /// multiplied by that cell.
+    #[cfg(feature = "nightly")]
+    pub fn kronecker<const OTHER_ROWS: usize, const OTHER_COLUMNS: usize>(
+        &self,
+        other: &Matrix<T, OTHER_ROWS, OTHER_COLUMNS>,
+    ) -> Matrix<T, { ROWS * OTHER_ROWS }, { COLUMNS * OTHER_COLUMNS }>
+    where
+        [(); ROWS * OTHER_ROWS]:,
+        [(); COLUMNS * OTHER_COLUMNS]:,
+        T: Mul<Output = T> + Clone,
+    {
+        Matrix(core::array::from_fn(|y| {
+            core::array::from_fn(|x| {
+                self.0[y / OTHER_COLUMNS][x / OTHER_ROWS].clone()
+                    * other.0[y % OTHER_COLUMNS][x % OTHER_ROWS].clone()
+            })
+        }))
+    }
+
+    /// Combine elements of this and another matrix of the same shape.
+    pub fn zip_map<Y, Z, F>(
+        self,
+        other: Matrix<Y, ROWS, COLUMNS>,
+        mut map: F,
+    ) -> Matrix<Z, ROWS, COLUMNS>
+    where
+        F: FnMut(T, Y) -> Z,
+    {
+        let mut new = Matrix::<Z, ROWS, COLUMNS>::new_uninit();
+        let cells = new.0.as_flattened_mut();
+        for (cell, (a, b)) in cells.iter_mut().zip(
+            self.0
+                .into_iter()
+                .flatten()
+                .zip(other.0.into_iter().flatten()),
+        ) {
+            cell.write(map(a, b));
+        }
+        unsafe { new.assume_init() }
+    }
+
+    /// Multiply elements of this matrix by elements of another matrix.
+    ///
+    /// Also known as Hadamard product. For the matrix product, use
+    /// `*` operator.
+    pub fn component_mul<Y, Z>(self, other: Matrix<Y, ROWS, COLUMNS>) -> Matrix<Z, ROWS, COLUMNS>
+    where
+        T: Mul<Y, Output = Z>,
+    {
+        self.zip_map(other, T::mul)
+    }
+
+    /// Divide elements of this matrix by elements of another matrix.
+    pub fn component_div<Y, Z>(self, other: Matrix<Y, ROWS, COLUMNS>) -> Matrix<Z, ROWS, COLUMNS>
+    where
+        T: Div<Y, Output = Z>,
+    {
+        self.zip_map(other, T::div)
+    }
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<T, ROWS, COLUMNS>
+where
+    T: Float,
+{
+    /// Calculate the Frobenius norm of this matrix.
+    ///
+    /// Square root of the sum of squares of all cells.
+    pub fn frobenius_norm(&self) -> T {
+        let mut sum = T::ZERO;
+        for x in self.0.as_flattened() {
+            sum += *x * *x;
+        }
+        sum.sqrt()
+    }
+
+    /// Calculate the 1-norm of this matrix.
+    ///
+    /// Largest sum of absolute values of cells in a column.
+    pub fn norm_l1(&self) -> T {
+        let mut norm = T::ZERO;
+        for col in self.columns() {
+            let mut sum = T::ZERO;
+            for x in col {
+                sum += x.abs();
+            }
+            if sum > norm {
+                norm = sum;
+            }
+        }
+        norm
+    }
+
+    /// Calculate the infinity norm of this matrix.
+    ///
+    /// Largest sum of absolute values of cells in a row.
+    pub fn norm_inf(&self) -> T {
+        let mut norm = T::ZERO;
+        for row in self.rows() {
+            let mut sum = T::ZERO;
+            for x in row {
+                sum += x.abs();
+            }
+            if sum > norm {
+                norm = sum;
+            }
+        }
+        norm
+    }
+}
+impl<T, const SIZE: usize> Matrix<T, 1, SIZE> {
+    /// Calculate the outer product of this and another column vector.
+    ///
+    /// Same as multiplying this vector by the transposed other vector.
+    pub fn outer<const OTHER: usize>(&self, other: &Matrix<T, 1, OTHER>) -> Matrix<T, OTHER, SIZE>
+    where
+        T: Mul<Output = T> + Clone,
+    {
+        Matrix(core::array::from_fn(|y| {
+            core::array::from_fn(|x| self.0[y][0].clone() * other.0[x][0].clone())
+        }))
     }
 }
 impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<T, ROWS, COLUMNS>
 where
-    [T; ROWS * COLUMNS]:,
     T: Num,
 {
-    const ZERO: Self = Self::new([T::ZERO; ROWS * COLUMNS]);
+    const ZERO_ROW: [T; ROWS] = [T::ZERO; ROWS];
+    const ZERO: Self = Self([Self::ZERO_ROW; COLUMNS]);
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> Display for Matrix<T, ROWS, COLUMNS>
+where
+    T: Display,
+{
+    /// Print this matrix as aligned rows.
+    ///
+    /// Precision and width provided to the formatter are applied
+    /// to each cell.
+    ///
+    /// ```text
+    /// [ 1.00, -2.00]
+    /// [10.00,  4.50]
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_cells(self.0.as_flattened(), &mut [0; ROWS], f)
+    }
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> FromIterator<T> for Matrix<T, ROWS, COLUMNS> {
+    /// Collect an iterator into a matrix.
+    ///
+    /// ## Panics
+    /// Panics if iterator does not yield exactly `ROWS * COLUMNS` elements.
+    /// See [Matrix::try_from_iter] for a non-panicking version.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::try_from_iter(iter).unwrap_or_else(|_| {
+            panic!("Collecting an iterator into a matrix ({ROWS}x{COLUMNS}) of a wrong length")
+        })
+    }
 }
 // impl<T, I, const ROWS: usize, const COLUMNS: usize> From<[I; ROWS * COLUMNS]>
 //     for Matrix<T, ROWS, COLUMNS>
@@ -406,32 +918,69 @@ where
 //         Self(value.map(|x| x.into()))
 //     }
 // }
-impl<T, I, const ROWS: usize, const COLUMNS: usize> TryFrom<[I; ROWS * COLUMNS]>
+impl<T, I, const ROWS: usize, const COLUMNS: usize, const LEN: usize> TryFrom<[I; LEN]>
     for Matrix<T, ROWS, COLUMNS>
 where
-    [I; ROWS * COLUMNS]:,
     I: TryInto<T>,
 {
     type Error = I::Error;
 
-    fn try_from(value: [I; ROWS * COLUMNS]) -> Result<Self, Self::Error> {
-        Ok(Self(value.try_map(|x| x.try_into())?))
+    /// Convert a flattened array of rows into a matrix.
+    ///
+    /// Fails to compile if the array does not have exactly
+    /// `ROWS * COLUMNS` elements.
+    fn try_from(value: [I; LEN]) -> Result<Self, Self::Error> {
+        let () = Len::<LEN, ROWS, COLUMNS>::CHECK;
+        Self::try_from_results(value.into_iter().map(I::try_into))
+    }
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> TryFrom<&[T]> for Matrix<T, ROWS, COLUMNS>
+where
+    T: Clone,
+{
+    type Error = LengthError;
+
+    /// Copy a flattened slice of rows into a matrix.
+    ///
+    /// Returns [LengthError] if the slice does not have exactly
+    /// `ROWS * COLUMNS` elements.
+    fn try_from(value: &[T]) -> Result<Self, Self::Error> {
+        if value.len() != ROWS * COLUMNS {
+            return Err(LengthError);
+        }
+        Self::try_from_iter(value.iter().cloned())
+    }
+}
+#[cfg(feature = "alloc")]
+impl<T, const ROWS: usize, const COLUMNS: usize> TryFrom<Vec<T>> for Matrix<T, ROWS, COLUMNS> {
+    type Error = LengthError;
+
+    /// Move a flattened [Vec] of rows into a matrix.
+    ///
+    /// Returns [LengthError] if the vector does not have exactly
+    /// `ROWS * COLUMNS` elements.
+    fn try_from(value: Vec<T>) -> Result<Self, Self::Error> {
+        if value.len() != ROWS * COLUMNS {
+            return Err(LengthError);
+        }
+        Self::try_from_iter(value)
     }
 }
 impl<A, B, const ROWS: usize, const COLUMNS: usize> PartialEq<Matrix<B, ROWS, COLUMNS>>
     for Matrix<A, ROWS, COLUMNS>
 where
-    [A; ROWS * COLUMNS]:,
-    [B; ROWS * COLUMNS]:,
     A: PartialEq<B>,
 {
     fn eq(&self, other: &Matrix<B, ROWS, COLUMNS>) -> bool {
-        self.0.iter().zip(other.0.iter()).all(|(a, b)| a == b)
+        self.0
+            .as_flattened()
+            .iter()
+            .zip(other.0.as_flattened())
+            .all(|(a, b)| a == b)
     }
 }
 impl<T, R, const ROWS: usize, const COLUMNS: usize> Mul<T> for Matrix<T, ROWS, COLUMNS>
 where
-    [T; ROWS * COLUMNS]:,
     T: Mul<Output = R> + Clone,
 {
     type Output = Matrix<R, ROWS, COLUMNS>;
@@ -443,49 +992,33 @@ where
 impl<T, const SIDE: usize, const EXTRA1: usize, const EXTRA2: usize> Mul<Matrix<T, EXTRA2, SIDE>>
     for Matrix<T, SIDE, EXTRA1>
 where
-    [T; SIDE * EXTRA1]:,
-    [T; EXTRA2 * SIDE]:,
-    [T; EXTRA2 * EXTRA1]:,
-    T: Mul<Output = T> + Add<Output = T> + Clone,
+    T: Num,
 {
     type Output = Matrix<T, EXTRA2, EXTRA1>;
 
+    /// Multiply matrices.
+    ///
+    /// This impl is generic over all element types, so it can't use
+    /// SIMD. Use [Matrix::mul_mat4] and [Matrix::mul_vec4] to multiply
+    /// [Mat4f] matrices.
     fn mul(self, rhs: Matrix<T, EXTRA2, SIDE>) -> Self::Output {
         unsafe {
             let mut new = Self::Output::new_uninit();
             for x in 0..EXTRA2 {
                 for y in 0..EXTRA1 {
-                    let mut sum: Option<T> = None;
+                    let mut sum = T::ZERO;
                     for i in 0..SIDE {
-                        match &mut sum {
-                            Some(sum) => {
-                                #[allow(clippy::uninit_assumed_init)]
-                                let mut t = MaybeUninit::uninit().assume_init();
-                                std::mem::swap(sum, &mut t);
-                                t = self.get(i, y).unwrap().clone()
-                                    * rhs.get(x, i).unwrap().clone()
-                                    + t;
-                                std::mem::swap(sum, &mut t);
-                                std::mem::forget(t);
-                            }
-                            None => {
-                                sum = Some(
-                                    T::clone(self.get(i, y).unwrap())
-                                        * T::clone(rhs.get(x, i).unwrap()),
-                                )
-                            }
-                        }
+                        sum += self.get_unchecked(i, y).clone() * rhs.get_unchecked(x, i).clone();
                     }
-                    new.get_unchecked_mut(x, y).write(sum.unwrap_unchecked());
+                    new.get_unchecked_mut(x, y).write(sum);
                 }
             }
             new.assume_init()
         }
     }
 }
-impl<T, const ROWS: usize, const COLUMNS: usize> Index<(usize, usize)> for Matrix<T, ROWS, COLUMNS>
-where
-    [T; ROWS * COLUMNS]:,
+impl<T, const ROWS: usize, const COLUMNS: usize> Index<(usize, usize)>
+    for Matrix<T, ROWS, COLUMNS>
 {
     type Output = T;
 
@@ -500,8 +1033,6 @@ where
 }
 impl<T, const ROWS: usize, const COLUMNS: usize> IndexMut<(usize, usize)>
     for Matrix<T, ROWS, COLUMNS>
-where
-    [T; ROWS * COLUMNS]:,
 {
     fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
         self.get_mut(index.0, index.1).unwrap_or_else(|| {
@@ -515,16 +1046,257 @@ where
 impl<T, const ROWS: usize, const COLUMNS: usize> Add<Matrix<T, ROWS, COLUMNS>>
     for Matrix<T, ROWS, COLUMNS>
 where
-    [T; ROWS * COLUMNS]:,
     T: Num,
 {
     type Output = Matrix<T, ROWS, COLUMNS>;
 
     fn add(mut self, rhs: Matrix<T, ROWS, COLUMNS>) -> Self::Output {
-        self.0.iter_mut().zip(rhs.0).for_each(|(x, v)| *x += v);
+        self.0
+            .as_flattened_mut()
+            .iter_mut()
+            .zip(rhs.0.into_iter().flatten())
+            .for_each(|(x, v)| *x += v);
         self
     }
 }
+impl<T, const ROWS: usize, const COLUMNS: usize> AddAssign<Matrix<T, ROWS, COLUMNS>>
+    for Matrix<T, ROWS, COLUMNS>
+where
+    T: AddAssign,
+{
+    fn add_assign(&mut self, rhs: Matrix<T, ROWS, COLUMNS>) {
+        self.0
+            .as_flattened_mut()
+            .iter_mut()
+            .zip(rhs.0.into_iter().flatten())
+            .for_each(|(x, v)| *x += v);
+    }
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> SubAssign<Matrix<T, ROWS, COLUMNS>>
+    for Matrix<T, ROWS, COLUMNS>
+where
+    T: SubAssign,
+{
+    fn sub_assign(&mut self, rhs: Matrix<T, ROWS, COLUMNS>) {
+        self.0
+            .as_flattened_mut()
+            .iter_mut()
+            .zip(rhs.0.into_iter().flatten())
+            .for_each(|(x, v)| *x -= v);
+    }
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> MulAssign<T> for Matrix<T, ROWS, COLUMNS>
+where
+    T: MulAssign + Clone,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        for x in self.0.as_flattened_mut() {
+            *x *= rhs.clone();
+        }
+    }
+}
+impl<T, const SIZE: usize, const COLUMNS: usize> MulAssign<Matrix<T, SIZE, SIZE>>
+    for Matrix<T, SIZE, COLUMNS>
+where
+    T: Mul<Output = T> + Add<Output = T> + Clone,
+{
+    /// Multiply this matrix by a square matrix in place.
+    ///
+    /// Same as `self = self * rhs`, but reuses storage of this matrix.
+    fn mul_assign(&mut self, rhs: Matrix<T, SIZE, SIZE>) {
+        for row in &mut self.0 {
+            let new: [T; SIZE] = core::array::from_fn(|x| {
+                let mut sum: Option<T> = None;
+                for (i, cell) in row.iter().enumerate() {
+                    let product = cell.clone() * rhs.0[i][x].clone();
+                    sum = Some(match sum.take() {
+                        Some(sum) => product + sum,
+                        None => product,
+                    });
+                }
+                sum.expect("Multiplying by an empty matrix")
+            });
+            *row = new;
+        }
+    }
+}
+
+macro_rules! impl_transform_f {
+    ($($ty:ty)*) => {$(
+        impl Matrix<$ty, 3, 3> {
+            /// 2D translation matrix.
+            ///
+            /// Moves a point `(x, y, 1)` by the provided offsets.
+            pub const fn translation(x: $ty, y: $ty) -> Self {
+                mat! {
+                    1.0, 0.0, x,
+                    0.0, 1.0, y,
+                    0.0, 0.0, 1.0,
+                }
+            }
+
+            /// 2D scale matrix.
+            pub const fn scale(x: $ty, y: $ty) -> Self {
+                mat! {
+                    x,   0.0, 0.0,
+                    0.0, y,   0.0,
+                    0.0, 0.0, 1.0,
+                }
+            }
+
+            /// 2D rotation matrix.
+            ///
+            /// Rotates counter-clockwise by `angle` radians.
+            #[cfg(feature = "std")]
+            pub fn rotation(angle: $ty) -> Self {
+                let (s, c) = angle.sin_cos();
+                mat! {
+                    c,   -s,  0.0,
+                    s,   c,   0.0,
+                    0.0, 0.0, 1.0,
+                }
+            }
+        }
+        impl Matrix<$ty, 4, 4> {
+            /// 3D translation matrix.
+            ///
+            /// Moves a point `(x, y, z, 1)` by the provided offsets.
+            pub const fn translation(x: $ty, y: $ty, z: $ty) -> Self {
+                mat! {
+                    1.0, 0.0, 0.0, x,
+                    0.0, 1.0, 0.0, y,
+                    0.0, 0.0, 1.0, z,
+                    0.0, 0.0, 0.0, 1.0,
+                }
+            }
+
+            /// 3D scale matrix.
+            pub const fn scale(x: $ty, y: $ty, z: $ty) -> Self {
+                mat! {
+                    x,   0.0, 0.0, 0.0,
+                    0.0, y,   0.0, 0.0,
+                    0.0, 0.0, z,   0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                }
+            }
+
+            /// 3D rotation matrix around the X axis.
+            ///
+            /// Rotates counter-clockwise by `angle` radians when
+            /// looking from positive X towards the origin.
+            #[cfg(feature = "std")]
+            pub fn rotation_x(angle: $ty) -> Self {
+                let (s, c) = angle.sin_cos();
+                mat! {
+                    1.0, 0.0, 0.0, 0.0,
+                    0.0, c,   -s,  0.0,
+                    0.0, s,   c,   0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                }
+            }
+
+            /// 3D rotation matrix around the Y axis.
+            ///
+            /// Rotates counter-clockwise by `angle` radians when
+            /// looking from positive Y towards the origin.
+            #[cfg(feature = "std")]
+            pub fn rotation_y(angle: $ty) -> Self {
+                let (s, c) = angle.sin_cos();
+                mat! {
+                    c,   0.0, s,   0.0,
+                    0.0, 1.0, 0.0, 0.0,
+                    -s,  0.0, c,   0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                }
+            }
+
+            /// 3D rotation matrix around the Z axis.
+            ///
+            /// Rotates counter-clockwise by `angle` radians when
+            /// looking from positive Z towards the origin.
+            #[cfg(feature = "std")]
+            pub fn rotation_z(angle: $ty) -> Self {
+                let (s, c) = angle.sin_cos();
+                mat! {
+                    c,   -s,  0.0, 0.0,
+                    s,   c,   0.0, 0.0,
+                    0.0, 0.0, 1.0, 0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                }
+            }
+        }
+    )*};
+}
+impl_transform_f! {
+    f32 f64
+}
+
+impl Matrix<f32, 4, 4> {
+    /// Multiply this matrix by another 4x4 matrix.
+    ///
+    /// Produces the same result as `self * rhs` up to rounding errors,
+    /// but uses SIMD instructions where available. `*` never does this
+    /// by itself, so call this method directly in hot code.
+    pub fn mul_mat4(&self, rhs: &Self) -> Self {
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            use core::arch::x86_64::{
+                _mm_add_ps, _mm_loadu_ps, _mm_mul_ps, _mm_set1_ps, _mm_storeu_ps,
+            };
+
+            let b = [
+                _mm_loadu_ps(rhs.0[0].as_ptr()),
+                _mm_loadu_ps(rhs.0[1].as_ptr()),
+                _mm_loadu_ps(rhs.0[2].as_ptr()),
+                _mm_loadu_ps(rhs.0[3].as_ptr()),
+            ];
+            let mut new = [0.0f32; 16];
+            for i in 0..4 {
+                let a = &self.0[i];
+                let row = _mm_add_ps(
+                    _mm_add_ps(
+                        _mm_mul_ps(_mm_set1_ps(a[0]), b[0]),
+                        _mm_mul_ps(_mm_set1_ps(a[1]), b[1]),
+                    ),
+                    _mm_add_ps(
+                        _mm_mul_ps(_mm_set1_ps(a[2]), b[2]),
+                        _mm_mul_ps(_mm_set1_ps(a[3]), b[3]),
+                    ),
+                );
+                _mm_storeu_ps(new.as_mut_ptr().add(i * 4), row);
+            }
+            Self::new(new)
+        }
+
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            let a = self.0.as_flattened();
+            let b = rhs.0.as_flattened();
+            let mut new = [0.0f32; 16];
+            for i in 0..4 {
+                for j in 0..4 {
+                    new[j + i * 4] = a[i * 4] * b[j]
+                        + a[1 + i * 4] * b[j + 4]
+                        + a[2 + i * 4] * b[j + 8]
+                        + a[3 + i * 4] * b[j + 12];
+                }
+            }
+            Self::new(new)
+        }
+    }
+
+    /// Multiply this matrix by a column vector.
+    ///
+    /// Produces the same result as `self * rhs` up to rounding errors,
+    /// but without the overhead of generic matrix multiplication.
+    pub fn mul_vec4(&self, rhs: &Matrix<f32, 1, 4>) -> Matrix<f32, 1, 4> {
+        let a = &self.0;
+        let v = rhs.0.as_flattened();
+        Matrix(core::array::from_fn(|i| {
+            [a[i][0] * v[0] + a[i][1] * v[1] + a[i][2] * v[2] + a[i][3] * v[3]]
+        }))
+    }
+}
 
 pub type Mat4f = Matrix<f32, 4, 4>;
 /// Identity matrix for f32 4x4 matrix.
@@ -535,9 +1307,9 @@ pub const MAT3F_IDENTITY: Mat3f = Mat3f::IDENTITY;
 
 // https://www.mathsisfun.com/algebra/matrix-multiplying.html
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use super::{Mat3f, Matrix};
+    use super::{LengthError, Mat3f, Mat4f, Matrix};
 
     #[test]
     fn get_test() {
@@ -576,6 +1348,11 @@ mod tests {
         };
 
         assert_eq!(matrix * Mat3f::IDENTITY, matrix);
+        assert_eq!(
+            Matrix::<i32, 5, 5>::IDENTITY.diagonal_ref(),
+            [&1, &1, &1, &1, &1]
+        );
+        assert_eq!(Matrix::<u8, 5, 5>::identity().trace(), 5);
     }
 
     #[test]
@@ -597,6 +1374,10 @@ mod tests {
                 139.0, 154.0
             }
         );
+        assert_eq!(
+            Matrix::<f32, 0, 2>::ZERO * Matrix::<f32, 3, 0>::ZERO,
+            Matrix::<f32, 3, 2>::ZERO
+        );
     }
 
     #[test]
@@ -618,4 +1399,323 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn transform_test() {
+        let point: Matrix<f32, 1, 4> = mat![1.0, 0.0, 0.0, 1.0];
+        let moved = Mat4f::translation(0.0, 0.0, 2.0)
+            * (Mat4f::rotation_z(std::f32::consts::FRAC_PI_2)
+                * (Mat4f::scale(2.0, 1.0, 1.0) * point));
+
+        assert_eq!(moved.map(|x| x.round()), mat![0.0, 2.0, 2.0, 1.0]);
+
+        let point: Matrix<f32, 1, 3> = mat![1.0, 1.0, 1.0];
+        assert_eq!(Mat3f::translation(-1.0, 2.0) * point, mat![0.0, 3.0, 1.0]);
+    }
+
+    #[test]
+    fn rows_columns_test() {
+        let matrix: Matrix<i32, 3, 2> = mat! {
+            1, 2, 3,
+            4, 5, 6,
+        };
+
+        assert_eq!(matrix.rows().collect::<Vec<_>>(), [&[1, 2, 3], &[4, 5, 6]]);
+        assert_eq!(
+            matrix.columns().collect::<Vec<_>>(),
+            [[&1, &4], [&2, &5], [&3, &6]]
+        );
+        assert_eq!(matrix.row(1), Some(&[4, 5, 6]));
+        assert_eq!(matrix.row(2), None);
+        assert_eq!(matrix.col(2), Some([&3, &6]));
+        assert_eq!(matrix.col(3), None);
+    }
+
+    #[test]
+    fn submatrix_test() {
+        let mut matrix = Mat4f::translation(1.0, 2.0, 3.0) * Mat4f::scale(2.0, 2.0, 2.0);
+
+        assert_eq!(
+            matrix.submatrix::<3, 3>(0, 0),
+            Some(mat! { <f32, 3, 3>
+                2.0, 0.0, 0.0,
+                0.0, 2.0, 0.0,
+                0.0, 0.0, 2.0,
+            })
+        );
+        assert_eq!(
+            matrix.submatrix_ref::<1, 3>(3, 0).map(|x| x.copied()),
+            Some(mat![1.0, 2.0, 3.0])
+        );
+        assert!(matrix.submatrix::<2, 2>(3, 0).is_none());
+        assert!(matrix.submatrix::<2, 2>(usize::MAX, 0).is_none());
+        assert!(matrix.submatrix::<2, 2>(0, usize::MAX).is_none());
+        assert!(matrix.submatrix_mut::<2, 2>(usize::MAX, 0).is_none());
+        assert!(matrix.submatrix_mut::<2, 2>(0, usize::MAX).is_none());
+
+        matrix
+            .submatrix_mut::<1, 3>(3, 0)
+            .unwrap()
+            .map(|x| *x = 0.0);
+        assert_eq!(
+            matrix.submatrix::<1, 4>(3, 0),
+            Some(mat![0.0, 0.0, 0.0, 1.0])
+        );
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn stack_test() {
+        let matrix: Matrix<i32, 2, 2> = mat! {
+            1, 2,
+            3, 4,
+        };
+        let column: Matrix<i32, 1, 2> = mat![5, 6];
+
+        assert_eq!(
+            matrix.hstack(column),
+            mat! { <i32, 3, 2>
+                1, 2, 5,
+                3, 4, 6,
+            }
+        );
+        assert_eq!(
+            matrix.vstack(mat! { <i32, 2, 1> 7, 8 }),
+            mat! { <i32, 2, 3>
+                1, 2,
+                3, 4,
+                7, 8,
+            }
+        );
+    }
+
+    #[test]
+    fn diagonal_test() {
+        let mut matrix: Matrix<i32, 3, 3> = mat! {
+            1, 2, 3,
+            4, 5, 6,
+            7, 8, 9,
+        };
+
+        assert_eq!(matrix.trace(), 15);
+        assert_eq!(matrix.diagonal(), [1, 5, 9]);
+
+        matrix.set_diagonal([0, 0, 0]);
+        assert_eq!(matrix.trace(), 0);
+        assert_eq!(matrix.row(1), Some(&[4, 0, 6]));
+
+        assert_eq!(
+            Matrix::<i32, 3, 3>::identity(),
+            Matrix::<i32, 3, 3>::IDENTITY
+        );
+    }
+
+    #[test]
+    fn component_test() {
+        let first: Matrix<i32, 2, 2> = mat! {
+            1, 2,
+            3, 4,
+        };
+        let second: Matrix<i32, 2, 2> = mat! {
+            5, 6,
+            7, 8,
+        };
+
+        assert_eq!(
+            first.component_mul(second),
+            mat! {
+                5,  12,
+                21, 32,
+            }
+        );
+        assert_eq!(second.component_div(first), mat![5, 3, 2, 2]);
+        assert_eq!(first.zip_map(second, |a, b| a.max(b - 5)), mat![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn from_iter_test() {
+        let matrix: Matrix<i32, 2, 2> = (1..=4).collect();
+        assert_eq!(matrix, mat![1, 2, 3, 4]);
+
+        assert!(Matrix::<i32, 2, 2>::try_from_iter(1..=3).is_err());
+        assert!(Matrix::<i32, 2, 2>::try_from_iter(1..=5).is_err());
+        assert!(Matrix::<String, 1, 2>::try_from_iter(["a".to_string()]).is_err());
+        assert_eq!(
+            Matrix::<i32, 2, 2>::try_from_iter([4, 3, 2, 1]),
+            Ok(mat![4, 3, 2, 1])
+        );
+    }
+
+    #[test]
+    fn try_from_test() {
+        let data = [1, 2, 3, 4, 5, 6];
+
+        assert_eq!(
+            Matrix::<i32, 3, 2>::try_from(&data[..]),
+            Ok(mat![1, 2, 3, 4, 5, 6])
+        );
+        assert_eq!(Matrix::<i32, 2, 2>::try_from(&data[..]), Err(LengthError));
+        assert_eq!(
+            Matrix::<String, 1, 2>::try_from(vec!["a".to_string(), "b".to_string()]),
+            Ok(mat!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(
+            Matrix::<i32, 2, 2>::try_from(data.to_vec()),
+            Err(LengthError)
+        );
+    }
+
+    #[test]
+    fn indexed_test() {
+        let matrix = Matrix::<i32, 3, 2>::new([0; 6]).map_indexed(|x, y, _| (x * 10 + y) as i32);
+
+        assert_eq!(matrix, mat![0, 10, 20, 1, 11, 21]);
+        assert!(matrix.iter_cells().all(|(x, y, v)| matrix[(x, y)] == *v));
+        assert_eq!(matrix.iter_cells().len(), 6);
+        assert_eq!(matrix.iter_cells().next_back(), Some((2, 1, &21)));
+    }
+
+    #[test]
+    fn assign_test() {
+        let mut matrix: Matrix<i32, 2, 2> = mat! {
+            1, 2,
+            3, 4,
+        };
+
+        matrix += mat![1, 1, 1, 1];
+        assert_eq!(matrix, mat![2, 3, 4, 5]);
+        matrix -= mat![2, 2, 2, 2];
+        assert_eq!(matrix, mat![0, 1, 2, 3]);
+        matrix *= 2;
+        assert_eq!(matrix, mat![0, 2, 4, 6]);
+
+        let rotation = Mat3f::rotation(1.0);
+        let mut transform = Mat3f::translation(1.0, 2.0);
+        transform *= rotation;
+        assert_eq!(transform, Mat3f::translation(1.0, 2.0) * rotation);
+
+        let mut wide: Matrix<i32, 2, 3> = mat! {
+            1, 2,
+            3, 4,
+            5, 6,
+        };
+        wide *= mat! { <i32, 2, 2> 0, 1, 1, 0 };
+        assert_eq!(wide, mat![2, 1, 4, 3, 6, 5]);
+    }
+
+    #[test]
+    fn cast_test() {
+        let matrix: Matrix<u8, 2, 2> = mat![1, 2, 3, 255];
+
+        assert_eq!(matrix.cast::<f32>(), mat![1.0, 2.0, 3.0, 255.0]);
+        assert_eq!(matrix.cast::<i8>(), mat![1, 2, 3, -1]);
+        assert_eq!(mat! { <f32, 1, 2> -1.5, 300.0 }.cast::<u8>(), mat![0, 255]);
+        assert_eq!(matrix.convert::<u32>(), mat![1, 2, 3, 255]);
+        assert_eq!(matrix.try_convert::<i8>().ok(), None);
+        assert_eq!(
+            mat! { <i32, 1, 2> 1, 2 }.try_convert::<u8>(),
+            Ok(mat![1, 2])
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let matrix: Matrix<f32, 2, 2> = mat! {
+            1.0,  -2.0,
+            10.0, 4.5,
+        };
+
+        assert_eq!(format!("{matrix:.2}"), "[ 1.00, -2.00]\n[10.00,  4.50]");
+        assert_eq!(format!("{matrix}"), "[ 1,  -2]\n[10, 4.5]");
+        assert_eq!(format!("{:3}", mat! { <i32, 1, 2> 1, 2 }), "[  1]\n[  2]");
+    }
+
+    #[test]
+    fn elementary_test() {
+        let mut matrix: Matrix<i32, 3, 2> = mat! {
+            1, 2, 3,
+            4, 5, 6,
+        };
+
+        matrix.swap_rows(0, 1);
+        assert_eq!(matrix, mat![4, 5, 6, 1, 2, 3]);
+        matrix.swap_cols(0, 2);
+        assert_eq!(matrix, mat![6, 5, 4, 3, 2, 1]);
+        matrix.scale_row(1, 2);
+        assert_eq!(matrix, mat![6, 5, 4, 6, 4, 2]);
+        matrix.add_scaled_row(0, 1, -1);
+        assert_eq!(matrix, mat![0, 1, 2, 6, 4, 2]);
+    }
+
+    #[test]
+    fn norm_test() {
+        let matrix: Matrix<f32, 2, 2> = mat! {
+            1.0, -2.0,
+            -3.0, 4.0,
+        };
+
+        assert_eq!(matrix.frobenius_norm(), 30.0f32.sqrt());
+        assert_eq!(matrix.norm_l1(), 6.0);
+        assert_eq!(matrix.norm_inf(), 7.0);
+    }
+
+    #[test]
+    fn outer_test() {
+        let first: Matrix<i32, 1, 3> = mat![1, 2, 3];
+        let second: Matrix<i32, 1, 2> = mat![4, 5];
+
+        assert_eq!(
+            first.outer(&second),
+            mat! { <i32, 2, 3>
+                4,  5,
+                8,  10,
+                12, 15,
+            }
+        );
+        assert_eq!(first.outer(&second), first * second.transposed().copied());
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn kronecker_test() {
+        let first: Matrix<i32, 2, 2> = mat! {
+            1, 2,
+            3, 4,
+        };
+        let second: Matrix<i32, 3, 1> = mat![0, 1, -1];
+
+        assert_eq!(
+            first.kronecker(&second),
+            mat! { <i32, 6, 2>
+                0, 1, -1, 0, 2, -2,
+                0, 3, -3, 0, 4, -4,
+            }
+        );
+    }
+
+    #[test]
+    fn mat4_fast_test() {
+        let first: Mat4f = (0..16).map(|x| x as f32 * 0.5 - 3.0).collect();
+        let second: Mat4f = (0..16).map(|x| (x * 7 % 11) as f32).collect();
+        let vector: Matrix<f32, 1, 4> = mat![1.0, -2.0, 0.5, 4.0];
+
+        for (a, b) in first
+            .mul_mat4(&second)
+            .0
+            .as_flattened()
+            .iter()
+            .zip((first * second).0.as_flattened())
+        {
+            assert!((a - b).abs() <= b.abs() * 1e-6);
+        }
+        for (a, b) in first
+            .mul_vec4(&vector)
+            .0
+            .as_flattened()
+            .iter()
+            .zip((first * vector).0.as_flattened())
+        {
+            assert!((a - b).abs() <= b.abs() * 1e-6);
+        }
+    }
 }
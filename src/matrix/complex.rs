@@ -0,0 +1,260 @@
+//! Complex numbers.
+
+use core::{
+    fmt::Display,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+use super::Num;
+
+/// A complex number.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Complex<T> {
+    /// Real part.
+    pub re: T,
+    /// Imaginary part.
+    pub im: T,
+}
+impl<T> Complex<T> {
+    /// Create a new complex number.
+    pub const fn new(re: T, im: T) -> Self {
+        Self { re, im }
+    }
+}
+impl<T> Complex<T>
+where
+    T: Num,
+{
+    /// Imaginary unit.
+    pub const I: Self = Self::new(T::ZERO, T::ONE);
+
+    /// Get the complex conjugate of this number.
+    pub fn conj(self) -> Self {
+        Self::new(self.re, T::ZERO - self.im)
+    }
+
+    /// Get the squared absolute value of this number.
+    pub fn norm_sqr(&self) -> T {
+        self.re.clone() * self.re.clone() + self.im.clone() * self.im.clone()
+    }
+}
+
+macro_rules! impl_complex_f {
+    ($($ty:ty)*) => {$(
+        #[cfg(feature = "std")]
+        impl Complex<$ty> {
+            /// Create a complex number from its absolute value and argument.
+            pub fn from_polar(abs: $ty, arg: $ty) -> Self {
+                let (s, c) = arg.sin_cos();
+                Self::new(abs * c, abs * s)
+            }
+
+            /// Get the absolute value of this number.
+            pub fn abs(self) -> $ty {
+                self.re.hypot(self.im)
+            }
+
+            /// Get the argument of this number.
+            pub fn arg(self) -> $ty {
+                self.im.atan2(self.re)
+            }
+
+            /// Raise `e` to the power of this number.
+            pub fn exp(self) -> Self {
+                Self::from_polar(self.re.exp(), self.im)
+            }
+        }
+    )*};
+}
+impl_complex_f! {
+    f32 f64
+}
+
+impl<T> Num for Complex<T>
+where
+    T: Num,
+{
+    const ZERO: Self = Self::new(T::ZERO, T::ZERO);
+    const ONE: Self = Self::new(T::ONE, T::ZERO);
+}
+impl<T> Display for Complex<T>
+where
+    T: Num + Display + PartialOrd,
+{
+    /// Print this number as `re+imi`.
+    ///
+    /// Formatting options are applied to both parts.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.re, f)?;
+        if self.im < T::ZERO {
+            f.write_str("-")?;
+            Display::fmt(&(T::ZERO - self.im.clone()), f)?;
+        } else {
+            f.write_str("+")?;
+            Display::fmt(&self.im, f)?;
+        }
+        f.write_str("i")
+    }
+}
+impl<T> Add for Complex<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+impl<T> Sub for Complex<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+impl<T> Mul for Complex<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.re.clone() * rhs.re.clone() - self.im.clone() * rhs.im.clone(),
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+impl<T> Div for Complex<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let norm = rhs.norm_sqr();
+        Self::new(
+            (self.re.clone() * rhs.re.clone() + self.im.clone() * rhs.im.clone()) / norm.clone(),
+            (self.im * rhs.re - self.re * rhs.im) / norm,
+        )
+    }
+}
+impl<T> Rem for Complex<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    /// Calculate the remainder of division.
+    ///
+    /// Parts of the quotient are truncated towards zero, so for integer
+    /// parts this is the remainder of Gaussian integer division.
+    fn rem(self, rhs: Self) -> Self::Output {
+        let quotient = self.clone() / rhs.clone();
+        let quotient = Self::new(
+            quotient.re.clone() - quotient.re % T::ONE,
+            quotient.im.clone() - quotient.im % T::ONE,
+        );
+        self - rhs * quotient
+    }
+}
+impl<T> Neg for Complex<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.re, -self.im)
+    }
+}
+impl<T> AddAssign for Complex<T>
+where
+    T: Num,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.re += rhs.re;
+        self.im += rhs.im;
+    }
+}
+impl<T> SubAssign for Complex<T>
+where
+    T: Num,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.re -= rhs.re;
+        self.im -= rhs.im;
+    }
+}
+impl<T> MulAssign for Complex<T>
+where
+    T: Num,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.clone() * rhs;
+    }
+}
+impl<T> DivAssign for Complex<T>
+where
+    T: Num,
+{
+    fn div_assign(&mut self, rhs: Self) {
+        *self = self.clone() / rhs;
+    }
+}
+impl<T> RemAssign for Complex<T>
+where
+    T: Num,
+{
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = self.clone() % rhs;
+    }
+}
+impl<T> From<T> for Complex<T>
+where
+    T: Num,
+{
+    fn from(value: T) -> Self {
+        Self::new(value, T::ZERO)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::Complex;
+    use crate::{mat, matrix::Matrix};
+
+    #[test]
+    fn arithmetic_test() {
+        let a = Complex::new(1, 2);
+        let b = Complex::new(3, -1);
+
+        assert_eq!(a + b, Complex::new(4, 1));
+        assert_eq!(a * b, Complex::new(5, 5));
+        assert_eq!(a * Complex::I, Complex::new(-2, 1));
+        assert_eq!(
+            Complex::new(5.0, 5.0) / Complex::new(3.0, -1.0),
+            Complex::new(1.0, 2.0)
+        );
+        assert_eq!(Complex::new(7, 3) % Complex::new(2, 0), Complex::new(1, 1));
+        assert_eq!(a.conj(), Complex::new(1, -2));
+        assert_eq!(format!("{:.1}", Complex::new(1.0, -0.5)), "1.0-0.5i");
+    }
+
+    #[test]
+    fn dft_test() {
+        let dft = Matrix::<Complex<f64>, 4, 4>::IDENTITY.map_indexed(|x, y, _| {
+            Complex::<f64>::from_polar(1.0, -2.0 * std::f64::consts::PI * (x * y) as f64 / 4.0)
+        });
+        let signal: Matrix<Complex<f64>, 1, 4> = mat![1.0, 1.0, 1.0, 1.0].map(Complex::from);
+
+        let spectrum = dft * signal;
+        for (a, b) in spectrum.rows().zip([4.0, 0.0, 0.0, 0.0]) {
+            assert!((a[0] - Complex::from(b)).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,531 @@
+//! Matrices with size known only at runtime.
+
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::Display,
+    ops::{Add, Index, IndexMut, Mul},
+};
+
+use super::{Float, LengthError, Matrix, Num, fmt_cells, linalg};
+
+/// A heap-allocated matrix.
+///
+/// Works like [Matrix], but its size is specified at runtime.
+/// `rows` and `columns` have the same meaning as `ROWS` and `COLUMNS`
+/// of [Matrix]: `rows` is the length of a single row and `columns`
+/// is the length of a single column.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct MatrixDyn<T> {
+    rows: usize,
+    columns: usize,
+    data: Vec<T>,
+}
+impl<T> MatrixDyn<T> {
+    /// Create a new matrix.
+    ///
+    /// Matrix layout is a flattened array of rows. Returns [LengthError]
+    /// if `data` does not have exactly `rows * columns` elements.
+    pub fn new(rows: usize, columns: usize, data: Vec<T>) -> Result<Self, LengthError> {
+        if rows.checked_mul(columns) != Some(data.len()) {
+            return Err(LengthError);
+        }
+        Ok(Self {
+            rows,
+            columns,
+            data,
+        })
+    }
+
+    /// Create a new matrix by calling a function for each cell.
+    ///
+    /// The function is called with the same arguments as [get()](Self::get).
+    ///
+    /// ## Panics
+    /// Panics if `rows * columns` overflows.
+    pub fn from_fn<F>(rows: usize, columns: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let len = rows
+            .checked_mul(columns)
+            .expect("number of matrix elements overflows");
+        let mut data = Vec::with_capacity(len);
+        for y in 0..columns {
+            for x in 0..rows {
+                data.push(f(x, y));
+            }
+        }
+        Self {
+            rows,
+            columns,
+            data,
+        }
+    }
+
+    /// Get the size of this matrix as `(rows, columns)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.columns)
+    }
+
+    /// Check whether this matrix is square.
+    pub fn is_square(&self) -> bool {
+        self.rows == self.columns
+    }
+
+    /// Get cells of this matrix as a flattened array of rows.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Get mutable cells of this matrix as a flattened array of rows.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    /// Convert this matrix into a flattened array of rows.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Get a reference to a cell.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row >= self.rows || col >= self.columns {
+            None
+        } else {
+            Some(&self.data[row + col * self.rows])
+        }
+    }
+
+    /// Get a mutable reference to a cell.
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row >= self.rows || col >= self.columns {
+            None
+        } else {
+            Some(&mut self.data[row + col * self.rows])
+        }
+    }
+
+    /// Iterate over rows of this matrix.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + '_ {
+        (0..self.columns).map(|n| &self.data[n * self.rows..][..self.rows])
+    }
+
+    /// Iterate over mutable rows of this matrix.
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [T]> + '_ {
+        let rows = self.rows;
+        // Chunks can't be empty, so rows of a matrix without rows
+        // are made up.
+        let mut chunks = self.data.chunks_exact_mut(rows.max(1));
+        (0..self.columns).map(move |_| match rows {
+            0 => Default::default(),
+            _ => chunks.next().unwrap(),
+        })
+    }
+
+    /// Get a reference to a row.
+    pub fn row(&self, n: usize) -> Option<&[T]> {
+        if n >= self.columns {
+            None
+        } else {
+            Some(&self.data[n * self.rows..][..self.rows])
+        }
+    }
+
+    /// Get a mutable reference to a row.
+    pub fn row_mut(&mut self, n: usize) -> Option<&mut [T]> {
+        if n >= self.columns {
+            None
+        } else {
+            Some(&mut self.data[n * self.rows..][..self.rows])
+        }
+    }
+
+    /// Obtain transposed matrix.
+    pub fn transposed(&self) -> MatrixDyn<&T> {
+        MatrixDyn::from_fn(self.columns, self.rows, |x, y| {
+            &self.data[y + x * self.rows]
+        })
+    }
+
+    /// Map all elements of this matrix to a new matrix.
+    pub fn map<Y, F>(self, map: F) -> MatrixDyn<Y>
+    where
+        F: FnMut(T) -> Y,
+    {
+        MatrixDyn {
+            rows: self.rows,
+            columns: self.columns,
+            data: self.data.into_iter().map(map).collect(),
+        }
+    }
+
+    /// Get a matrix of references to elements of this matrix
+    pub fn as_ref(&self) -> MatrixDyn<&T> {
+        MatrixDyn {
+            rows: self.rows,
+            columns: self.columns,
+            data: self.data.iter().collect(),
+        }
+    }
+
+    /// Multiply this matrix by another matrix.
+    ///
+    /// Returns [None] if the length of rows of this matrix does
+    /// not match the length of columns of the other matrix.
+    pub fn checked_mul(&self, rhs: &MatrixDyn<T>) -> Option<MatrixDyn<T>>
+    where
+        T: Num,
+    {
+        if self.rows != rhs.columns {
+            return None;
+        }
+        Some(MatrixDyn::from_fn(rhs.rows, self.columns, |x, y| {
+            let mut sum = T::ZERO;
+            for i in 0..self.rows {
+                sum += self[(i, y)].clone() * rhs[(x, i)].clone();
+            }
+            sum
+        }))
+    }
+
+    /// Add another matrix to this matrix.
+    ///
+    /// Returns [None] if the matrices have different shapes.
+    pub fn checked_add(mut self, rhs: MatrixDyn<T>) -> Option<MatrixDyn<T>>
+    where
+        T: Num,
+    {
+        if self.shape() != rhs.shape() {
+            return None;
+        }
+        self.data
+            .iter_mut()
+            .zip(rhs.data)
+            .for_each(|(x, v)| *x += v);
+        Some(self)
+    }
+}
+impl<T> MatrixDyn<&T>
+where
+    T: Copy,
+{
+    /// Create a copy of this matrix with all values copied.
+    pub fn copied(&self) -> MatrixDyn<T> {
+        MatrixDyn {
+            rows: self.rows,
+            columns: self.columns,
+            data: self.data.iter().map(|x| **x).collect(),
+        }
+    }
+}
+impl<T> MatrixDyn<&T>
+where
+    T: Clone,
+{
+    /// Create a copy of this matrix with all values cloned.
+    pub fn cloned(&self) -> MatrixDyn<T> {
+        MatrixDyn {
+            rows: self.rows,
+            columns: self.columns,
+            data: self.data.iter().map(|x| (*x).clone()).collect(),
+        }
+    }
+}
+impl<T> MatrixDyn<T>
+where
+    T: Num,
+{
+    /// Create a matrix filled with zeroes.
+    pub fn zeroed(rows: usize, columns: usize) -> Self {
+        Self::from_fn(rows, columns, |_, _| T::ZERO)
+    }
+
+    /// Obtain the identity matrix of the provided size.
+    pub fn identity(size: usize) -> Self {
+        Self::from_fn(size, size, |x, y| if x == y { T::ONE } else { T::ZERO })
+    }
+}
+impl<T> MatrixDyn<T>
+where
+    T: Float,
+{
+    /// Calculate the determinant of this matrix.
+    ///
+    /// Returns zero if this matrix is singular, like
+    /// [Matrix::determinant].
+    ///
+    /// ## Panics
+    /// Panics if this matrix is not square.
+    pub fn determinant(&self) -> T {
+        assert!(self.is_square(), "determinant of a non-square matrix");
+        let size = self.rows;
+        let tolerance = linalg::tolerance(&self.data, size);
+        let mut u = self.clone();
+        let mut det = T::ONE;
+        for k in 0..size {
+            let pivot = linalg::pivot(&u.data, size, k);
+            if pivot != k {
+                u.swap_rows(k, pivot);
+                det = T::ZERO - det;
+            }
+            if u[(k, k)].abs() <= tolerance {
+                return T::ZERO;
+            }
+            det *= u[(k, k)];
+            linalg::eliminate(&mut u.data, size, k, |_, _| {});
+        }
+        det
+    }
+
+    /// Solve a linear system `self * x = rhs` for `x`.
+    ///
+    /// `rhs` must be a column vector. Uses Gaussian elimination with partial
+    /// pivoting. Returns [None] if this matrix is singular.
+    ///
+    /// ## Panics
+    /// Panics if this matrix is not square or `rhs` does not have
+    /// the same number of rows as this matrix.
+    pub fn solve(&self, rhs: &MatrixDyn<T>) -> Option<MatrixDyn<T>> {
+        assert!(self.is_square(), "solving a non-square system");
+        assert_eq!(
+            rhs.shape(),
+            (1, self.columns),
+            "right-hand side is not a matching column vector"
+        );
+
+        let size = self.rows;
+        let tolerance = linalg::tolerance(&self.data, size);
+        let mut u = self.clone();
+        let mut x = rhs.clone();
+        for k in 0..size {
+            let pivot = linalg::pivot(&u.data, size, k);
+            if pivot != k {
+                u.swap_rows(k, pivot);
+                x.data.swap(k, pivot);
+            }
+            if u[(k, k)].abs() <= tolerance {
+                return None;
+            }
+            linalg::eliminate(&mut u.data, size, k, |i, factor| {
+                let sub = factor * x.data[k];
+                x.data[i] -= sub;
+            });
+        }
+        for i in (0..size).rev() {
+            let mut sum = x.data[i];
+            for j in i + 1..size {
+                sum -= u[(j, i)] * x.data[j];
+            }
+            x.data[i] = sum / u[(i, i)];
+        }
+        Some(x)
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for j in 0..self.rows {
+            self.data.swap(j + a * self.rows, j + b * self.rows);
+        }
+    }
+}
+impl<T> Display for MatrixDyn<T>
+where
+    T: Display,
+{
+    /// Print this matrix as aligned rows.
+    ///
+    /// See [Matrix] for details.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_cells(&self.data, &mut vec![0; self.rows], f)
+    }
+}
+impl<T> Index<(usize, usize)> for MatrixDyn<T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        self.get(index.0, index.1).unwrap_or_else(|| {
+            panic!(
+                "Indexing a matrix ({}x{}) out of bounds ({}, {})",
+                self.rows, self.columns, index.0, index.1
+            )
+        })
+    }
+}
+impl<T> IndexMut<(usize, usize)> for MatrixDyn<T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        let (rows, columns) = self.shape();
+        self.get_mut(index.0, index.1).unwrap_or_else(|| {
+            panic!(
+                "Indexing a matrix ({rows}x{columns}) out of bounds ({}, {})",
+                index.0, index.1
+            )
+        })
+    }
+}
+impl<T> Mul<MatrixDyn<T>> for MatrixDyn<T>
+where
+    T: Num,
+{
+    type Output = MatrixDyn<T>;
+
+    /// Multiply matrices.
+    ///
+    /// ## Panics
+    /// Panics if shapes of matrices are incompatible. See
+    /// [MatrixDyn::checked_mul] for a non-panicking version.
+    fn mul(self, rhs: MatrixDyn<T>) -> Self::Output {
+        self.checked_mul(&rhs).unwrap_or_else(|| {
+            panic!(
+                "Multiplying matrices of incompatible shapes ({}x{} and {}x{})",
+                self.rows, self.columns, rhs.rows, rhs.columns
+            )
+        })
+    }
+}
+impl<T> Mul<T> for MatrixDyn<T>
+where
+    T: Mul<Output = T> + Clone,
+{
+    type Output = MatrixDyn<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|x| x * rhs.clone())
+    }
+}
+impl<T> Add<MatrixDyn<T>> for MatrixDyn<T>
+where
+    T: Num,
+{
+    type Output = MatrixDyn<T>;
+
+    /// Add matrices.
+    ///
+    /// ## Panics
+    /// Panics if shapes of matrices are different. See
+    /// [MatrixDyn::checked_add] for a non-panicking version.
+    fn add(self, rhs: MatrixDyn<T>) -> Self::Output {
+        let (a, b) = (self.shape(), rhs.shape());
+        self.checked_add(rhs).unwrap_or_else(|| {
+            panic!(
+                "Adding matrices of different shapes ({}x{} and {}x{})",
+                a.0, a.1, b.0, b.1
+            )
+        })
+    }
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> From<Matrix<T, ROWS, COLUMNS>> for MatrixDyn<T> {
+    fn from(value: Matrix<T, ROWS, COLUMNS>) -> Self {
+        Self {
+            rows: ROWS,
+            columns: COLUMNS,
+            data: value.0.into_iter().flatten().collect(),
+        }
+    }
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> TryFrom<MatrixDyn<T>>
+    for Matrix<T, ROWS, COLUMNS>
+{
+    type Error = LengthError;
+
+    /// Convert into a fixed-size matrix.
+    ///
+    /// Returns [LengthError] if shape of the matrix does not match.
+    fn try_from(value: MatrixDyn<T>) -> Result<Self, Self::Error> {
+        if value.shape() != (ROWS, COLUMNS) {
+            return Err(LengthError);
+        }
+        Matrix::try_from_iter(value.data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::MatrixDyn;
+    use crate::{mat, matrix::Matrix};
+
+    #[test]
+    fn basic_test() {
+        let matrix = MatrixDyn::new(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+
+        assert_eq!(matrix.shape(), (3, 2));
+        assert_eq!(matrix[(1, 1)], 5);
+        assert_eq!(matrix.get(3, 0), None);
+        assert_eq!(matrix.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(
+            matrix.transposed().copied(),
+            MatrixDyn::new(2, 3, vec![1, 4, 2, 5, 3, 6]).unwrap()
+        );
+        assert!(MatrixDyn::new(3, 2, vec![1, 2, 3]).is_err());
+        assert_eq!(format!("{matrix}"), "[1, 2, 3]\n[4, 5, 6]");
+
+        let mut empty = MatrixDyn::<i32>::new(0, 2, vec![]).unwrap();
+        assert_eq!(empty.rows().collect::<Vec<_>>(), [&[], &[]]);
+        assert_eq!(empty.rows_mut().len(), 2);
+    }
+
+    #[test]
+    #[should_panic = "number of matrix elements overflows"]
+    fn from_fn_overflow() {
+        MatrixDyn::from_fn(usize::MAX, 2, |_, _| 0u8);
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let first: Matrix<f32, 3, 2> = mat! {
+            1.0, 2.0, 3.0,
+            4.0, 5.0, 6.0,
+        };
+        let second: Matrix<f32, 2, 3> = mat! {
+            7.0,  8.0,
+            9.0,  10.0,
+            11.0, 12.0,
+        };
+
+        let product = MatrixDyn::from(first) * MatrixDyn::from(second);
+        assert_eq!(Matrix::try_from(product.clone()), Ok(first * second));
+        assert!(Matrix::<f32, 2, 3>::try_from(product.clone()).is_err());
+        assert!(
+            MatrixDyn::from(first)
+                .checked_mul(&MatrixDyn::from(first))
+                .is_none()
+        );
+
+        assert_eq!(
+            product.clone() + MatrixDyn::identity(2),
+            MatrixDyn::from(first * second + Matrix::IDENTITY)
+        );
+        assert!(product.checked_add(MatrixDyn::zeroed(3, 2)).is_none());
+
+        let empty = MatrixDyn::<f32>::zeroed(0, 2) * MatrixDyn::zeroed(3, 0);
+        assert_eq!(empty, MatrixDyn::zeroed(3, 2));
+    }
+
+    #[test]
+    fn solve_test() {
+        let matrix: MatrixDyn<f64> =
+            MatrixDyn::new(3, 3, vec![2.0, 1.0, -1.0, -3.0, -1.0, 2.0, -2.0, 1.0, 2.0]).unwrap();
+        let rhs = MatrixDyn::new(1, 3, vec![8.0, -11.0, -3.0]).unwrap();
+
+        let x = matrix.solve(&rhs).unwrap();
+        for (a, b) in x.as_slice().iter().zip([2.0, 3.0, -1.0]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert!((matrix.determinant() + 1.0).abs() < 1e-9);
+
+        let singular = MatrixDyn::new(2, 2, vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+        assert!(
+            singular
+                .solve(&MatrixDyn::new(1, 2, vec![1.0, 1.0]).unwrap())
+                .is_none()
+        );
+        assert_eq!(singular.determinant(), 0.0);
+
+        let singular =
+            MatrixDyn::new(3, 3, vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9]).unwrap();
+        assert!(
+            singular
+                .solve(&MatrixDyn::new(1, 3, vec![1.0, 1.0, 1.0]).unwrap())
+                .is_none()
+        );
+        assert_eq!(singular.determinant(), 0.0);
+    }
+}
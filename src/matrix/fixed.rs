@@ -0,0 +1,241 @@
+//! Fixed-point numbers.
+
+use core::{
+    fmt::Display,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+use super::{Float, Num};
+
+/// A fixed-point number.
+///
+/// Stores a number as an integer `I` scaled by `2^FRAC`, so the lowest
+/// `FRAC` bits are the fractional part. Useful on targets without an FPU.
+///
+/// Implemented for `i8`, `i16`, `i32` and `i64`. Multiplication and division
+/// are done in a twice as wide integer, results that do not fit into `I`
+/// are truncated.
+///
+/// ## Compile-time errors
+/// `FRAC` must be less than the number of bits in `I`.
+///
+/// ```compile_fail
+/// use libcommons::matrix::fixed::Fixed;
+///
+/// let x = Fixed::<i8, 8>::from_int(1);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct Fixed<I, const FRAC: u32>(I);
+
+macro_rules! impl_fixed {
+    ($($ty:ty => $wide:ty)*) => {$(
+        impl<const FRAC: u32> Fixed<$ty, FRAC> {
+            const CHECK: () = assert!(
+                FRAC < <$ty>::BITS,
+                "FRAC must be less than the number of bits"
+            );
+
+            /// Create a number from raw bits.
+            pub const fn from_bits(bits: $ty) -> Self {
+                let () = Self::CHECK;
+                Self(bits)
+            }
+
+            /// Get raw bits of this number.
+            pub const fn to_bits(self) -> $ty {
+                self.0
+            }
+
+            /// Convert an integer to a fixed-point number.
+            pub const fn from_int(value: $ty) -> Self {
+                let () = Self::CHECK;
+                Self(value << FRAC)
+            }
+
+            /// Get the integer part of this number, rounding towards
+            /// negative infinity.
+            pub const fn to_int(self) -> $ty {
+                let () = Self::CHECK;
+                self.0 >> FRAC
+            }
+
+            /// Convert a float to a fixed-point number.
+            ///
+            /// Values that do not fit are saturated.
+            pub fn from_f64(value: f64) -> Self {
+                let () = Self::CHECK;
+                Self((value * (1u128 << FRAC) as f64) as $ty)
+            }
+
+            /// Convert this number to a float.
+            pub fn to_f64(self) -> f64 {
+                let () = Self::CHECK;
+                self.0 as f64 / (1u128 << FRAC) as f64
+            }
+
+            /// Convert a float to a fixed-point number.
+            ///
+            /// Values that do not fit are saturated.
+            pub fn from_f32(value: f32) -> Self {
+                Self::from_f64(value as f64)
+            }
+
+            /// Convert this number to a float.
+            pub fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+        }
+        impl<const FRAC: u32> Num for Fixed<$ty, FRAC> {
+            const ZERO: Self = Self(0);
+            const ONE: Self = {
+                let () = Self::CHECK;
+                Self(1 << FRAC)
+            };
+        }
+        impl<const FRAC: u32> Float for Fixed<$ty, FRAC> {
+            const EPSILON: Self = Self(1);
+
+            fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+
+            /// Square root.
+            ///
+            /// Returns zero for negative numbers.
+            fn sqrt(self) -> Self {
+                if self.0 <= 0 {
+                    return Self(0);
+                }
+                let () = Self::CHECK;
+                Self((((self.0 as $wide) << FRAC).isqrt()) as $ty)
+            }
+        }
+        impl<const FRAC: u32> Display for Fixed<$ty, FRAC> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Display::fmt(&self.to_f64(), f)
+            }
+        }
+        impl<const FRAC: u32> Add for Fixed<$ty, FRAC> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self(self.0 + rhs.0)
+            }
+        }
+        impl<const FRAC: u32> Sub for Fixed<$ty, FRAC> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self(self.0 - rhs.0)
+            }
+        }
+        impl<const FRAC: u32> Mul for Fixed<$ty, FRAC> {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                let () = Self::CHECK;
+                Self(((self.0 as $wide * rhs.0 as $wide) >> FRAC) as $ty)
+            }
+        }
+        impl<const FRAC: u32> Div for Fixed<$ty, FRAC> {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                let () = Self::CHECK;
+                Self((((self.0 as $wide) << FRAC) / rhs.0 as $wide) as $ty)
+            }
+        }
+        impl<const FRAC: u32> Rem for Fixed<$ty, FRAC> {
+            type Output = Self;
+
+            fn rem(self, rhs: Self) -> Self::Output {
+                Self(self.0 % rhs.0)
+            }
+        }
+        impl<const FRAC: u32> Neg for Fixed<$ty, FRAC> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self(-self.0)
+            }
+        }
+        impl<const FRAC: u32> AddAssign for Fixed<$ty, FRAC> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+        impl<const FRAC: u32> SubAssign for Fixed<$ty, FRAC> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+        impl<const FRAC: u32> MulAssign for Fixed<$ty, FRAC> {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+        impl<const FRAC: u32> DivAssign for Fixed<$ty, FRAC> {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+        impl<const FRAC: u32> RemAssign for Fixed<$ty, FRAC> {
+            fn rem_assign(&mut self, rhs: Self) {
+                *self = *self % rhs;
+            }
+        }
+        impl<const FRAC: u32> From<$ty> for Fixed<$ty, FRAC> {
+            fn from(value: $ty) -> Self {
+                Self::from_int(value)
+            }
+        }
+    )*};
+}
+impl_fixed! {
+    i8 => i16
+    i16 => i32
+    i32 => i64
+    i64 => i128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fixed;
+    use crate::{
+        mat,
+        matrix::{Float, Matrix, Num},
+    };
+
+    type F = Fixed<i32, 16>;
+
+    #[test]
+    fn arithmetic_test() {
+        let a = F::from_f64(1.5);
+        let b = F::from_int(-2);
+
+        assert_eq!((a + b).to_f64(), -0.5);
+        assert_eq!((a * b).to_f64(), -3.0);
+        assert_eq!((b / a).to_f64(), -1.3333282470703125);
+        assert_eq!((a % F::ONE).to_f64(), 0.5);
+        assert_eq!((-a).to_int(), -2);
+        assert_eq!(format!("{}", F::from_f32(0.25)), "0.25");
+        assert_eq!(Fixed::<i8, 4>::ONE.to_bits(), 16);
+    }
+
+    #[test]
+    fn matrix_test() {
+        let matrix: Matrix<F, 2, 2> = mat! {
+            F::from_int(1), F::from_int(2),
+            F::from_int(3), F::from_int(4),
+        };
+
+        assert_eq!(matrix * Matrix::IDENTITY, matrix);
+        let close = |a: F, b: F| (a - b).abs() < F::from_f64(0.001);
+        assert!(close(matrix.determinant(), F::from_int(-2)));
+        let x = matrix.solve(mat![F::from_int(5), F::from_int(11)]).unwrap();
+        assert!(close(x[(0, 0)], F::from_int(1)));
+        assert!(close(x[(0, 1)], F::from_int(2)));
+        assert_eq!(F::from_int(9).sqrt(), F::from_int(3));
+    }
+}
@@ -0,0 +1,104 @@
+//! Conversions between matrices of this crate and other math libraries.
+//!
+//! Matrices here are stored as rows, while both `glam` and `nalgebra`
+//! store columns, so conversions transpose the underlying storage. The
+//! mathematical meaning of a matrix is preserved.
+
+use super::Matrix;
+
+#[cfg(feature = "glam")]
+macro_rules! impl_glam {
+    ($($glam:ty => $ty:ty, $size:literal;)*) => {$(
+        impl From<Matrix<$ty, $size, $size>> for $glam {
+            fn from(value: Matrix<$ty, $size, $size>) -> Self {
+                <$glam>::from_cols_array_2d(&value.transposed().copied().0)
+            }
+        }
+        impl From<$glam> for Matrix<$ty, $size, $size> {
+            fn from(value: $glam) -> Self {
+                Matrix(value.transpose().to_cols_array_2d())
+            }
+        }
+    )*};
+}
+#[cfg(feature = "glam")]
+impl_glam! {
+    glam::Mat2 => f32, 2;
+    glam::Mat3 => f32, 3;
+    glam::Mat4 => f32, 4;
+    glam::DMat2 => f64, 2;
+    glam::DMat3 => f64, 3;
+    glam::DMat4 => f64, 4;
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T, const ROWS: usize, const COLUMNS: usize> From<Matrix<T, ROWS, COLUMNS>>
+    for nalgebra::SMatrix<T, COLUMNS, ROWS>
+where
+    T: nalgebra::Scalar,
+{
+    /// Convert into a `nalgebra` matrix.
+    ///
+    /// Note that `nalgebra` lists the number of rows first, so
+    /// `Matrix<T, ROWS, COLUMNS>` becomes `SMatrix<T, COLUMNS, ROWS>`.
+    fn from(value: Matrix<T, ROWS, COLUMNS>) -> Self {
+        Self::from_fn(|y, x| value.0[y][x].clone())
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T, const ROWS: usize, const COLUMNS: usize> From<nalgebra::SMatrix<T, COLUMNS, ROWS>>
+    for Matrix<T, ROWS, COLUMNS>
+where
+    T: nalgebra::Scalar,
+{
+    /// Convert from a `nalgebra` matrix.
+    fn from(value: nalgebra::SMatrix<T, COLUMNS, ROWS>) -> Self {
+        Matrix(core::array::from_fn(|y| {
+            core::array::from_fn(|x| value[(y, x)].clone())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Mat4f;
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn glam_test() {
+        let matrix = Mat4f::translation(1.0, 2.0, 3.0);
+        let glam: glam::Mat4 = matrix.into();
+
+        assert_eq!(
+            glam,
+            glam::Mat4::from_translation(glam::vec3(1.0, 2.0, 3.0))
+        );
+        assert_eq!(Mat4f::from(glam), matrix);
+        assert_eq!(
+            glam.transform_point3(glam::vec3(1.0, 1.0, 1.0)),
+            glam::vec3(2.0, 3.0, 4.0)
+        );
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_test() {
+        use crate::mat;
+
+        let matrix = Mat4f::translation(1.0, 2.0, 3.0);
+        let nalgebra: nalgebra::Matrix4<f32> = matrix.into();
+
+        assert_eq!(
+            nalgebra,
+            nalgebra::Matrix4::new_translation(&nalgebra::vector![1.0, 2.0, 3.0])
+        );
+        assert_eq!(Mat4f::from(nalgebra), matrix);
+
+        let wide: nalgebra::Matrix2x3<i32> = mat! { <i32, 3, 2>
+            1, 2, 3,
+            4, 5, 6,
+        }
+        .into();
+        assert_eq!(wide, nalgebra::Matrix2x3::new(1, 2, 3, 4, 5, 6));
+    }
+}
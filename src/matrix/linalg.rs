@@ -0,0 +1,551 @@
+//! Matrix decompositions.
+
+use super::{Float, Matrix};
+
+/// LU decomposition of a square matrix.
+///
+/// Holds lower triangular matrix `L` with ones on its diagonal, upper
+/// triangular matrix `U` and a row permutation `P`, such that `P * A = L * U`.
+///
+/// See [Matrix::lu].
+#[derive(Clone, Copy, Debug)]
+pub struct Lu<T, const SIZE: usize> {
+    l: Matrix<T, SIZE, SIZE>,
+    u: Matrix<T, SIZE, SIZE>,
+    permutation: [usize; SIZE],
+    odd: bool,
+    tolerance: T,
+}
+impl<T, const SIZE: usize> Lu<T, SIZE>
+where
+    T: Float,
+{
+    /// Get the lower triangular matrix.
+    pub fn l(&self) -> &Matrix<T, SIZE, SIZE> {
+        &self.l
+    }
+
+    /// Get the upper triangular matrix.
+    pub fn u(&self) -> &Matrix<T, SIZE, SIZE> {
+        &self.u
+    }
+
+    /// Get the row permutation.
+    ///
+    /// Row `i` of `P * A` is row `permutation()[i]` of `A`.
+    pub fn permutation(&self) -> &[usize; SIZE] {
+        &self.permutation
+    }
+
+    /// Split this decomposition into `L`, `U` and the row permutation.
+    pub fn into_parts(self) -> (Matrix<T, SIZE, SIZE>, Matrix<T, SIZE, SIZE>, [usize; SIZE]) {
+        (self.l, self.u, self.permutation)
+    }
+
+    /// Check whether the decomposed matrix is singular.
+    ///
+    /// Pivots are compared against a tolerance scaled by the largest
+    /// element of the decomposed matrix, so matrices that are singular up
+    /// to rounding errors are detected as well.
+    pub fn is_singular(&self) -> bool {
+        (0..SIZE).any(|i| self.u.0[i][i].abs() <= self.tolerance)
+    }
+
+    /// Calculate the determinant of the decomposed matrix.
+    ///
+    /// Returns zero if the decomposed matrix is singular (see
+    /// [is_singular()](Self::is_singular)).
+    pub fn determinant(&self) -> T {
+        if self.is_singular() {
+            return T::ZERO;
+        }
+
+        let mut det = T::ONE;
+        for i in 0..SIZE {
+            det *= self.u.0[i][i];
+        }
+        if self.odd { T::ZERO - det } else { det }
+    }
+
+    /// Solve `A * x = rhs` for `x`, where `A` is the decomposed matrix.
+    ///
+    /// Returns [None] if the decomposed matrix is singular.
+    pub fn solve(&self, rhs: &Matrix<T, 1, SIZE>) -> Option<Matrix<T, 1, SIZE>> {
+        if self.is_singular() {
+            return None;
+        }
+
+        let mut x = Matrix::<T, 1, SIZE>::ZERO;
+        for i in 0..SIZE {
+            let mut sum = rhs.0[self.permutation[i]][0];
+            for j in 0..i {
+                sum -= self.l.0[i][j] * x.0[j][0];
+            }
+            x.0[i][0] = sum;
+        }
+        for i in (0..SIZE).rev() {
+            let mut sum = x.0[i][0];
+            for j in i + 1..SIZE {
+                sum -= self.u.0[i][j] * x.0[j][0];
+            }
+            x.0[i][0] = sum / self.u.0[i][i];
+        }
+        Some(x)
+    }
+}
+
+/// QR decomposition of a matrix.
+///
+/// Holds matrix `Q` with orthonormal columns and upper triangular matrix `R`,
+/// such that `A = Q * R`.
+///
+/// See [Matrix::qr].
+#[derive(Clone, Copy, Debug)]
+pub struct Qr<T, const ROWS: usize, const COLUMNS: usize> {
+    q: Matrix<T, ROWS, COLUMNS>,
+    r: Matrix<T, ROWS, ROWS>,
+    tolerance: T,
+}
+impl<T, const ROWS: usize, const COLUMNS: usize> Qr<T, ROWS, COLUMNS>
+where
+    T: Float,
+{
+    /// Get the orthonormal matrix.
+    pub fn q(&self) -> &Matrix<T, ROWS, COLUMNS> {
+        &self.q
+    }
+
+    /// Get the upper triangular matrix.
+    pub fn r(&self) -> &Matrix<T, ROWS, ROWS> {
+        &self.r
+    }
+
+    /// Split this decomposition into `Q` and `R`.
+    pub fn into_parts(self) -> (Matrix<T, ROWS, COLUMNS>, Matrix<T, ROWS, ROWS>) {
+        (self.q, self.r)
+    }
+
+    /// Check whether the decomposed matrix has linearly dependent columns.
+    ///
+    /// Like [Lu::is_singular], diagonal of `R` is compared against
+    /// a tolerance scaled by the largest element of the decomposed matrix.
+    pub fn is_rank_deficient(&self) -> bool {
+        (0..ROWS).any(|i| self.r.0[i][i].abs() <= self.tolerance)
+    }
+
+    /// Find `x` minimizing the length of `A * x - rhs`, where `A` is the
+    /// decomposed matrix.
+    ///
+    /// Returns [None] if the decomposed matrix is rank deficient.
+    pub fn least_squares(&self, rhs: &Matrix<T, 1, COLUMNS>) -> Option<Matrix<T, 1, ROWS>> {
+        if self.is_rank_deficient() {
+            return None;
+        }
+
+        let mut x = Matrix::<T, 1, ROWS>::ZERO;
+        for j in 0..ROWS {
+            let mut sum = T::ZERO;
+            for i in 0..COLUMNS {
+                sum += self.q.0[i][j] * rhs.0[i][0];
+            }
+            x.0[j][0] = sum;
+        }
+        for i in (0..ROWS).rev() {
+            let mut sum = x.0[i][0];
+            for j in i + 1..ROWS {
+                sum -= self.r.0[i][j] * x.0[j][0];
+            }
+            x.0[i][0] = sum / self.r.0[i][i];
+        }
+        Some(x)
+    }
+}
+
+impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<T, ROWS, COLUMNS>
+where
+    T: Float,
+{
+    /// Decompose this matrix into an orthonormal and an upper triangular
+    /// matrix.
+    ///
+    /// Uses Householder reflections. Matrices with more columns than rows
+    /// (`ROWS > COLUMNS`) are rejected at compile time.
+    ///
+    /// ```compile_fail
+    /// use libcommons::{mat, matrix::Matrix};
+    ///
+    /// let wide: Matrix<f64, 3, 2> = mat! {
+    ///     1.0, 2.0, 3.0,
+    ///     4.0, 5.0, 6.0,
+    /// };
+    /// wide.qr();
+    /// ```
+    pub fn qr(&self) -> Qr<T, ROWS, COLUMNS> {
+        let () = QrShape::<ROWS, COLUMNS>::CHECK;
+        let tolerance = tolerance(self.0.as_flattened(), COLUMNS);
+
+        let two = T::ONE + T::ONE;
+        let mut r = *self;
+        let mut v = Self::ZERO;
+
+        for k in 0..ROWS {
+            let mut norm = T::ZERO;
+            for i in k..COLUMNS {
+                norm += r.0[i][k] * r.0[i][k];
+            }
+            let norm = norm.sqrt();
+            if norm == T::ZERO {
+                continue;
+            }
+
+            let alpha = if r.0[k][k] > T::ZERO {
+                T::ZERO - norm
+            } else {
+                norm
+            };
+            let mut vnorm = T::ZERO;
+            for i in k..COLUMNS {
+                let x = if i == k { r.0[i][k] - alpha } else { r.0[i][k] };
+                v.0[i][k] = x;
+                vnorm += x * x;
+            }
+            let vnorm = vnorm.sqrt();
+            for i in k..COLUMNS {
+                v.0[i][k] /= vnorm;
+            }
+
+            for j in k..ROWS {
+                let mut dot = T::ZERO;
+                for i in k..COLUMNS {
+                    dot += v.0[i][k] * r.0[i][j];
+                }
+                for i in k..COLUMNS {
+                    let sub = two * dot * v.0[i][k];
+                    r.0[i][j] -= sub;
+                }
+            }
+        }
+
+        let mut q = Self::ZERO;
+        for i in 0..ROWS {
+            q.0[i][i] = T::ONE;
+        }
+        for k in (0..ROWS).rev() {
+            for j in 0..ROWS {
+                let mut dot = T::ZERO;
+                for i in k..COLUMNS {
+                    dot += v.0[i][k] * q.0[i][j];
+                }
+                for i in k..COLUMNS {
+                    let sub = two * dot * v.0[i][k];
+                    q.0[i][j] -= sub;
+                }
+            }
+        }
+
+        let mut upper = Matrix::<T, ROWS, ROWS>::ZERO;
+        for i in 0..ROWS {
+            for j in i..ROWS {
+                upper.0[i][j] = r.0[i][j];
+            }
+        }
+
+        Qr {
+            q,
+            r: upper,
+            tolerance,
+        }
+    }
+
+    /// Find `x` minimizing the length of `self * x - rhs`.
+    ///
+    /// Useful for solving overdetermined systems (i.e. fitting a curve
+    /// to a set of points). Returns [None] if columns of this matrix are
+    /// linearly dependent.
+    ///
+    /// Shortcut for `self.qr().least_squares(&rhs)`.
+    pub fn least_squares(&self, rhs: Matrix<T, 1, COLUMNS>) -> Option<Matrix<T, 1, ROWS>> {
+        self.qr().least_squares(&rhs)
+    }
+}
+
+impl<T, const SIZE: usize> Matrix<T, SIZE, SIZE>
+where
+    T: Float,
+{
+    /// Decompose this matrix into lower and upper triangular matrices.
+    ///
+    /// Uses Gaussian elimination with partial pivoting. Singular matrices
+    /// are decomposed as well, with (nearly) zero values left on the
+    /// diagonal of `U` (see [Lu::is_singular]).
+    pub fn lu(&self) -> Lu<T, SIZE> {
+        let tolerance = tolerance(self.0.as_flattened(), SIZE);
+        let mut u = *self;
+        let mut l = Self::ZERO;
+        let mut permutation: [usize; SIZE] = core::array::from_fn(|i| i);
+        let mut odd = false;
+
+        for k in 0..SIZE {
+            let pivot = pivot(u.0.as_flattened(), SIZE, k);
+            if pivot != k {
+                u.swap_rows(k, pivot);
+                for j in 0..k {
+                    let x = l.0[k][j];
+                    l.0[k][j] = l.0[pivot][j];
+                    l.0[pivot][j] = x;
+                }
+                permutation.swap(k, pivot);
+                odd = !odd;
+            }
+
+            l.0[k][k] = T::ONE;
+
+            if u.0[k][k].abs() <= tolerance {
+                continue;
+            }
+            eliminate(u.0.as_flattened_mut(), SIZE, k, |i, factor| {
+                l.0[i][k] = factor;
+            });
+        }
+
+        Lu {
+            l,
+            u,
+            permutation,
+            odd,
+            tolerance,
+        }
+    }
+
+    /// Calculate the determinant of this matrix.
+    ///
+    /// Shortcut for `self.lu().determinant()`.
+    pub fn determinant(&self) -> T {
+        self.lu().determinant()
+    }
+
+    /// Calculate the condition number of this matrix in the 1-norm.
+    ///
+    /// Tells how much errors in `rhs` can be amplified by [solve()](Self::solve).
+    /// Large values mean the matrix is close to singular. Returns [None] if
+    /// this matrix is singular.
+    ///
+    /// Finds the inverse matrix by solving `SIZE` linear systems, so
+    /// consider caching the result.
+    pub fn condition_number(&self) -> Option<T> {
+        let lu = self.lu();
+        if lu.is_singular() {
+            return None;
+        }
+
+        let mut inverse_norm = T::ZERO;
+        for x in 0..SIZE {
+            let mut unit = Matrix::<T, 1, SIZE>::ZERO;
+            unit.0[x][0] = T::ONE;
+            let inverse = lu.solve(&unit)?;
+            let mut sum = T::ZERO;
+            for [x] in inverse.0 {
+                sum += x.abs();
+            }
+            if sum > inverse_norm {
+                inverse_norm = sum;
+            }
+        }
+        Some(self.norm_l1() * inverse_norm)
+    }
+
+    /// Solve a linear system `self * x = rhs` for `x`.
+    ///
+    /// Uses Gaussian elimination with partial pivoting. Returns [None]
+    /// if this matrix is singular.
+    ///
+    /// When solving multiple systems with the same matrix, consider
+    /// calling [Lu::solve] on a [lu()](Self::lu) instead.
+    pub fn solve(&self, rhs: Matrix<T, 1, SIZE>) -> Option<Matrix<T, 1, SIZE>> {
+        self.lu().solve(&rhs)
+    }
+}
+
+/// Compile-time check of the shape of a matrix passed to [Matrix::qr].
+struct QrShape<const ROWS: usize, const COLUMNS: usize>;
+impl<const ROWS: usize, const COLUMNS: usize> QrShape<ROWS, COLUMNS> {
+    const CHECK: () = assert!(ROWS <= COLUMNS, "QR requires a matrix with ROWS <= COLUMNS");
+}
+
+/// Get the magnitude below which pivots of a decomposition of an `n`-sized
+/// matrix with `values` are considered to be zero.
+pub(super) fn tolerance<T: Float>(values: &[T], n: usize) -> T {
+    let mut max = T::ZERO;
+    for x in values {
+        if x.abs() > max {
+            max = x.abs();
+        }
+    }
+    let step = max * T::EPSILON;
+    let mut tolerance = T::ZERO;
+    for _ in 0..n {
+        tolerance += step;
+    }
+    tolerance
+}
+
+/// Find the row with the largest magnitude in column `k`, starting from
+/// row `k`.
+///
+/// `u` is a flattened array of rows of an `n`-sized square matrix.
+pub(super) fn pivot<T: Float>(u: &[T], n: usize, k: usize) -> usize {
+    let mut pivot = k;
+    for i in k + 1..n {
+        if u[i * n + k].abs() > u[pivot * n + k].abs() {
+            pivot = i;
+        }
+    }
+    pivot
+}
+
+/// Subtract multiples of row `k` from rows below it to zero column `k`.
+///
+/// `u` is a flattened array of rows of an `n`-sized square matrix.
+/// `f` is called with the index of each row and the factor row `k` was
+/// multiplied by.
+pub(super) fn eliminate<T: Float>(u: &mut [T], n: usize, k: usize, mut f: impl FnMut(usize, T)) {
+    let head = u[k * n + k];
+    for i in k + 1..n {
+        let factor = u[i * n + k] / head;
+        for j in k..n {
+            let sub = factor * u[k * n + j];
+            u[i * n + j] -= sub;
+        }
+        f(i, factor);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{mat, matrix::Matrix};
+
+    #[test]
+    fn lu_test() {
+        let matrix: Matrix<f64, 3, 3> = mat! {
+            1.0, 2.0, 3.0,
+            4.0, 5.0, 6.0,
+            7.0, 8.0, 10.0,
+        };
+        let lu = matrix.lu();
+
+        assert_eq!(lu.permutation(), &[2, 0, 1]);
+        let permuted = Matrix::from_rows(lu.permutation().map(|i| matrix.0[i]));
+        let product = *lu.l() * *lu.u();
+        for (a, b) in product
+            .0
+            .as_flattened()
+            .iter()
+            .zip(permuted.0.as_flattened())
+        {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert!((lu.determinant() + 3.0).abs() < 1e-9);
+        assert!(!lu.is_singular());
+
+        let singular: Matrix<f64, 2, 2> = mat! {
+            1.0, 2.0,
+            2.0, 4.0,
+        };
+        assert!(singular.lu().is_singular());
+        assert_eq!(singular.determinant(), 0.0);
+
+        let singular: Matrix<f64, 3, 3> = mat! {
+            0.1, 0.2, 0.3,
+            0.4, 0.5, 0.6,
+            0.7, 0.8, 0.9,
+        };
+        assert!(singular.lu().is_singular());
+        assert!(singular.solve(mat![1.0, 1.0, 1.0]).is_none());
+        assert_eq!(singular.determinant(), 0.0);
+    }
+
+    #[test]
+    fn solve_test() {
+        let matrix: Matrix<f64, 3, 3> = mat! {
+            2.0,  1.0, -1.0,
+            -3.0, -1.0, 2.0,
+            -2.0, 1.0,  2.0,
+        };
+        let rhs: Matrix<f64, 1, 3> = mat![8.0, -11.0, -3.0];
+
+        let x = matrix.solve(rhs).unwrap();
+        for (a, b) in x.0.as_flattened().iter().zip([2.0, 3.0, -1.0]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        for (a, b) in (matrix * x)
+            .0
+            .as_flattened()
+            .iter()
+            .zip(rhs.0.as_flattened())
+        {
+            assert!((a - b).abs() < 1e-9);
+        }
+
+        let singular: Matrix<f64, 2, 2> = mat! {
+            1.0, 2.0,
+            2.0, 4.0,
+        };
+        assert!(singular.solve(mat![1.0, 1.0]).is_none());
+    }
+
+    #[test]
+    fn condition_test() {
+        let matrix: Matrix<f64, 2, 2> = mat! {
+            1.0, 2.0,
+            3.0, 4.0,
+        };
+        assert!((matrix.condition_number().unwrap() - 21.0).abs() < 1e-9);
+        assert!((Matrix::<f64, 3, 3>::IDENTITY.condition_number().unwrap() - 1.0).abs() < 1e-9);
+
+        let singular: Matrix<f64, 2, 2> = mat! {
+            1.0, 2.0,
+            2.0, 4.0,
+        };
+        assert!(singular.condition_number().is_none());
+    }
+
+    #[test]
+    fn qr_test() {
+        let matrix: Matrix<f64, 2, 4> = mat! {
+            1.0, 0.0,
+            1.0, 1.0,
+            1.0, 2.0,
+            1.0, 3.0,
+        };
+        let qr = matrix.qr();
+
+        for (a, b) in (*qr.q() * *qr.r())
+            .0
+            .as_flattened()
+            .iter()
+            .zip(matrix.0.as_flattened())
+        {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert_eq!(qr.r().0[1][0], 0.0);
+
+        let x = matrix.least_squares(mat![1.0, 3.0, 5.0, 7.0]).unwrap();
+        for (a, b) in x.0.as_flattened().iter().zip([1.0, 2.0]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+
+        let dependent: Matrix<f64, 2, 3> = mat! {
+            1.0, 2.0,
+            1.0, 2.0,
+            1.0, 2.0,
+        };
+        assert!(dependent.least_squares(mat![1.0, 2.0, 3.0]).is_none());
+
+        let dependent: Matrix<f64, 2, 3> = mat! {
+            0.1, 0.3,
+            0.2, 0.6,
+            0.7, 2.1,
+        };
+        assert!(dependent.qr().is_rank_deficient());
+    }
+}
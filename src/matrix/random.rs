@@ -0,0 +1,77 @@
+//! Random matrix generation.
+
+use rand::{
+    Rng,
+    distr::{
+        Distribution, StandardUniform,
+        uniform::{SampleRange, SampleUniform},
+    },
+};
+
+use super::Matrix;
+
+impl<T, const ROWS: usize, const COLUMNS: usize> Distribution<Matrix<T, ROWS, COLUMNS>>
+    for StandardUniform
+where
+    StandardUniform: Distribution<T>,
+{
+    /// Generate a matrix with every cell sampled from [StandardUniform].
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Matrix<T, ROWS, COLUMNS> {
+        Matrix(core::array::from_fn(|_| {
+            core::array::from_fn(|_| self.sample(rng))
+        }))
+    }
+}
+
+impl<T, const ROWS: usize, const COLUMNS: usize> Matrix<T, ROWS, COLUMNS> {
+    /// Generate a random matrix.
+    ///
+    /// Same as `rng.random::<Matrix<T, ROWS, COLUMNS>>()`.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self
+    where
+        StandardUniform: Distribution<T>,
+    {
+        Distribution::<Self>::sample(&StandardUniform, rng)
+    }
+
+    /// Generate a random matrix with every cell in the provided range.
+    ///
+    /// ## Panics
+    /// Panics if the range is empty.
+    pub fn random_range<R, G>(rng: &mut R, range: G) -> Self
+    where
+        R: Rng + ?Sized,
+        T: SampleUniform,
+        G: SampleRange<T> + Clone,
+    {
+        assert!(!range.is_empty(), "cannot sample empty range");
+        Matrix(core::array::from_fn(|_| {
+            core::array::from_fn(|_| range.clone().sample_single(rng).unwrap())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    use crate::matrix::{Mat4f, Matrix};
+
+    #[test]
+    fn random_test() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let matrix: Mat4f = rng.random();
+        assert_eq!(
+            matrix.as_ref().map(|x| (0.0..1.0).contains(x)),
+            Matrix::new([true; 16])
+        );
+
+        let matrix = Matrix::<i32, 3, 3>::random_range(&mut rng, -2..=2);
+        assert_eq!(
+            matrix.as_ref().map(|x| (-2..=2).contains(x)),
+            Matrix::new([true; 9])
+        );
+        assert_ne!(Mat4f::random(&mut rng), Mat4f::random(&mut rng));
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(target_os = "macos")]
+pub mod macos;
 #[cfg(unix)]
 pub mod unix;
 #[cfg(windows)]
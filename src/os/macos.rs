@@ -0,0 +1,2 @@
+#[cfg(feature = "dirs")]
+pub mod dirs;
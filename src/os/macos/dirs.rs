@@ -0,0 +1,28 @@
+use std::{
+    env::{home_dir, temp_dir},
+    path::PathBuf,
+};
+
+use crate::fs::{DirRel, DirType};
+
+#[allow(deprecated)]
+pub fn dir(rel: DirRel, ty: DirType) -> Option<PathBuf> {
+    match (ty, rel) {
+        (DirType::Home, DirRel::User) => home_dir(),
+        (DirType::Home, DirRel::System) => Some("/Library".into()),
+        (DirType::Runtime, DirRel::User) => Some(temp_dir()),
+        (DirType::Runtime, DirRel::System) => Some("/var/run".into()),
+        (DirType::Share | DirType::State | DirType::Config, DirRel::User) => {
+            home_dir().map(|x| x.join("Library/Application Support"))
+        }
+        (DirType::Share | DirType::State | DirType::Config, DirRel::System) => {
+            Some("/Library/Application Support".into())
+        }
+        (DirType::Cache, DirRel::User) => home_dir().map(|x| x.join("Library/Caches")),
+        (DirType::Cache, DirRel::System) => Some("/Library/Caches".into()),
+        (DirType::Bin, DirRel::User) => home_dir().map(|x| x.join(".local/bin")),
+        (DirType::Bin, DirRel::System) => Some("/usr/local/bin".into()),
+        (DirType::Lib, DirRel::User) => home_dir().map(|x| x.join("Library/Frameworks")),
+        (DirType::Lib, DirRel::System) => Some("/Library/Frameworks".into()),
+    }
+}
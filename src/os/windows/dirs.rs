@@ -1,11 +1,125 @@
-use std::env::home_dir;
+use std::{
+    env::temp_dir,
+    ffi::{OsString, c_void},
+    os::windows::ffi::OsStringExt,
+    path::PathBuf,
+    ptr::null_mut,
+    slice,
+};
 
 use crate::fs::{DirRel, DirType};
 
-#[allow(deprecated)]
+#[repr(C)]
+struct Guid(u32, u16, u16, [u8; 8]);
+
+const FOLDERID_PROFILE: Guid = Guid(
+    0x5e6c858f,
+    0x0e22,
+    0x4760,
+    [0x9a, 0xfe, 0xea, 0x33, 0x17, 0xb6, 0x71, 0x73],
+);
+const FOLDERID_ROAMING_APP_DATA: Guid = Guid(
+    0x3eb685db,
+    0x65f9,
+    0x4cf6,
+    [0xa0, 0x3a, 0xe3, 0xef, 0x65, 0x72, 0x9f, 0x3d],
+);
+const FOLDERID_LOCAL_APP_DATA: Guid = Guid(
+    0xf1b32785,
+    0x6fba,
+    0x4fcf,
+    [0x9d, 0x55, 0x7b, 0x8e, 0x7f, 0x15, 0x70, 0x91],
+);
+const FOLDERID_PROGRAM_DATA: Guid = Guid(
+    0x62ab5d82,
+    0xfdc1,
+    0x4dc3,
+    [0xa9, 0xdd, 0x07, 0x0d, 0x1d, 0x49, 0x5d, 0x97],
+);
+const FOLDERID_PROGRAM_FILES: Guid = Guid(
+    0x905e63b6,
+    0xc1bf,
+    0x494e,
+    [0xb2, 0x9c, 0x65, 0xb7, 0x32, 0xd3, 0xd2, 0x1a],
+);
+const FOLDERID_USER_PROGRAM_FILES: Guid = Guid(
+    0x5cd7aee2,
+    0x2219,
+    0x4a67,
+    [0xb8, 0x5d, 0x6c, 0x9c, 0xe1, 0x56, 0x60, 0xcb],
+);
+const FOLDERID_USER_PROGRAM_FILES_COMMON: Guid = Guid(
+    0xbcbd3057,
+    0xca5c,
+    0x4622,
+    [0xb4, 0x2d, 0xbc, 0x56, 0xdb, 0x0a, 0xe5, 0x16],
+);
+const FOLDERID_SYSTEM: Guid = Guid(
+    0x1ac14e77,
+    0x02e7,
+    0x4e5d,
+    [0xb7, 0x44, 0x2e, 0xb1, 0xae, 0x51, 0x98, 0xb7],
+);
+const FOLDERID_WINDOWS: Guid = Guid(
+    0xf38bf404,
+    0x1d43,
+    0x42f2,
+    [0x93, 0x05, 0x67, 0xde, 0x0b, 0x28, 0xfc, 0x23],
+);
+
+/// Don't verify that the folder exists, since it may be created later.
+const KF_FLAG_DONT_VERIFY: u32 = 0x4000;
+
+#[link(name = "shell32")]
+unsafe extern "system" {
+    fn SHGetKnownFolderPath(
+        rfid: *const Guid,
+        flags: u32,
+        token: *mut c_void,
+        path: *mut *mut u16,
+    ) -> i32;
+}
+
+#[link(name = "ole32")]
+unsafe extern "system" {
+    fn CoTaskMemFree(pv: *mut c_void);
+}
+
+/// Get the path of a Known Folder for the current user.
+fn known_folder(id: Guid) -> Option<PathBuf> {
+    let mut path = null_mut();
+    let result = unsafe { SHGetKnownFolderPath(&id, KF_FLAG_DONT_VERIFY, null_mut(), &mut path) };
+    // Path must be freed even if the call fails.
+    let dir = (result >= 0 && !path.is_null()).then(|| unsafe {
+        let mut len = 0;
+        while path.add(len).read() != 0 {
+            len += 1;
+        }
+        PathBuf::from(OsString::from_wide(slice::from_raw_parts(path, len)))
+    });
+    unsafe { CoTaskMemFree(path.cast()) };
+    dir
+}
+
 pub fn dir(rel: DirRel, ty: DirType) -> Option<PathBuf> {
     match (ty, rel) {
-        (DirType::Home, DirRel::User) => home_dir(),
-        _ => todo!(),
+        (DirType::Home, DirRel::User) => known_folder(FOLDERID_PROFILE),
+        (DirType::Home, DirRel::System) => known_folder(FOLDERID_PROGRAM_FILES),
+        (DirType::Runtime, DirRel::User) => Some(temp_dir()),
+        (DirType::Runtime, DirRel::System) => {
+            known_folder(FOLDERID_WINDOWS).map(|x| x.join("Temp"))
+        }
+        (DirType::Share, DirRel::User) => known_folder(FOLDERID_ROAMING_APP_DATA),
+        (DirType::Share, DirRel::System) => known_folder(FOLDERID_PROGRAM_DATA),
+        (DirType::Cache, DirRel::User) => known_folder(FOLDERID_LOCAL_APP_DATA),
+        (DirType::Cache, DirRel::System) => known_folder(FOLDERID_PROGRAM_DATA),
+        (DirType::State, DirRel::User) => known_folder(FOLDERID_LOCAL_APP_DATA),
+        (DirType::State, DirRel::System) => known_folder(FOLDERID_PROGRAM_DATA),
+        (DirType::Bin, DirRel::User) => known_folder(FOLDERID_USER_PROGRAM_FILES),
+        (DirType::Bin, DirRel::System) => known_folder(FOLDERID_PROGRAM_FILES),
+        (DirType::Lib, DirRel::User) => known_folder(FOLDERID_USER_PROGRAM_FILES_COMMON),
+        (DirType::Lib, DirRel::System) => known_folder(FOLDERID_SYSTEM),
+        (DirType::Config, DirRel::User) => known_folder(FOLDERID_ROAMING_APP_DATA),
+        (DirType::Config, DirRel::System) => known_folder(FOLDERID_PROGRAM_DATA),
     }
 }
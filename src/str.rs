@@ -1,15 +1,36 @@
+#[cfg(feature = "std")]
 use std::io::{Cursor, Read};
 
+#[cfg(feature = "std")]
 use utf8::Utf8;
 
+pub use layout::{Align, PadTo, TruncateEllipsis, pad_to, truncate_ellipsis};
+#[cfg(feature = "std")]
+pub use utf8::{ValidationReport, validate_utf8};
+pub use width::{char_width, width};
+
+pub mod cmp;
+pub mod escape;
+mod layout;
+#[cfg(feature = "std")]
+pub mod os;
+#[cfg(feature = "alloc")]
+pub mod similarity;
+#[cfg(feature = "alloc")]
+pub mod small;
 pub mod stack;
+#[cfg(feature = "std")]
+pub mod utf16;
 pub mod utf8;
+mod width;
 
+#[cfg(feature = "std")]
 pub trait AsUtf8<'a> {
     type Inner: Read;
 
     fn as_utf8(&'a self) -> Utf8<Self::Inner>;
 }
+#[cfg(feature = "std")]
 impl<'a> AsUtf8<'a> for [u8] {
     type Inner = Cursor<&'a [u8]>;
 
@@ -18,11 +39,13 @@ impl<'a> AsUtf8<'a> for [u8] {
     }
 }
 
+#[cfg(feature = "std")]
 pub trait AsUtf8Mut<'a> {
     type Inner: Read;
 
     fn as_utf8(&'a mut self) -> Utf8<Self::Inner>;
 }
+#[cfg(feature = "std")]
 impl<'a, R> AsUtf8Mut<'a> for R
 where
     R: Read + 'a,
@@ -34,7 +57,7 @@ where
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
     use std::io::Cursor;
 
@@ -0,0 +1,153 @@
+//! String comparison.
+//!
+//! Besides functions for [slice::sort_by], there are wrappers that
+//! implement [Ord], so they can be used as keys in sorted collections.
+//!
+//! ```
+//! use libcommons::str::cmp::{Natural, natural_cmp};
+//! use std::collections::BTreeSet;
+//!
+//! let mut files = ["file10.txt", "file2.txt", "file1.txt"];
+//! files.sort_by(|a, b| natural_cmp(a, b));
+//! assert_eq!(files, ["file1.txt", "file2.txt", "file10.txt"]);
+//!
+//! let set: BTreeSet<_> = ["v1.10", "v1.9", "v1.1"].into_iter().map(Natural).collect();
+//! assert_eq!(set.first().unwrap().0, "v1.1");
+//! assert_eq!(set.last().unwrap().0, "v1.10");
+//! ```
+
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+/// Compare strings ignoring ASCII case.
+///
+/// ```
+/// use libcommons::str::cmp::cmp_ignore_ascii_case;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(cmp_ignore_ascii_case("Hello", "hELLO"), Ordering::Equal);
+/// assert_eq!(cmp_ignore_ascii_case("apple", "Banana"), Ordering::Less);
+/// ```
+pub fn cmp_ignore_ascii_case(a: &str, b: &str) -> Ordering {
+    a.bytes()
+        .map(|x| x.to_ascii_lowercase())
+        .cmp(b.bytes().map(|x| x.to_ascii_lowercase()))
+}
+
+/// Compare strings in natural order.
+///
+/// Runs of ASCII digits are compared as numbers of any length, the rest
+/// is compared like [str]s. Numbers that only differ in leading zeros
+/// are ordered by the number of zeros if the strings are otherwise
+/// equal.
+///
+/// ```
+/// use libcommons::str::cmp::natural_cmp;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+/// assert_eq!(natural_cmp("a99999999999999999999999", "a100000000000000000000000"), Ordering::Less);
+/// assert_eq!(natural_cmp("x01", "x1"), Ordering::Greater);
+/// assert_eq!(natural_cmp("x01y", "x1z"), Ordering::Less);
+/// ```
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    let mut zeros = Ordering::Equal;
+
+    while i < a.len() && j < b.len() {
+        if !a[i].is_ascii_digit() || !b[j].is_ascii_digit() {
+            match a[i].cmp(&b[j]) {
+                Ordering::Equal => (i, j) = (i + 1, j + 1),
+                x => return x,
+            }
+            continue;
+        }
+
+        let (x, x_zeros, x_end) = digit_run(a, i);
+        let (y, y_zeros, y_end) = digit_run(b, j);
+        match x.len().cmp(&y.len()).then_with(|| x.cmp(y)) {
+            Ordering::Equal => zeros = zeros.then(x_zeros.cmp(&y_zeros)),
+            x => return x,
+        }
+        (i, j) = (x_end, y_end);
+    }
+
+    (a.len() - i).cmp(&(b.len() - j)).then(zeros)
+}
+
+/// Split a run of digits starting at `start`.
+///
+/// Returns digits without leading zeros, number of zeros and the end of
+/// the run.
+fn digit_run(str: &[u8], start: usize) -> (&[u8], usize, usize) {
+    let len = str[start..]
+        .iter()
+        .take_while(|x| x.is_ascii_digit())
+        .count();
+    let zeros = str[start..start + len]
+        .iter()
+        .take_while(|x| **x == b'0')
+        .count();
+    (&str[start + zeros..start + len], zeros, start + len)
+}
+
+/// A string ordered ignoring ASCII case.
+///
+/// See [cmp_ignore_ascii_case].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IgnoreAsciiCase<S: AsRef<str>>(pub S);
+impl<S: AsRef<str>> PartialEq for IgnoreAsciiCase<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ref().eq_ignore_ascii_case(other.0.as_ref())
+    }
+}
+impl<S: AsRef<str>> Eq for IgnoreAsciiCase<S> {}
+impl<S: AsRef<str>> PartialOrd for IgnoreAsciiCase<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<S: AsRef<str>> Ord for IgnoreAsciiCase<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_ignore_ascii_case(self.0.as_ref(), other.0.as_ref())
+    }
+}
+impl<S: AsRef<str>> Hash for IgnoreAsciiCase<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.0.as_ref().bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+        state.write_u8(0xFF);
+    }
+}
+
+/// A string ordered in natural order.
+///
+/// See [natural_cmp].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Natural<S: AsRef<str>>(pub S);
+impl<S: AsRef<str>> PartialEq for Natural<S> {
+    /// Natural order only considers equal strings equal.
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ref() == other.0.as_ref()
+    }
+}
+impl<S: AsRef<str>> Eq for Natural<S> {}
+impl<S: AsRef<str>> PartialOrd for Natural<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<S: AsRef<str>> Ord for Natural<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        natural_cmp(self.0.as_ref(), other.0.as_ref())
+    }
+}
+impl<S: AsRef<str>> Hash for Natural<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_ref().hash(state)
+    }
+}
@@ -0,0 +1,243 @@
+//! String escaping.
+//!
+//! Escaping functions return wrappers implementing [Display], so they can
+//! be written anywhere without allocating.
+//!
+//! ```
+//! use libcommons::str::{escape, stack::StackString};
+//! use std::fmt::Write;
+//!
+//! let mut command = String::from("rm");
+//! write!(command, " {}", escape::shell("my file.txt")).unwrap();
+//! assert_eq!(command, "rm 'my file.txt'");
+//!
+//! let mut literal = StackString::<32>::new();
+//! write!(literal, "\"{}\"", escape::c("tab\there")).unwrap();
+//! assert_eq!(literal, r#""tab\there""#);
+//! ```
+
+use core::{
+    error::Error,
+    fmt::{self, Display, Write},
+    str::Chars,
+};
+
+/// Quote a string for POSIX shells.
+///
+/// Strings that only contain safe characters are left as is, others are
+/// wrapped in single quotes.
+///
+/// ```
+/// use libcommons::str::escape::shell;
+///
+/// assert_eq!(shell("file.txt").to_string(), "file.txt");
+/// assert_eq!(shell("it's").to_string(), r#"'it'\''s'"#);
+/// assert_eq!(shell("").to_string(), "''");
+/// ```
+pub fn shell(str: &str) -> Shell<'_> {
+    Shell(str)
+}
+
+/// A string quoted for POSIX shells.
+///
+/// Created with [shell].
+#[derive(Clone, Copy, Debug)]
+pub struct Shell<'a>(&'a str);
+impl Display for Shell<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let safe = |x: char| x.is_ascii_alphanumeric() || "_@%+=:,./-".contains(x);
+        if !self.0.is_empty() && self.0.chars().all(safe) {
+            return f.write_str(self.0);
+        }
+
+        f.write_char('\'')?;
+        for (i, part) in self.0.split('\'').enumerate() {
+            if i != 0 {
+                f.write_str(r"'\''")?;
+            }
+            f.write_str(part)?;
+        }
+        f.write_char('\'')
+    }
+}
+
+/// Escape a string for C string and character literals.
+///
+/// Surrounding quotes are not added. Non-ASCII characters are left as is,
+/// other non-printable characters are written as octal escapes.
+///
+/// ```
+/// use libcommons::str::escape::c;
+///
+/// assert_eq!(c("\"hi\"\n").to_string(), r#"\"hi\"\n"#);
+/// assert_eq!(c("\x01a").to_string(), r"\001a");
+/// ```
+pub fn c(str: &str) -> CEscape<'_> {
+    CEscape(str)
+}
+
+/// A string escaped for C literals.
+///
+/// Created with [c].
+#[derive(Clone, Copy, Debug)]
+pub struct CEscape<'a>(&'a str);
+impl Display for CEscape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for char in self.0.chars() {
+            match char {
+                '\n' => f.write_str(r"\n")?,
+                '\r' => f.write_str(r"\r")?,
+                '\t' => f.write_str(r"\t")?,
+                '\\' => f.write_str(r"\\")?,
+                '"' => f.write_str("\\\"")?,
+                '\'' => f.write_str(r"\'")?,
+                '\x07' => f.write_str(r"\a")?,
+                '\x08' => f.write_str(r"\b")?,
+                '\x0B' => f.write_str(r"\v")?,
+                '\x0C' => f.write_str(r"\f")?,
+                // Octal escapes have at most 3 digits, so unlike `\x` they
+                // can't swallow following characters.
+                x if x.is_ascii_control() => write!(f, "\\{:03o}", x as u32)?,
+                x => f.write_char(x)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Escape a string for JSON string literals.
+///
+/// Surrounding quotes are not added.
+///
+/// ```
+/// use libcommons::str::escape::json;
+///
+/// assert_eq!(json("say \"hi\"\n").to_string(), r#"say \"hi\"\n"#);
+/// assert_eq!(json("\x1B[0m").to_string(), r"\u001b[0m");
+/// ```
+pub fn json(str: &str) -> Json<'_> {
+    Json(str)
+}
+
+/// A string escaped for JSON.
+///
+/// Created with [json].
+#[derive(Clone, Copy, Debug)]
+pub struct Json<'a>(&'a str);
+impl Display for Json<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for char in self.0.chars() {
+            match char {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str(r"\\")?,
+                '\n' => f.write_str(r"\n")?,
+                '\r' => f.write_str(r"\r")?,
+                '\t' => f.write_str(r"\t")?,
+                '\x08' => f.write_str(r"\b")?,
+                '\x0C' => f.write_str(r"\f")?,
+                x if (x as u32) < 0x20 => write!(f, "\\u{:04x}", x as u32)?,
+                x => f.write_char(x)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An invalid escape sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidEscape {
+    /// Byte offset of the backslash starting the sequence.
+    pub offset: usize,
+}
+impl Display for InvalidEscape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid escape sequence at byte {}", self.offset)
+    }
+}
+impl Error for InvalidEscape {}
+
+/// Unescape contents of a C string literal.
+///
+/// Supports simple escapes, octal, `\x`, `\u` and `\U` escapes. Octal and
+/// `\x` escapes are taken as code points, so `\xFF` becomes `ÿ`.
+///
+/// ```
+/// use libcommons::str::escape::{InvalidEscape, unescape_c};
+///
+/// let str: String = unescape_c(r"tab\t\x41\101é").collect::<Result<_, _>>().unwrap();
+/// assert_eq!(str, "tab\tAAé");
+///
+/// let err = unescape_c(r"ok\q").collect::<Result<String, _>>().unwrap_err();
+/// assert_eq!(err, InvalidEscape { offset: 2 });
+/// ```
+pub fn unescape_c(str: &str) -> UnescapeC<'_> {
+    UnescapeC {
+        chars: str.chars(),
+        len: str.len(),
+    }
+}
+
+/// An iterator over unescaped characters of a C string literal.
+///
+/// Created with [unescape_c].
+#[derive(Clone, Debug)]
+pub struct UnescapeC<'a> {
+    chars: Chars<'a>,
+    len: usize,
+}
+impl UnescapeC<'_> {
+    /// Read at most `max` digits in `radix`, appending them to `value`.
+    ///
+    /// Returns the new value and the number of digits read.
+    fn digits(&mut self, radix: u32, max: usize, mut value: u32) -> (u32, usize) {
+        let mut count = 0;
+        while count < max {
+            let Some(digit) = self.chars.clone().next().and_then(|x| x.to_digit(radix)) else {
+                break;
+            };
+            self.chars.next();
+            value = value * radix + digit;
+            count += 1;
+        }
+        (value, count)
+    }
+
+    /// Read exactly `len` hex digits as a code point.
+    fn code_point(&mut self, len: usize) -> Option<char> {
+        match self.digits(16, len, 0) {
+            (value, count) if count == len => char::from_u32(value),
+            _ => None,
+        }
+    }
+}
+impl Iterator for UnescapeC<'_> {
+    type Item = Result<char, InvalidEscape>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.len - self.chars.as_str().len();
+        let char = self.chars.next()?;
+        if char != '\\' {
+            return Some(Ok(char));
+        }
+
+        let char = match self.chars.next() {
+            Some('n') => Some('\n'),
+            Some('r') => Some('\r'),
+            Some('t') => Some('\t'),
+            Some('a') => Some('\x07'),
+            Some('b') => Some('\x08'),
+            Some('v') => Some('\x0B'),
+            Some('f') => Some('\x0C'),
+            Some(x @ ('\\' | '"' | '\'' | '?')) => Some(x),
+            Some(x @ '0'..='7') => char::from_u32(self.digits(8, 2, x as u32 - '0' as u32).0),
+            Some('x') => match self.digits(16, 2, 0) {
+                (_, 0) => None,
+                (value, _) => char::from_u32(value),
+            },
+            Some('u') => self.code_point(4),
+            Some('U') => self.code_point(8),
+            _ => None,
+        };
+        Some(char.ok_or(InvalidEscape { offset }))
+    }
+}
@@ -0,0 +1,116 @@
+//! Fitting strings into fixed-width terminal cells.
+//!
+//! Helpers return wrappers implementing [Display], so they can be written
+//! into a [StackString](super::stack::StackString) or any [fmt::Write]
+//! without allocating.
+
+use core::fmt::{self, Display, Write};
+
+use super::width::{char_width, width as str_width};
+
+/// Limit a string to `max` terminal cells, replacing the end with `…`
+/// if it doesn't fit.
+///
+/// ```
+/// use libcommons::str::truncate_ellipsis;
+///
+/// assert_eq!(truncate_ellipsis("short", 8).to_string(), "short");
+/// assert_eq!(truncate_ellipsis("way too long", 8).to_string(), "way too…");
+/// assert_eq!(truncate_ellipsis("ёжик", 3).to_string(), "ёж…");
+/// assert_eq!(truncate_ellipsis("日本語", 4).to_string(), "日…");
+/// ```
+pub fn truncate_ellipsis(str: &str, max: usize) -> TruncateEllipsis<'_> {
+    TruncateEllipsis { str, max }
+}
+
+/// A string limited to a number of terminal cells.
+///
+/// Created with [truncate_ellipsis].
+#[derive(Clone, Copy, Debug)]
+pub struct TruncateEllipsis<'a> {
+    str: &'a str,
+    max: usize,
+}
+impl Display for TruncateEllipsis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if str_width(self.str) <= self.max {
+            return f.write_str(self.str);
+        }
+        if self.max == 0 {
+            return Ok(());
+        }
+
+        let mut width = 0;
+        for (i, char) in self.str.char_indices() {
+            width += char_width(char);
+            if width > self.max - 1 {
+                f.write_str(&self.str[..i])?;
+                break;
+            }
+        }
+        f.write_char('…')
+    }
+}
+
+/// Alignment of a string within padding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    /// Center the string, leaning left when padding can't be split
+    /// evenly.
+    Center,
+}
+
+/// Pad a string with spaces to take at least `width` terminal cells.
+///
+/// Unlike formatting with `{:width$}`, this counts terminal cells rather
+/// than characters. Longer strings are left as is.
+///
+/// ```
+/// use libcommons::str::{Align, pad_to, stack::StackString, truncate_ellipsis};
+///
+/// let mut line = StackString::<32>::new();
+/// write!(line, "{}|", pad_to("libcommons", 8, Align::Left)).unwrap();
+/// write!(line, "{}", truncate_ellipsis("libcommons", 8)).unwrap();
+/// assert_eq!(line, "libcommons|libcomm…");
+///
+/// assert_eq!(pad_to("ab", 5, Align::Left).to_string(), "ab   ");
+/// assert_eq!(pad_to("ab", 5, Align::Right).to_string(), "   ab");
+/// assert_eq!(pad_to("ab", 5, Align::Center).to_string(), " ab  ");
+/// assert_eq!(pad_to("abcdef", 5, Align::Center).to_string(), "abcdef");
+/// assert_eq!(pad_to("日本", 5, Align::Left).to_string(), "日本 ");
+/// ```
+pub fn pad_to(str: &str, width: usize, align: Align) -> PadTo<'_> {
+    PadTo { str, width, align }
+}
+
+/// A string padded to a number of terminal cells.
+///
+/// Created with [pad_to].
+#[derive(Clone, Copy, Debug)]
+pub struct PadTo<'a> {
+    str: &'a str,
+    width: usize,
+    align: Align,
+}
+impl Display for PadTo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pad = self.width.saturating_sub(str_width(self.str));
+        let left = match self.align {
+            Align::Left => 0,
+            Align::Right => pad,
+            Align::Center => pad / 2,
+        };
+
+        for _ in 0..left {
+            f.write_char(' ')?;
+        }
+        f.write_str(self.str)?;
+        for _ in left..pad {
+            f.write_char(' ')?;
+        }
+        Ok(())
+    }
+}
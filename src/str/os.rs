@@ -0,0 +1,118 @@
+//! Converting platform strings.
+//!
+//! Helpers accept anything that can be viewed as an [OsStr], including
+//! [Path](std::path::Path) and [PathBuf](std::path::PathBuf).
+//!
+//! Strict variants fail if the string is not valid Unicode. Lossy variants
+//! replace invalid parts with [char::REPLACEMENT_CHARACTER]: invalid byte
+//! sequences on Unix and unpaired surrogates on Windows.
+//!
+//! ```
+//! use libcommons::str::{os, stack::StackString};
+//! use std::path::Path;
+//!
+//! let path = Path::new("/home/user/file.txt");
+//! assert_eq!(os::to_str(path).unwrap(), "/home/user/file.txt");
+//!
+//! let name: StackString<16> = os::to_stack_lossy(path.file_name().unwrap()).unwrap();
+//! assert_eq!(name, "file.txt");
+//! ```
+
+use std::{borrow::Cow, error::Error, ffi::OsStr, fmt::Display};
+
+use super::stack::{PushError, StackString};
+
+/// A platform string is not valid Unicode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotUnicode;
+impl Display for NotUnicode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("string is not valid unicode")
+    }
+}
+impl Error for NotUnicode {}
+
+/// Failed to convert a platform string into [StackString].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToStackError {
+    /// The string is not valid Unicode.
+    NotUnicode,
+    /// The string doesn't fit into the buffer.
+    Full,
+}
+impl Display for ToStackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotUnicode => Display::fmt(&NotUnicode, f),
+            Self::Full => Display::fmt(&PushError, f),
+        }
+    }
+}
+impl Error for ToStackError {}
+
+/// Get a platform string as an [str].
+pub fn to_str<S: AsRef<OsStr> + ?Sized>(str: &S) -> Result<&str, NotUnicode> {
+    str.as_ref().to_str().ok_or(NotUnicode)
+}
+
+/// Get a platform string as an [str], replacing invalid parts.
+///
+/// Only allocates if the string is not valid Unicode.
+pub fn to_str_lossy<S: AsRef<OsStr> + ?Sized>(str: &S) -> Cow<'_, str> {
+    str.as_ref().to_string_lossy()
+}
+
+/// Copy a platform string into a [StackString].
+pub fn to_stack<const CAP: usize, S: AsRef<OsStr> + ?Sized>(
+    str: &S,
+) -> Result<StackString<CAP>, ToStackError> {
+    let str = to_str(str).map_err(|_| ToStackError::NotUnicode)?;
+    str.parse().map_err(|_| ToStackError::Full)
+}
+
+/// Copy a platform string into a [StackString], replacing invalid parts.
+///
+/// Unlike `to_str_lossy(str).parse()`, this never allocates.
+///
+/// ```
+/// use libcommons::str::os::to_stack_lossy;
+///
+/// # #[cfg(unix)] {
+/// use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+///
+/// let str = OsStr::from_bytes(b"bad\xFFname");
+/// assert_eq!(to_stack_lossy::<16, _>(str).unwrap(), "bad\u{FFFD}name");
+/// # }
+/// # #[cfg(windows)] {
+/// use std::{ffi::OsString, os::windows::ffi::OsStringExt};
+///
+/// let str = OsString::from_wide(&[0x62, 0xD800, 0x6E]);
+/// assert_eq!(to_stack_lossy::<16, _>(&str).unwrap(), "b\u{FFFD}n");
+/// # }
+/// ```
+pub fn to_stack_lossy<const CAP: usize, S: AsRef<OsStr> + ?Sized>(
+    str: &S,
+) -> Result<StackString<CAP>, PushError> {
+    let str = str.as_ref();
+    if let Some(str) = str.to_str() {
+        return str.parse();
+    }
+
+    let mut st = StackString::new();
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+
+        for char in char::decode_utf16(str.encode_wide()) {
+            st.push(char.unwrap_or(char::REPLACEMENT_CHARACTER))?;
+        }
+    }
+    #[cfg(not(windows))]
+    for chunk in str.as_encoded_bytes().utf8_chunks() {
+        st.push_str(chunk.valid())?;
+        if !chunk.invalid().is_empty() {
+            st.push(char::REPLACEMENT_CHARACTER)?;
+        }
+    }
+    Ok(st)
+}
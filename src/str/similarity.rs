@@ -0,0 +1,135 @@
+//! String similarity metrics.
+//!
+//! All metrics work on [char]s rather than bytes. Distances only keep
+//! the last rows of the edit matrix, which live on the stack for short
+//! strings.
+//!
+//! ```
+//! use libcommons::str::similarity::similarity;
+//!
+//! let commands = ["build", "check", "clean", "test"];
+//! let best = commands
+//!     .iter()
+//!     .max_by(|a, b| similarity("chek", a).total_cmp(&similarity("chek", b)))
+//!     .unwrap();
+//! assert_eq!(*best, "check");
+//! ```
+
+use alloc::vec;
+
+/// Number of cells kept on the stack.
+const STACK_CELLS: usize = 192;
+
+/// Run `f` with a zeroed buffer of `len` cells.
+fn with_buffer<R>(len: usize, f: impl FnOnce(&mut [usize]) -> R) -> R {
+    if len <= STACK_CELLS {
+        f(&mut [0; STACK_CELLS][..len])
+    } else {
+        f(&mut vec![0; len])
+    }
+}
+
+/// Order strings so the second one has less characters.
+fn by_length<'a>(a: &'a str, b: &'a str) -> (&'a str, &'a str, usize) {
+    let (a_len, b_len) = (a.chars().count(), b.chars().count());
+    if a_len < b_len {
+        (b, a, a_len)
+    } else {
+        (a, b, b_len)
+    }
+}
+
+/// Get the Levenshtein distance between two strings.
+///
+/// This is the minimum number of insertions, deletions and substitutions
+/// required to turn one string into the other.
+///
+/// ```
+/// use libcommons::str::similarity::levenshtein;
+///
+/// assert_eq!(levenshtein("kitten", "sitting"), 3);
+/// assert_eq!(levenshtein("", "abc"), 3);
+/// assert_eq!(levenshtein("ab", "ba"), 2);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let (a, b, len) = by_length(a, b);
+    with_buffer(len + 1, |row| {
+        for (i, x) in row.iter_mut().enumerate() {
+            *x = i;
+        }
+        for (i, ca) in a.chars().enumerate() {
+            let mut diag = row[0];
+            row[0] = i + 1;
+            for (j, cb) in b.chars().enumerate() {
+                let cost = diag + (ca != cb) as usize;
+                diag = row[j + 1];
+                row[j + 1] = cost.min(row[j] + 1).min(diag + 1);
+            }
+        }
+        row[len]
+    })
+}
+
+/// Get the Damerau-Levenshtein distance between two strings.
+///
+/// Like [levenshtein], but swapping two adjacent characters counts as a
+/// single edit. This is the optimal string alignment variant, so a
+/// substring can't be edited more than once.
+///
+/// ```
+/// use libcommons::str::similarity::damerau_levenshtein;
+///
+/// assert_eq!(damerau_levenshtein("ab", "ba"), 1);
+/// assert_eq!(damerau_levenshtein("recieve", "receive"), 1);
+/// assert_eq!(damerau_levenshtein("ca", "abc"), 3);
+/// ```
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let (a, b, len) = by_length(a, b);
+    with_buffer((len + 1) * 3, |rows| {
+        let (mut prev2, rest) = rows.split_at_mut(len + 1);
+        let (mut prev, mut row) = rest.split_at_mut(len + 1);
+        for (i, x) in prev.iter_mut().enumerate() {
+            *x = i;
+        }
+
+        let mut last_a = None;
+        for (i, ca) in a.chars().enumerate() {
+            row[0] = i + 1;
+            let mut last_b = None;
+            for (j, cb) in b.chars().enumerate() {
+                let mut cost = (prev[j] + (ca != cb) as usize)
+                    .min(row[j] + 1)
+                    .min(prev[j + 1] + 1);
+                if last_a == Some(cb) && last_b == Some(ca) {
+                    cost = cost.min(prev2[j - 1] + 1);
+                }
+                row[j + 1] = cost;
+                last_b = Some(cb);
+            }
+            last_a = Some(ca);
+            (prev2, prev, row) = (prev, row, prev2);
+        }
+        prev[len]
+    })
+}
+
+/// Get similarity of two strings.
+///
+/// Returns a number from `0.0` for completely different strings to `1.0`
+/// for equal ones, based on [damerau_levenshtein] distance relative to
+/// the length of the longer string.
+///
+/// ```
+/// use libcommons::str::similarity::similarity;
+///
+/// assert_eq!(similarity("same", "same"), 1.0);
+/// assert_eq!(similarity("abc", "xyz"), 0.0);
+/// assert_eq!(similarity("test", "tset"), 0.75);
+/// ```
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let len = a.chars().count().max(b.chars().count());
+    if len == 0 {
+        return 1.0;
+    }
+    1.0 - damerau_levenshtein(a, b) as f64 / len as f64
+}
@@ -0,0 +1,264 @@
+use alloc::string::String;
+use core::{
+    borrow::{Borrow, BorrowMut},
+    convert::Infallible,
+    fmt::{self, Display},
+    hash::Hash,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+use super::stack::StackString;
+
+/// A string that stores up to `N` bytes inline.
+///
+/// Works like [StackString], but instead of returning
+/// [PushError](super::stack::PushError) on overflow, moves its contents
+/// into a heap-allocated [String].
+///
+/// ```
+/// use libcommons::str::small::SmallString;
+///
+/// let mut s = SmallString::<8>::new();
+/// s.push_str("hello");
+/// assert!(s.is_inline());
+///
+/// s.push_str(", world");
+/// assert!(!s.is_inline());
+/// assert_eq!(s, "hello, world");
+/// ```
+#[derive(Clone)]
+pub struct SmallString<const N: usize>(Repr<N>);
+
+#[derive(Clone)]
+enum Repr<const N: usize> {
+    Inline(StackString<N>),
+    Heap(String),
+}
+
+impl<const N: usize> SmallString<N> {
+    /// Create an empty string.
+    pub const fn new() -> Self {
+        Self(Repr::Inline(StackString::new()))
+    }
+
+    /// Get the length of this string in bytes.
+    pub fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    /// Check if this string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get this string's capacity.
+    ///
+    /// Returns `N` while the string is inline.
+    pub fn capacity(&self) -> usize {
+        match &self.0 {
+            Repr::Inline(x) => x.capacity(),
+            Repr::Heap(x) => x.capacity(),
+        }
+    }
+
+    /// Check if this string is stored inline.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline(_))
+    }
+
+    /// Append a [char].
+    pub fn push(&mut self, char: char) {
+        self.push_str(char.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Append an [str].
+    ///
+    /// If it doesn't fit inline, the string is moved to the heap.
+    pub fn push_str(&mut self, str: &str) {
+        match &mut self.0 {
+            Repr::Inline(x) => {
+                if x.push_str(str).is_err() {
+                    let mut heap = String::with_capacity(x.len() + str.len());
+                    heap.push_str(x);
+                    heap.push_str(str);
+                    self.0 = Repr::Heap(heap);
+                }
+            }
+            Repr::Heap(x) => x.push_str(str),
+        }
+    }
+
+    /// Remove all contents of this string.
+    ///
+    /// Heap allocation is kept.
+    pub fn clear(&mut self) {
+        match &mut self.0 {
+            Repr::Inline(x) => *x = StackString::new(),
+            Repr::Heap(x) => x.clear(),
+        }
+    }
+
+    /// Get this string as an [str].
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Inline(x) => x.as_str(),
+            Repr::Heap(x) => x.as_str(),
+        }
+    }
+
+    /// Get this string as a mutable [str].
+    pub fn as_str_mut(&mut self) -> &mut str {
+        match &mut self.0 {
+            Repr::Inline(x) => x.as_str_mut(),
+            Repr::Heap(x) => x.as_mut_str(),
+        }
+    }
+
+    /// Convert this string into a [String].
+    ///
+    /// Doesn't allocate if the string is already on the heap.
+    pub fn into_string(self) -> String {
+        match self.0 {
+            Repr::Inline(x) => String::from(x.as_str()),
+            Repr::Heap(x) => x,
+        }
+    }
+}
+impl<const N: usize> Hash for SmallString<N> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+impl<const N: usize> Default for SmallString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize> Deref for SmallString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+impl<const N: usize> DerefMut for SmallString<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_str_mut()
+    }
+}
+impl<const N: usize> AsRef<[u8]> for SmallString<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+impl<const N: usize> AsRef<str> for SmallString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl<const N: usize> AsMut<str> for SmallString<N> {
+    fn as_mut(&mut self) -> &mut str {
+        self.as_str_mut()
+    }
+}
+impl<const N: usize> Borrow<str> for SmallString<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+impl<const N: usize> BorrowMut<str> for SmallString<N> {
+    fn borrow_mut(&mut self) -> &mut str {
+        self.as_str_mut()
+    }
+}
+impl<const N: usize> From<SmallString<N>> for String {
+    fn from(value: SmallString<N>) -> Self {
+        value.into_string()
+    }
+}
+impl<const N: usize> From<&'_ str> for SmallString<N> {
+    fn from(value: &str) -> Self {
+        let mut st = Self::new();
+        st.push_str(value);
+        st
+    }
+}
+impl<const N: usize> From<String> for SmallString<N> {
+    /// Wrap a [String].
+    ///
+    /// The string stays on the heap.
+    fn from(value: String) -> Self {
+        Self(Repr::Heap(value))
+    }
+}
+impl<const N: usize> From<StackString<N>> for SmallString<N> {
+    fn from(value: StackString<N>) -> Self {
+        Self(Repr::Inline(value))
+    }
+}
+impl<const N: usize> FromStr for SmallString<N> {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+impl<const N: usize> Extend<char> for SmallString<N> {
+    fn extend<T: IntoIterator<Item = char>>(&mut self, iter: T) {
+        for char in iter {
+            self.push(char);
+        }
+    }
+}
+impl<'a, const N: usize> Extend<&'a str> for SmallString<N> {
+    fn extend<T: IntoIterator<Item = &'a str>>(&mut self, iter: T) {
+        for str in iter {
+            self.push_str(str);
+        }
+    }
+}
+impl<const N: usize> FromIterator<char> for SmallString<N> {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        let mut st = Self::new();
+        st.extend(iter);
+        st
+    }
+}
+impl<const N: usize> fmt::Write for SmallString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+impl<const N: usize> PartialEq for SmallString<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+impl<const N: usize> Eq for SmallString<N> {}
+impl<const N: usize> PartialEq<&'_ str> for SmallString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+impl<const N: usize> PartialEq<str> for SmallString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl<const N: usize> PartialEq<String> for SmallString<N> {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+impl<const N: usize> Display for SmallString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+impl<const N: usize> fmt::Debug for SmallString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
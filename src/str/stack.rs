@@ -1,9 +1,12 @@
-use std::{
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{
     borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
     error::Error,
-    fmt::{Arguments, Display},
+    ffi::{CStr, c_char},
+    fmt::{self, Arguments, Display},
     hash::Hash,
-    io::Write,
     ops::{Deref, DerefMut},
     str::FromStr,
 };
@@ -15,27 +18,56 @@ use std::{
 #[derive(Debug)]
 pub struct PushError;
 impl Display for PushError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("ran out of space in buffer")
     }
 }
 impl Error for PushError {}
 
-struct Writer<'a, const CAPACITY: usize>(&'a mut StackString<CAPACITY>);
-impl<const CAPACITY: usize> Write for Writer<'_, CAPACITY> {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let len = buf.len().min(CAPACITY - self.0.len);
-        self.0.buf[self.0.len..][0..len].copy_from_slice(&buf[0..len]);
-        self.0.len += len;
-        Ok(len)
-    }
+/// Format a [StackString].
+///
+/// Like [format], but returns `Result<StackString<CAP>, PushError>`.
+///
+/// ```
+/// use libcommons::stackfmt;
+///
+/// let name = "world";
+/// assert_eq!(stackfmt!(16, "hello, {name}!").unwrap(), "hello, world!");
+/// assert!(stackfmt!(4, "{}", 12345).is_err());
+/// ```
+#[macro_export]
+macro_rules! stackfmt {
+    ($cap:expr, $($arg:tt)*) => {{
+        let mut st = $crate::str::stack::StackString::<{ $cap }>::new();
+        st.write_fmt(::core::format_args!($($arg)*)).map(|_| st)
+    }};
+}
 
-    fn flush(&mut self) -> std::io::Result<()> {
-        Ok(())
+struct Writer<'a, const CAPACITY: usize>(&'a mut StackString<CAPACITY>);
+impl<const CAPACITY: usize> fmt::Write for Writer<'_, CAPACITY> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.push_str(s).map_err(|_| fmt::Error)
     }
 }
 
 /// Stack-allocated string.
+///
+/// Strings can be compared with each other regardless of capacity, as
+/// well as with [str] and [String].
+///
+/// ```
+/// use libcommons::str::stack::StackString;
+/// use std::collections::BTreeSet;
+///
+/// let a: StackString<8> = "apple".parse().unwrap();
+/// let b: StackString<16> = "apple".parse().unwrap();
+/// assert_eq!(a, b);
+/// assert!(a < "banana");
+/// assert_eq!("apple", a);
+///
+/// let set: BTreeSet<StackString<8>> = ["b", "c", "a"].iter().map(|x| x.parse().unwrap()).collect();
+/// assert_eq!(set.first().unwrap(), "a");
+/// ```
 #[derive(Clone, Copy)]
 pub struct StackString<const CAPACITY: usize> {
     buf: [u8; CAPACITY],
@@ -60,6 +92,21 @@ impl<const CAPACITY: usize> StackString<CAPACITY> {
         self.len == 0
     }
 
+    /// Get number of terminal cells taken by this string.
+    ///
+    /// See [width](super::width).
+    ///
+    /// ```
+    /// use libcommons::str::stack::StackString;
+    ///
+    /// let str: StackString<16> = "日本語".parse().unwrap();
+    /// assert_eq!(str.len(), 9);
+    /// assert_eq!(str.display_width(), 6);
+    /// ```
+    pub fn display_width(&self) -> usize {
+        super::width(self)
+    }
+
     /// Get this string's capacity.
     ///
     /// Will always return the value provided as a generic argument.
@@ -74,7 +121,7 @@ impl<const CAPACITY: usize> StackString<CAPACITY> {
     pub fn write_fmt(&mut self, fmt: Arguments<'_>) -> Result<(), PushError> {
         let len = self.len;
         let mut writer = Writer(self);
-        match writer.write_fmt(fmt).map_err(|_| PushError) {
+        match fmt::Write::write_fmt(&mut writer, fmt).map_err(|_| PushError) {
             Ok(x) => Ok(x),
             Err(why) => {
                 self.len = len;
@@ -96,15 +143,118 @@ impl<const CAPACITY: usize> StackString<CAPACITY> {
     /// If there isn't enough empty space in the buffer, [PushError] is
     /// returned and string is reverted to its length before the call.
     pub fn push_str(&mut self, str: &str) -> Result<(), PushError> {
-        let len = self.len;
-        let mut writer = Writer(self);
-        match writer.write_all(str.as_bytes()).map_err(|_| PushError) {
-            Ok(x) => Ok(x),
-            Err(why) => {
-                self.len = len;
-                Err(why)
-            }
+        if str.len() > CAPACITY - self.len {
+            return Err(PushError);
+        }
+        self.buf[self.len..][..str.len()].copy_from_slice(str.as_bytes());
+        self.len += str.len();
+        Ok(())
+    }
+
+    /// Replace all matches of `from` with `to`.
+    ///
+    /// Like [str::replace], but produces a new [StackString] instead of
+    /// allocating. Returns [PushError] if the result doesn't fit.
+    ///
+    /// ```
+    /// use libcommons::str::stack::StackString;
+    ///
+    /// let s: StackString<16> = "one two one".parse().unwrap();
+    /// assert_eq!(s.replace("one", "1").unwrap(), "1 two 1");
+    /// assert_eq!(s.replacen("one", "1", 1).unwrap(), "1 two one");
+    /// assert!(s.replace("one", "three ").is_err());
+    /// ```
+    pub fn replace(&self, from: &str, to: &str) -> Result<Self, PushError> {
+        self.replacen(from, to, usize::MAX)
+    }
+
+    /// Replace first `count` matches of `from` with `to`.
+    ///
+    /// Like [str::replacen], but produces a new [StackString] instead of
+    /// allocating. Returns [PushError] if the result doesn't fit.
+    pub fn replacen(&self, from: &str, to: &str, count: usize) -> Result<Self, PushError> {
+        let mut st = Self::new();
+        let mut last = 0;
+        for (i, x) in self.match_indices(from).take(count) {
+            st.push_str(&self[last..i])?;
+            st.push_str(to)?;
+            last = i + x.len();
+        }
+        st.push_str(&self[last..])?;
+        Ok(st)
+    }
+
+    /// Get a lowercase copy of this string.
+    ///
+    /// Like [str::to_lowercase], but produces a new [StackString] instead of
+    /// allocating. Returns [PushError] if the result doesn't fit, which may
+    /// happen as some characters change length.
+    ///
+    /// Characters are mapped one by one, so unlike [str::to_lowercase], a
+    /// final `Σ` becomes `σ` rather than `ς`.
+    ///
+    /// ```
+    /// use libcommons::str::stack::StackString;
+    ///
+    /// let s: StackString<16> = "Hello, World".parse().unwrap();
+    /// assert_eq!(s.to_lowercase().unwrap(), "hello, world");
+    /// assert_eq!(s.to_uppercase().unwrap(), "HELLO, WORLD");
+    /// ```
+    pub fn to_lowercase(&self) -> Result<Self, PushError> {
+        let mut st = Self::new();
+        for char in self.chars().flat_map(char::to_lowercase) {
+            st.push(char)?;
+        }
+        Ok(st)
+    }
+
+    /// Get an uppercase copy of this string.
+    ///
+    /// Like [str::to_uppercase], but produces a new [StackString] instead of
+    /// allocating. Returns [PushError] if the result doesn't fit, which may
+    /// happen as some characters change length.
+    pub fn to_uppercase(&self) -> Result<Self, PushError> {
+        let mut st = Self::new();
+        for char in self.chars().flat_map(char::to_uppercase) {
+            st.push(char)?;
         }
+        Ok(st)
+    }
+
+    /// Convert this string to ASCII upper case in place.
+    ///
+    /// Non-ASCII characters are not changed.
+    pub fn make_ascii_uppercase(&mut self) {
+        self.buf[..self.len].make_ascii_uppercase();
+    }
+
+    /// Convert this string to ASCII lower case in place.
+    ///
+    /// Non-ASCII characters are not changed.
+    pub fn make_ascii_lowercase(&mut self) {
+        self.buf[..self.len].make_ascii_lowercase();
+    }
+
+    /// Remove leading and trailing whitespace in place.
+    ///
+    /// ```
+    /// use libcommons::str::stack::StackString;
+    ///
+    /// let mut s: StackString<16> = "  Hello!\n".parse().unwrap();
+    /// s.trim_in_place();
+    /// s.make_ascii_uppercase();
+    /// assert_eq!(s, "HELLO!");
+    /// ```
+    pub fn trim_in_place(&mut self) {
+        self.trim_end_in_place();
+        let start = self.len - self.trim_start().len();
+        self.buf.copy_within(start..self.len, 0);
+        self.len -= start;
+    }
+
+    /// Remove trailing whitespace in place.
+    pub fn trim_end_in_place(&mut self) {
+        self.len = self.trim_end().len();
     }
 
     /// Get underlying bytes as an [str].
@@ -136,7 +286,7 @@ impl<const CAPACITY: usize> StackString<CAPACITY> {
     }
 }
 impl<const CAPACITY: usize> Hash for StackString<CAPACITY> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.as_str().hash(state)
     }
 }
@@ -182,6 +332,7 @@ impl<const CAPACITY: usize> Borrow<str> for StackString<CAPACITY> {
         self.as_str()
     }
 }
+#[cfg(feature = "alloc")]
 impl<const CAPACITY: usize> From<StackString<CAPACITY>> for String {
     fn from(value: StackString<CAPACITY>) -> Self {
         String::from_str(value.as_str()).unwrap()
@@ -196,6 +347,7 @@ impl<const CAPACITY: usize> FromStr for StackString<CAPACITY> {
         Ok(st)
     }
 }
+#[cfg(feature = "alloc")]
 impl<const CAPACITY: usize> TryFrom<String> for StackString<CAPACITY> {
     type Error = PushError;
 
@@ -214,43 +366,307 @@ impl<const CAPACITY: usize> TryFrom<&'_ str> for StackString<CAPACITY> {
         Ok(st)
     }
 }
-impl<const CAPACITY: usize> PartialEq for StackString<CAPACITY> {
-    fn eq(&self, other: &Self) -> bool {
+impl<const A: usize, const B: usize> PartialEq<StackString<B>> for StackString<A> {
+    fn eq(&self, other: &StackString<B>) -> bool {
         self.as_str() == other.as_str()
     }
 }
 impl<const CAPACITY: usize> Eq for StackString<CAPACITY> {}
-impl<const CAPACITY: usize> PartialEq<&'_ str> for StackString<CAPACITY> {
-    fn eq(&self, other: &&str) -> bool {
-        self.as_str() == *other
+impl<const A: usize, const B: usize> PartialOrd<StackString<B>> for StackString<A> {
+    fn partial_cmp(&self, other: &StackString<B>) -> Option<Ordering> {
+        self.as_str().partial_cmp(other.as_str())
     }
 }
-impl<const CAPACITY: usize> PartialEq<str> for StackString<CAPACITY> {
-    fn eq(&self, other: &str) -> bool {
-        self.as_str() == other
+impl<const CAPACITY: usize> Ord for StackString<CAPACITY> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
     }
 }
-impl<const CAPACITY: usize> PartialEq<String> for StackString<CAPACITY> {
-    fn eq(&self, other: &String) -> bool {
-        self.as_str() == other
-    }
+macro_rules! impl_cmp_str {
+    ($($(#[$attr:meta])* $ty:ty),* $(,)?) => {$(
+        $(#[$attr])*
+        impl<const CAPACITY: usize> PartialEq<$ty> for StackString<CAPACITY> {
+            fn eq(&self, other: &$ty) -> bool {
+                self.as_str() == AsRef::<str>::as_ref(other)
+            }
+        }
+        $(#[$attr])*
+        impl<const CAPACITY: usize> PartialEq<StackString<CAPACITY>> for $ty {
+            fn eq(&self, other: &StackString<CAPACITY>) -> bool {
+                AsRef::<str>::as_ref(self) == other.as_str()
+            }
+        }
+        $(#[$attr])*
+        impl<const CAPACITY: usize> PartialOrd<$ty> for StackString<CAPACITY> {
+            fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+                self.as_str().partial_cmp(AsRef::<str>::as_ref(other))
+            }
+        }
+        $(#[$attr])*
+        impl<const CAPACITY: usize> PartialOrd<StackString<CAPACITY>> for $ty {
+            fn partial_cmp(&self, other: &StackString<CAPACITY>) -> Option<Ordering> {
+                AsRef::<str>::as_ref(self).partial_cmp(other.as_str())
+            }
+        }
+    )*};
+}
+impl_cmp_str! {
+    str,
+    &'_ str,
+    #[cfg(feature = "alloc")]
+    String,
 }
-impl<const CAPACITY: usize> Write for StackString<CAPACITY> {
+#[cfg(feature = "std")]
+impl<const CAPACITY: usize> std::io::Write for StackString<CAPACITY> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        Writer(self).write(buf)
+        let len = buf.len().min(CAPACITY - self.len);
+        self.buf[self.len..][0..len].copy_from_slice(&buf[0..len]);
+        self.len += len;
+        Ok(len)
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
-        Writer(self).flush()
+        Ok(())
+    }
+}
+impl<const CAPACITY: usize> Display for StackString<CAPACITY> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+impl<const CAPACITY: usize> fmt::Debug for StackString<CAPACITY> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Failed to push data into [StackCString].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CPushError {
+    /// Data doesn't fit into the buffer.
+    Full,
+    /// Data contains a NUL byte.
+    Nul,
+}
+impl Display for CPushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full => f.write_str("ran out of space in buffer"),
+            Self::Nul => f.write_str("data contains a nul byte"),
+        }
+    }
+}
+impl Error for CPushError {}
+
+/// Stack-allocated NUL-terminated string.
+///
+/// Keeps a NUL byte after its contents, so it can be passed to C APIs
+/// without allocating a [CString](alloc::ffi::CString). One byte of the
+/// buffer is taken by the terminator, so it can hold up to
+/// `CAPACITY - 1` bytes.
+///
+/// Unlike [StackString], contents are not required to be UTF-8.
+///
+/// ```
+/// use libcommons::str::stack::{CPushError, StackCString};
+///
+/// let mut path = StackCString::<16>::new();
+/// path.push_str("/tmp/").unwrap();
+/// path.push_str("file").unwrap();
+/// assert_eq!(path.as_c_str(), c"/tmp/file");
+/// assert_eq!(path.push_str("a\0b"), Err(CPushError::Nul));
+/// assert_eq!(path.push_str("/too/long"), Err(CPushError::Full));
+///
+/// // Pass `path.as_c_ptr()` to C.
+/// ```
+///
+/// ## Compile-time errors
+/// `CAPACITY` must be at least 1.
+#[derive(Clone, Copy)]
+pub struct StackCString<const CAPACITY: usize> {
+    buf: [u8; CAPACITY],
+    len: usize,
+}
+impl<const CAPACITY: usize> StackCString<CAPACITY> {
+    const CHECK: () = assert!(CAPACITY > 0, "no space for the nul terminator");
+
+    /// Create an empty string.
+    pub const fn new() -> Self {
+        let () = Self::CHECK;
+        Self {
+            buf: [0; CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Get the length of this string in bytes, without the terminator.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if this string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get this string's capacity, including the terminator.
+    ///
+    /// Will always return the value provided as a generic argument.
+    pub const fn capacity(&self) -> usize {
+        CAPACITY
+    }
+
+    /// Append bytes.
+    ///
+    /// If `bytes` contain NUL or there isn't enough empty space in the
+    /// buffer, [CPushError] is returned and the string is not changed.
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), CPushError> {
+        if bytes.contains(&0) {
+            return Err(CPushError::Nul);
+        }
+        if bytes.len() >= CAPACITY - self.len {
+            return Err(CPushError::Full);
+        }
+        self.buf[self.len..][..bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+        self.buf[self.len] = 0;
+        Ok(())
+    }
+
+    /// Append an [str].
+    ///
+    /// See [StackCString::push_bytes].
+    pub fn push_str(&mut self, str: &str) -> Result<(), CPushError> {
+        self.push_bytes(str.as_bytes())
+    }
+
+    /// Append a [char].
+    ///
+    /// See [StackCString::push_bytes].
+    pub fn push(&mut self, char: char) -> Result<(), CPushError> {
+        self.push_str(char.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Remove all contents of this string.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.buf[0] = 0;
+    }
+
+    /// Get the bytes of this string, without the terminator.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Get the bytes of this string, including the terminator.
+    pub fn as_bytes_with_nul(&self) -> &[u8] {
+        &self.buf[..=self.len]
+    }
+
+    /// Get this string as a [CStr].
+    pub fn as_c_str(&self) -> &CStr {
+        unsafe { CStr::from_bytes_with_nul_unchecked(self.as_bytes_with_nul()) }
+    }
+
+    /// Get a pointer to pass to C APIs.
+    ///
+    /// The pointer is valid as long as this string is not moved or
+    /// modified.
+    pub fn as_c_ptr(&self) -> *const c_char {
+        self.buf.as_ptr() as *const c_char
+    }
+
+    /// Get this string as an [str], if it's valid UTF-8.
+    pub fn to_str(&self) -> Option<&str> {
+        str::from_utf8(self.as_bytes()).ok()
+    }
+}
+impl<const CAPACITY: usize> Default for StackCString<CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const CAPACITY: usize> Deref for StackCString<CAPACITY> {
+    type Target = CStr;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_c_str()
+    }
+}
+impl<const CAPACITY: usize> AsRef<CStr> for StackCString<CAPACITY> {
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+impl<const CAPACITY: usize> Borrow<CStr> for StackCString<CAPACITY> {
+    fn borrow(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+impl<const CAPACITY: usize> Hash for StackCString<CAPACITY> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_c_str().hash(state)
+    }
+}
+impl<const CAPACITY: usize> PartialEq for StackCString<CAPACITY> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+impl<const CAPACITY: usize> Eq for StackCString<CAPACITY> {}
+impl<const CAPACITY: usize> PartialEq<CStr> for StackCString<CAPACITY> {
+    fn eq(&self, other: &CStr) -> bool {
+        self.as_c_str() == other
+    }
+}
+impl<const CAPACITY: usize> PartialEq<&'_ CStr> for StackCString<CAPACITY> {
+    fn eq(&self, other: &&CStr) -> bool {
+        self.as_c_str() == *other
+    }
+}
+impl<const CAPACITY: usize> TryFrom<&'_ str> for StackCString<CAPACITY> {
+    type Error = CPushError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut st = Self::new();
+        st.push_str(value)?;
+        Ok(st)
+    }
+}
+impl<const CAPACITY: usize> TryFrom<&'_ [u8]> for StackCString<CAPACITY> {
+    type Error = CPushError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let mut st = Self::new();
+        st.push_bytes(value)?;
+        Ok(st)
+    }
+}
+impl<const CAPACITY: usize> TryFrom<&'_ CStr> for StackCString<CAPACITY> {
+    type Error = CPushError;
+
+    fn try_from(value: &CStr) -> Result<Self, Self::Error> {
+        let mut st = Self::new();
+        st.push_bytes(value.to_bytes())?;
+        Ok(st)
+    }
+}
+impl<const CAPACITY: usize> TryFrom<StackString<CAPACITY>> for StackCString<CAPACITY> {
+    type Error = CPushError;
+
+    fn try_from(value: StackString<CAPACITY>) -> Result<Self, Self::Error> {
+        let mut st = Self::new();
+        st.push_str(&value)?;
+        Ok(st)
     }
 }
-impl<const CAPACITY: usize> std::fmt::Display for StackString<CAPACITY> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(self.as_str(), f)
+impl<const CAPACITY: usize> fmt::Write for StackCString<CAPACITY> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
     }
 }
-impl<const CAPACITY: usize> std::fmt::Debug for StackString<CAPACITY> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(self.as_str(), f)
+impl<const CAPACITY: usize> fmt::Debug for StackCString<CAPACITY> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_c_str(), f)
     }
 }
@@ -0,0 +1,153 @@
+use std::io::{self, Read};
+
+/// Byte order of UTF-16 data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// An iterator over UTF-16 characters from a [Read].
+///
+/// Byte order is either selected explicitly, or detected from the byte
+/// order mark with [Utf16::new]. Unpaired surrogates and a trailing odd
+/// byte are replaced with [char::REPLACEMENT_CHARACTER].
+///
+/// ```
+/// use libcommons::str::utf16::{Endian, Utf16};
+/// use std::io::Cursor;
+///
+/// let le = b"\xFF\xFEH\0i\0\x3E\xD8\x80\xDD";
+/// let text: String = Utf16::new(Cursor::new(le)).map(|x| x.unwrap()).collect();
+/// assert_eq!(text, "Hi🦀");
+///
+/// let be = b"\0H\0i";
+/// let text: String = Utf16::with_endian(Cursor::new(be), Endian::Big)
+///     .map(|x| x.unwrap())
+///     .collect();
+/// assert_eq!(text, "Hi");
+/// ```
+pub struct Utf16<R: Read> {
+    read: R,
+    endian: Option<Endian>,
+    pending: Option<u16>,
+}
+impl<R: Read> Utf16<R> {
+    /// Create an iterator that detects byte order from the byte order mark.
+    ///
+    /// If there is no byte order mark, little endian is assumed.
+    pub fn new(read: R) -> Self {
+        Self {
+            read,
+            endian: None,
+            pending: None,
+        }
+    }
+
+    /// Create an iterator with a specific byte order.
+    ///
+    /// A byte order mark is not skipped and is returned as `U+FEFF`.
+    pub fn with_endian(read: R, endian: Endian) -> Self {
+        Self {
+            read,
+            endian: Some(endian),
+            pending: None,
+        }
+    }
+
+    /// Get byte order, if it's known.
+    pub fn endian(&self) -> Option<Endian> {
+        self.endian
+    }
+
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Read 2 bytes. A truncated pair is returned as `None` inside.
+    fn read_bytes(&mut self) -> io::Result<Option<Option<[u8; 2]>>> {
+        let mut buf = [0u8; 2];
+        let mut len = 0;
+        while len < 2 {
+            match self.read.read(&mut buf[len..]) {
+                Ok(0) if len == 0 => return Ok(None),
+                Ok(0) => return Ok(Some(None)),
+                Ok(x) => len += x,
+                Err(why) if why.kind() == io::ErrorKind::Interrupted => {}
+                Err(why) => return Err(why),
+            }
+        }
+        Ok(Some(Some(buf)))
+    }
+
+    fn read_unit(&mut self) -> io::Result<Option<u16>> {
+        if let Some(x) = self.pending.take() {
+            return Ok(Some(x));
+        }
+
+        let Some(bytes) = self.read_bytes()? else {
+            return Ok(None);
+        };
+        let endian = match self.endian {
+            Some(x) => x,
+            None => {
+                let endian = match bytes {
+                    Some([0xFE, 0xFF]) => Endian::Big,
+                    _ => Endian::Little,
+                };
+                self.endian = Some(endian);
+                if matches!(bytes, Some([0xFF, 0xFE] | [0xFE, 0xFF])) {
+                    return self.read_unit();
+                }
+                endian
+            }
+        };
+
+        Ok(Some(match (bytes, endian) {
+            (None, _) => 0xFFFD,
+            (Some(x), Endian::Little) => u16::from_le_bytes(x),
+            (Some(x), Endian::Big) => u16::from_be_bytes(x),
+        }))
+    }
+}
+impl<R: Read> Iterator for Utf16<R> {
+    type Item = io::Result<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let unit = match self.read_unit() {
+            Ok(x) => x?,
+            Err(why) => return Some(Err(why)),
+        };
+
+        match unit {
+            0xD800..=0xDBFF => {
+                let low = match self.read_unit() {
+                    Ok(x) => x,
+                    Err(why) => return Some(Err(why)),
+                };
+                match low {
+                    Some(low @ 0xDC00..=0xDFFF) => {
+                        let char = 0x10000 + ((unit as u32 - 0xD800) << 10) + (low as u32 - 0xDC00);
+                        Some(Ok(
+                            char::from_u32(char).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        ))
+                    }
+                    x => {
+                        self.pending = x;
+                        Some(Ok(char::REPLACEMENT_CHARACTER))
+                    }
+                }
+            }
+            x => Some(Ok(
+                char::from_u32(x as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+            )),
+        }
+    }
+}
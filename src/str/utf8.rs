@@ -1,4 +1,6 @@
-use std::io::Read;
+use core::{error::Error, fmt::Display};
+#[cfg(feature = "std")]
+use std::io::{self, Read, Seek, SeekFrom};
 
 /// An iterator over UTF-8 characters from a [Read].
 ///
@@ -20,7 +22,9 @@ use std::io::Read;
 /// of replacement characters where should not have been.
 ///
 /// Consider using [.pre::<4>()](crate::io::PreRead) to make sure it doesn't
-/// happen.
+/// happen, or [.retry_would_block()](crate::io::retry::RetryWouldBlock) to
+/// wait for data instead. If data arrives in chunks anyway, [Utf8Decoder]
+/// keeps partial sequences between them.
 ///
 /// ```
 /// use libcommons::prelude::*;
@@ -89,32 +93,179 @@ use std::io::Read;
 /// is highly discouraged as reading multi-byte characters
 /// requires 2 reads (one for first characted, and another
 /// for the rest).
-pub struct Utf8<R: Read>(R);
+#[cfg(feature = "std")]
+pub struct Utf8<R: Read> {
+    read: R,
+    offset: u64,
+    strict: bool,
+}
+#[cfg(feature = "std")]
 impl<R: Read> Utf8<R> {
     pub fn new(read: R) -> Self {
-        Self(read)
+        Self {
+            read,
+            offset: 0,
+            strict: false,
+        }
+    }
+
+    /// Return errors for malformed sequences instead of replacing them.
+    ///
+    /// Malformed, overlong and truncated sequences produce
+    /// [io::ErrorKind::InvalidData] with [InvalidUtf8] inside.
+    ///
+    /// ```
+    /// use libcommons::str::utf8::{InvalidUtf8, Utf8};
+    /// use std::io::{Cursor, ErrorKind};
+    ///
+    /// let mut chars = Utf8::new(Cursor::new(b"ok\xC0\x80")).strict();
+    /// assert_eq!(chars.next().unwrap().unwrap(), 'o');
+    /// assert_eq!(chars.next().unwrap().unwrap(), 'k');
+    ///
+    /// let err = chars.next().unwrap().unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidData);
+    /// let err = err.get_ref().unwrap().downcast_ref::<InvalidUtf8>().unwrap();
+    /// assert_eq!(err.offset, 2);
+    /// ```
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Check if this iterator is strict. See [Utf8::strict].
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Get number of bytes read so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Also yield byte offset of every character.
+    ///
+    /// ```
+    /// use libcommons::str::utf8::Utf8;
+    /// use std::io::Cursor;
+    ///
+    /// let chars: Vec<_> = Utf8::new(Cursor::new(b"a\xF0\x9F\xA6\x80\xEF\xBF\xBDb\xFFc"))
+    ///     .char_indices()
+    ///     .map(|x| x.unwrap())
+    ///     .collect();
+    /// assert_eq!(
+    ///     chars,
+    ///     [(0, 'a'), (1, '🦀'), (5, '\u{FFFD}'), (8, 'b'), (9, '\u{FFFD}'), (10, 'c')],
+    /// );
+    /// ```
+    pub fn char_indices(self) -> CharIndices<R> {
+        CharIndices(self)
+    }
+
+    /// Read characters into `buf` until `delim` or end of stream.
+    ///
+    /// The delimiter is included. Returns number of bytes appended, which
+    /// is 0 at the end of stream. On error, characters read so far stay
+    /// in `buf`.
+    ///
+    /// ```
+    /// use libcommons::str::utf8::Utf8;
+    /// use std::io::Cursor;
+    ///
+    /// let mut chars = Utf8::new(Cursor::new("key=value"));
+    /// let mut buf = String::new();
+    /// assert_eq!(chars.read_until('=', &mut buf).unwrap(), 4);
+    /// assert_eq!(buf, "key=");
+    /// assert_eq!(chars.read_until('=', &mut buf).unwrap(), 5);
+    /// assert_eq!(buf, "key=value");
+    /// assert_eq!(chars.read_until('=', &mut buf).unwrap(), 0);
+    /// ```
+    pub fn read_until(&mut self, delim: char, buf: &mut String) -> io::Result<usize> {
+        let len = buf.len();
+        for char in self.by_ref() {
+            let char = char?;
+            buf.push(char);
+            if char == delim {
+                break;
+            }
+        }
+        Ok(buf.len() - len)
+    }
+
+    /// Iterate over lines.
+    ///
+    /// Lines are split on `\n`, with `\r\n` also being stripped.
+    ///
+    /// ```
+    /// use libcommons::str::utf8::Utf8;
+    /// use std::io::Cursor;
+    ///
+    /// let lines: Vec<_> = Utf8::new(Cursor::new("one\r\ntwo\n\nthree"))
+    ///     .lines()
+    ///     .map(|x| x.unwrap())
+    ///     .collect();
+    /// assert_eq!(lines, ["one", "two", "", "three"]);
+    /// ```
+    pub fn lines(self) -> Lines<R> {
+        Lines(self.split('\n'))
+    }
+
+    /// Iterate over parts separated by `delim`.
+    ///
+    /// Like [std::io::BufRead::split], a delimiter at the end of stream
+    /// doesn't produce an empty part.
+    ///
+    /// ```
+    /// use libcommons::str::utf8::Utf8;
+    /// use std::io::Cursor;
+    ///
+    /// let words: Vec<_> = Utf8::new(Cursor::new("crab 🦀  rust "))
+    ///     .split(' ')
+    ///     .map(|x| x.unwrap())
+    ///     .collect();
+    /// assert_eq!(words, ["crab", "🦀", "", "rust"]);
+    /// ```
+    pub fn split(self, delim: char) -> Split<R> {
+        Split {
+            chars: self,
+            delim,
+            buf: String::new(),
+        }
     }
 
     pub fn into_inner(self) -> R {
-        self.0
+        self.read
     }
 
     pub fn inner(&self) -> &R {
-        &self.0
+        &self.read
     }
     pub fn inner_mut(&mut self) -> &mut R {
-        &mut self.0
+        &mut self.read
+    }
+
+    /// Handle a malformed sequence starting at `offset`.
+    fn invalid(&self, offset: u64) -> io::Result<char> {
+        if self.strict {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                InvalidUtf8 { offset },
+            ))
+        } else {
+            Ok(char::REPLACEMENT_CHARACTER)
+        }
     }
 }
+#[cfg(feature = "std")]
 impl<R: Read> Iterator for Utf8<R> {
-    type Item = std::io::Result<char>;
+    type Item = io::Result<char>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut buf = [0];
+        let start = self.offset;
+        let mut buf = [0; 4];
 
-        match self.0.read(&mut buf) {
+        match self.read.read(&mut buf[..1]) {
             Ok(0) => return None,
-            Ok(_) => (),
+            Ok(_) => self.offset += 1,
             Err(why) => return Some(Err(why)),
         }
         let char1 = buf[0];
@@ -126,30 +277,523 @@ impl<R: Read> Iterator for Utf8<R> {
         let ones = char1.leading_ones();
 
         let len = if !(2..=4).contains(&ones) {
-            return Some(Ok(char::REPLACEMENT_CHARACTER));
+            return Some(self.invalid(start));
         } else {
             ones as usize - 1
         };
 
-        let mut buf = [0; 3];
-        let buf = match self.0.read(&mut buf[..len]) {
-            Ok(0) => return None,
-            Ok(x) if x != len => return Some(Ok(char::REPLACEMENT_CHARACTER)),
-            Ok(x) => &buf[..x],
-            Err(why) => return Some(Err(why)),
-        };
+        let mut read = 0;
+        while read < len {
+            match self.read.read(&mut buf[1 + read..1 + len]) {
+                Ok(0) if self.strict => return Some(self.invalid(start)),
+                Ok(0) => return None,
+                Ok(x) => {
+                    read += x;
+                    self.offset += x as u64;
+                }
+                Err(why) => return Some(Err(why)),
+            }
+        }
 
         let mut final_char = char1 as u32 & (0b00111111 >> len);
-        for v in buf {
+        for v in &buf[1..=len] {
             if *v & 0b11000000 != 0b10000000 {
-                return Some(Ok(char::REPLACEMENT_CHARACTER));
+                return Some(self.invalid(start));
             }
             final_char <<= 6;
             final_char |= *v as u32 & 0b00111111;
         }
 
-        Some(Ok(
-            char::from_u32(final_char).unwrap_or(char::REPLACEMENT_CHARACTER)
-        ))
+        let min = [0x80, 0x800, 0x10000][len - 1];
+        match char::from_u32(final_char) {
+            Some(_) if self.strict && final_char < min => Some(self.invalid(start)),
+            Some(x) => Some(Ok(x)),
+            None => Some(self.invalid(start)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read + Seek> Utf8<R> {
+    /// Move back to the start of the character at the current position.
+    ///
+    /// Skips back over up to 3 continuation bytes. If there are more, the
+    /// sequence is malformed and position isn't changed. Afterwards,
+    /// [Utf8::offset] is the position in the stream.
+    ///
+    /// ```
+    /// use libcommons::str::utf8::Utf8;
+    /// use std::io::{Cursor, Seek, SeekFrom};
+    ///
+    /// let mut chars = Utf8::new(Cursor::new("a🦀b"));
+    /// chars.inner_mut().seek(SeekFrom::Start(3)).unwrap();
+    /// assert_eq!(chars.seek_to_char_boundary().unwrap(), 1);
+    /// assert_eq!(chars.next().unwrap().unwrap(), '🦀');
+    /// ```
+    pub fn seek_to_char_boundary(&mut self) -> io::Result<u64> {
+        let start = self.read.stream_position()?;
+        let mut pos = start;
+        let mut byte = [0];
+        for _ in 0..4 {
+            if self.read.read(&mut byte)? == 0 || byte[0] & 0b11000000 != 0b10000000 {
+                self.offset = self.read.seek(SeekFrom::Start(pos))?;
+                return Ok(self.offset);
+            }
+            if pos == 0 {
+                break;
+            }
+            pos -= 1;
+            self.read.seek(SeekFrom::Start(pos))?;
+        }
+        self.offset = self.read.seek(SeekFrom::Start(start))?;
+        Ok(self.offset)
+    }
+
+    /// Iterate over characters backwards, starting from the end of stream.
+    ///
+    /// Malformed sequences are replaced with [char::REPLACEMENT_CHARACTER]
+    /// byte by byte, regardless of [Utf8::strict].
+    ///
+    /// ```
+    /// use libcommons::str::utf8::Utf8;
+    /// use std::io::Cursor;
+    ///
+    /// let log = "first\nsecond\nthird 🦀\n";
+    /// let mut tail: Vec<char> = Utf8::new(Cursor::new(log))
+    ///     .rev()
+    ///     .map(|x| x.unwrap())
+    ///     .skip(1)
+    ///     .take_while(|x| *x != '\n')
+    ///     .collect();
+    /// tail.reverse();
+    /// assert_eq!(String::from_iter(tail), "third 🦀");
+    /// ```
+    pub fn rev(self) -> Utf8Rev<R> {
+        Utf8Rev {
+            read: self.read,
+            pos: None,
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// An iterator over UTF-8 characters and their byte offsets.
+///
+/// Created with [Utf8::char_indices].
+#[cfg(feature = "std")]
+pub struct CharIndices<R: Read>(Utf8<R>);
+#[cfg(feature = "std")]
+impl<R: Read> CharIndices<R> {
+    pub fn into_inner(self) -> Utf8<R> {
+        self.0
+    }
+
+    pub fn inner(&self) -> &Utf8<R> {
+        &self.0
+    }
+    pub fn inner_mut(&mut self) -> &mut Utf8<R> {
+        &mut self.0
+    }
+}
+#[cfg(feature = "std")]
+impl<R: Read> Iterator for CharIndices<R> {
+    type Item = io::Result<(u64, char)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.0.offset;
+        self.0.next().map(|x| x.map(|x| (offset, x)))
+    }
+}
+
+/// An iterator over parts of a UTF-8 stream.
+///
+/// Created with [Utf8::split].
+///
+/// ## Errors
+/// If reading fails, the error is returned and the part read so far is
+/// kept, so iteration can continue after errors like
+/// [io::ErrorKind::WouldBlock].
+#[cfg(feature = "std")]
+pub struct Split<R: Read> {
+    chars: Utf8<R>,
+    delim: char,
+    buf: String,
+}
+#[cfg(feature = "std")]
+impl<R: Read> Split<R> {
+    pub fn into_inner(self) -> Utf8<R> {
+        self.chars
+    }
+
+    pub fn inner(&self) -> &Utf8<R> {
+        &self.chars
+    }
+    pub fn inner_mut(&mut self) -> &mut Utf8<R> {
+        &mut self.chars
+    }
+
+    /// Get the next part and whether it was terminated by the delimiter.
+    fn next_part(&mut self) -> Option<io::Result<(String, bool)>> {
+        if let Err(why) = self.chars.read_until(self.delim, &mut self.buf) {
+            return Some(Err(why));
+        }
+        if self.buf.is_empty() {
+            return None;
+        }
+        let mut part = core::mem::take(&mut self.buf);
+        let terminated = part.ends_with(self.delim);
+        if terminated {
+            part.pop();
+        }
+        Some(Ok((part, terminated)))
+    }
+}
+#[cfg(feature = "std")]
+impl<R: Read> Iterator for Split<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_part().map(|x| x.map(|(x, _)| x))
+    }
+}
+
+/// An iterator over lines of a UTF-8 stream.
+///
+/// Created with [Utf8::lines]. Errors are handled like in [Split].
+#[cfg(feature = "std")]
+pub struct Lines<R: Read>(Split<R>);
+#[cfg(feature = "std")]
+impl<R: Read> Lines<R> {
+    pub fn into_inner(self) -> Utf8<R> {
+        self.0.into_inner()
+    }
+
+    pub fn inner(&self) -> &Utf8<R> {
+        self.0.inner()
+    }
+    pub fn inner_mut(&mut self) -> &mut Utf8<R> {
+        self.0.inner_mut()
+    }
+}
+#[cfg(feature = "std")]
+impl<R: Read> Iterator for Lines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_part().map(|x| {
+            x.map(|(mut x, terminated)| {
+                if terminated && x.ends_with('\r') {
+                    x.pop();
+                }
+                x
+            })
+        })
+    }
+}
+
+/// A backwards iterator over UTF-8 characters from a [Read] + [Seek].
+///
+/// Created with [Utf8::rev].
+#[cfg(feature = "std")]
+pub struct Utf8Rev<R: Read + Seek> {
+    read: R,
+    /// Stream position of the start of `buf`, [None] before the first read.
+    pos: Option<u64>,
+    buf: Vec<u8>,
+}
+#[cfg(feature = "std")]
+impl<R: Read + Seek> Utf8Rev<R> {
+    /// Get byte offset of the last returned character.
+    ///
+    /// Returns [None] before the first call to [Iterator::next].
+    pub fn offset(&self) -> Option<u64> {
+        self.pos.map(|x| x + self.buf.len() as u64)
+    }
+
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.read
+    }
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.read
+    }
+
+    /// Read a chunk before `buf`.
+    fn fill(&mut self) -> io::Result<()> {
+        let pos = match self.pos {
+            Some(x) => x,
+            None => self.read.seek(SeekFrom::End(0))?,
+        };
+        let len = pos.min(4 * 1024);
+        let mut buf = vec![0; len as usize];
+        self.read.seek(SeekFrom::Start(pos - len))?;
+        self.read.read_exact(&mut buf)?;
+        buf.extend_from_slice(&self.buf);
+        self.buf = buf;
+        self.pos = Some(pos - len);
+        Ok(())
+    }
+}
+#[cfg(feature = "std")]
+impl<R: Read + Seek> Iterator for Utf8Rev<R> {
+    type Item = io::Result<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.len() < 4
+            && self.pos != Some(0)
+            && let Err(why) = self.fill()
+        {
+            return Some(Err(why));
+        }
+        let len = self.buf.len();
+        if len == 0 {
+            return None;
+        }
+
+        let start = (len.saturating_sub(4)..len)
+            .rev()
+            .find(|x| self.buf[*x] & 0b11000000 != 0b10000000)
+            .unwrap_or(len - 1);
+        let char = str::from_utf8(&self.buf[start..])
+            .ok()
+            .and_then(|x| x.chars().next());
+        match char {
+            Some(_) => self.buf.truncate(start),
+            None => self.buf.truncate(len - 1),
+        }
+        Some(Ok(char.unwrap_or(char::REPLACEMENT_CHARACTER)))
+    }
+}
+
+/// A malformed UTF-8 sequence, returned by strict [Utf8].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidUtf8 {
+    /// Byte offset of the start of the sequence.
+    pub offset: u64,
+}
+impl Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid utf-8 sequence at byte {}", self.offset)
+    }
+}
+impl Error for InvalidUtf8 {}
+
+/// Result of [validate_utf8].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Number of bytes in the stream.
+    pub len: u64,
+    /// Number of valid characters.
+    pub chars: u64,
+    /// Number of malformed sequences.
+    pub invalid: u64,
+    /// Byte offset of the first malformed sequence.
+    pub first_invalid: Option<u64>,
+}
+#[cfg(feature = "std")]
+impl ValidationReport {
+    /// Check if the whole stream was valid UTF-8.
+    pub fn is_valid(&self) -> bool {
+        self.invalid == 0
+    }
+
+    fn mark_invalid(&mut self, offset: u64) {
+        self.invalid += 1;
+        self.first_invalid.get_or_insert(offset);
+    }
+}
+
+/// Check if a stream is valid UTF-8 without decoding it.
+///
+/// The whole stream is read in chunks. A sequence cut off by the end of
+/// stream counts as malformed.
+///
+/// ```
+/// use libcommons::str::validate_utf8;
+/// use std::io::Cursor;
+///
+/// let report = validate_utf8(Cursor::new("crab: 🦀")).unwrap();
+/// assert!(report.is_valid());
+/// assert_eq!((report.len, report.chars), (10, 7));
+///
+/// let report = validate_utf8(Cursor::new(b"ok\xFF\xC0\x80!\xF0\x9F")).unwrap();
+/// assert_eq!(report.first_invalid, Some(2));
+/// assert_eq!(report.invalid, 4);
+/// ```
+///
+/// ## Errors
+/// Errors from the reader are returned, except for
+/// [io::ErrorKind::Interrupted], which is retried.
+#[cfg(feature = "std")]
+pub fn validate_utf8<R: Read>(mut read: R) -> io::Result<ValidationReport> {
+    let mut report = ValidationReport::default();
+    let mut buf = [0; 8 * 1024];
+    // Bytes of an incomplete sequence at the end of the previous chunk.
+    let mut carry = 0;
+
+    loop {
+        let len = match read.read(&mut buf[carry..]) {
+            Ok(0) => {
+                if carry != 0 {
+                    let offset = report.len - carry as u64;
+                    report.mark_invalid(offset);
+                }
+                return Ok(report);
+            }
+            Ok(x) => x,
+            Err(why) if why.kind() == io::ErrorKind::Interrupted => continue,
+            Err(why) => return Err(why),
+        };
+        let base = report.len - carry as u64;
+        report.len += len as u64;
+
+        let len = carry + len;
+        let mut pos = 0;
+        carry = 0;
+        while pos < len {
+            match str::from_utf8(&buf[pos..len]) {
+                Ok(x) => {
+                    report.chars += x.chars().count() as u64;
+                    break;
+                }
+                Err(why) => {
+                    let valid = why.valid_up_to();
+                    let chars = unsafe { str::from_utf8_unchecked(&buf[pos..pos + valid]) };
+                    report.chars += chars.chars().count() as u64;
+                    pos += valid;
+                    match why.error_len() {
+                        Some(x) => {
+                            report.mark_invalid(base + pos as u64);
+                            pos += x;
+                        }
+                        None => {
+                            buf.copy_within(pos..len, 0);
+                            carry = len - pos;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A push-based UTF-8 decoder.
+///
+/// Unlike [Utf8], this doesn't read data itself. Bytes are pushed as they
+/// arrive, and incomplete sequences are kept until the rest is pushed. This
+/// makes it suitable for non-blocking, async and packet-based sources.
+///
+/// Malformed sequences are replaced with [char::REPLACEMENT_CHARACTER].
+///
+/// ```
+/// use libcommons::str::utf8::Utf8Decoder;
+///
+/// let mut decoder = Utf8Decoder::new();
+/// let mut text = String::new();
+/// text.extend(decoder.push(b"crab: \xF0\x9F"));
+/// assert_eq!(text, "crab: ");
+/// text.extend(decoder.push(b"\xA6\x80!\xE2"));
+/// assert_eq!(text, "crab: 🦀!");
+/// text.extend(decoder.finish());
+/// assert_eq!(text, "crab: 🦀!\u{FFFD}");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Utf8Decoder {
+    buf: [u8; 4],
+    len: usize,
+    need: usize,
+}
+impl Utf8Decoder {
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; 4],
+            len: 0,
+            need: 0,
+        }
+    }
+
+    /// Decode pushed bytes.
+    ///
+    /// Bytes are decoded as the returned iterator advances, so it should be
+    /// consumed fully. Bytes it didn't get to are lost.
+    pub fn push<'a>(&'a mut self, bytes: &'a [u8]) -> Utf8Decode<'a> {
+        Utf8Decode {
+            decoder: self,
+            bytes,
+        }
+    }
+
+    /// Check if there is an incomplete sequence waiting for more bytes.
+    pub fn has_pending(&self) -> bool {
+        self.len != 0
+    }
+
+    /// End the input.
+    ///
+    /// Returns [char::REPLACEMENT_CHARACTER] if there was an incomplete
+    /// sequence. The decoder can be reused after this.
+    pub fn finish(&mut self) -> Option<char> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len = 0;
+        Some(char::REPLACEMENT_CHARACTER)
+    }
+}
+
+/// An iterator over characters decoded from pushed bytes.
+///
+/// Created with [Utf8Decoder::push].
+pub struct Utf8Decode<'a> {
+    decoder: &'a mut Utf8Decoder,
+    bytes: &'a [u8],
+}
+impl Iterator for Utf8Decode<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let decoder = &mut *self.decoder;
+        while let Some((&byte, rest)) = self.bytes.split_first() {
+            if decoder.len == 0 {
+                self.bytes = rest;
+                if byte.is_ascii() {
+                    return Some(byte as char);
+                }
+                match byte.leading_ones() {
+                    x @ 2..=4 => {
+                        decoder.buf[0] = byte;
+                        decoder.len = 1;
+                        decoder.need = x as usize;
+                    }
+                    _ => return Some(char::REPLACEMENT_CHARACTER),
+                }
+                continue;
+            }
+
+            if byte & 0b11000000 != 0b10000000 {
+                // Not consumed, so it starts the next sequence.
+                decoder.len = 0;
+                return Some(char::REPLACEMENT_CHARACTER);
+            }
+
+            self.bytes = rest;
+            decoder.buf[decoder.len] = byte;
+            decoder.len += 1;
+            if decoder.len == decoder.need {
+                decoder.len = 0;
+                return Some(
+                    str::from_utf8(&decoder.buf[..decoder.need])
+                        .ok()
+                        .and_then(|x| x.chars().next())
+                        .unwrap_or(char::REPLACEMENT_CHARACTER),
+                );
+            }
+        }
+        None
     }
 }
@@ -312,7 +312,7 @@ impl<T> Fun for T {
     fn drop(self) {}
 
     fn forget(self) {
-        std::mem::forget(self);
+        core::mem::forget(self);
     }
 }
 
@@ -348,7 +348,7 @@ pub trait ResultExt<T, E> {
         F: FnMut(&E) -> Option<E>;
 }
 #[cfg(feature = "extra_traits")]
-impl<T, E> ResultExt<T, E> for std::result::Result<T, E> {
+impl<T, E> ResultExt<T, E> for core::result::Result<T, E> {
     fn inflate<F>(self, mut filter: F) -> Result<Result<T, E>, E>
     where
         F: FnMut(&E) -> Option<E>,
@@ -367,8 +367,8 @@ impl<T, E> ResultExt<T, E> for std::result::Result<T, E> {
 }
 
 #[cfg(feature = "result")]
-pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+pub type BoxError = alloc::boxed::Box<dyn core::error::Error + Send + Sync + 'static>;
 #[cfg(feature = "result")]
-pub type Result<T = (), E = BoxError> = std::result::Result<T, E>;
+pub type Result<T = (), E = BoxError> = core::result::Result<T, E>;
 #[cfg(feature = "result")]
 pub const K: Result = Ok(());
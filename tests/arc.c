@@ -0,0 +1,31 @@
+#include <assert.h>
+#define LIBCOMMONS_PREFIX
+#define LIBCOMMONS_IMPLEMENTATION
+
+#include "../include/libcommons.h"
+
+static int owners = 1;
+
+void clone_str(const ffi_arc_str_t *self) { owners++; }
+
+void release_str(ffi_arc_str_t *self) { owners--; }
+
+int main() {
+    ffi_arc_str_t config = {
+        .buf = "verbose",
+        .len = 7,
+        .clone = &clone_str,
+        .release = &release_str,
+    };
+
+    ffi_arc_str_t copy = ffi_arc_str_clone(&config);
+    assert(owners == 2);
+    assert(ffi_str_eq(ffi_arc_str_slice(&copy), ffi_str_new("verbose")));
+
+    ffi_arc_str_release(&copy);
+    assert(owners == 1);
+    assert(copy.buf == NULL);
+
+    ffi_arc_str_release(&config);
+    assert(owners == 0);
+}
@@ -0,0 +1,25 @@
+#include <assert.h>
+#define LIBCOMMONS_PREFIX
+#define LIBCOMMONS_IMPLEMENTATION
+
+#include "../include/libcommons.h"
+
+static int dropped = 0;
+
+void drop_int(void *ptr) {
+    dropped += *(int *)ptr;
+    free(ptr);
+}
+
+int main() {
+    int *value = (int *)malloc(sizeof(int));
+    *value = 5;
+    ffi_box_t b = {.ptr = value, .drop = &drop_int};
+
+    ffi_box_free(&b);
+    assert(dropped == 5);
+    assert(b.ptr == NULL);
+
+    ffi_box_free(&b);
+    assert(dropped == 5);
+}
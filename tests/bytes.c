@@ -0,0 +1,30 @@
+#include <assert.h>
+#define LIBCOMMONS_PREFIX
+#define LIBCOMMONS_IMPLEMENTATION
+
+#include "../include/libcommons.h"
+
+int main() {
+    uint8_t data[] = {0xde, 0xad, 0x00, 0xef};
+    ffi_bytes_t b = ffi_bytes_new(data, 4);
+    assert(b.len == 4);
+    assert(b.buf[2] == 0x00);
+    assert(b.buf[3] == 0xef);
+
+    ffi_slice_p slice = ffi_bytes_slice(&b);
+    assert(slice.len == 4);
+    assert(((uint8_t *)slice.buf)[0] == 0xde);
+
+    ffi_bytes_free(&b);
+    assert(b.buf == NULL);
+    assert(b.len == 0);
+
+    ffi_bytes_t empty = ffi_bytes_new(NULL, 0);
+    assert(empty.buf == NULL);
+    ffi_bytes_free(&empty);
+
+    ffi_bytes_t huge = ffi_bytes_new(data, UINTPTR_MAX);
+    assert(huge.buf == NULL);
+    assert(huge.len == 0);
+    ffi_bytes_free(&huge);
+}
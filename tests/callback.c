@@ -0,0 +1,33 @@
+#include <assert.h>
+#define LIBCOMMONS_PREFIX
+#define LIBCOMMONS_IMPLEMENTATION
+
+#include "../include/libcommons.h"
+
+typedef LIBCOMMONS_FFI_CALLBACK(int, (void *, int)) int_callback_t;
+
+static int dropped = 0;
+
+int add(void *context, int x) {
+    int *total = (int *)context;
+    *total += x;
+    return *total;
+}
+
+void drop_total(void *context) { dropped = *(int *)context; }
+
+int main() {
+    int total = 0;
+    int_callback_t callback = {
+        .call = &add,
+        .context = &total,
+        .drop = &drop_total,
+    };
+
+    assert(callback.call(callback.context, 2) == 2);
+    assert(callback.call(callback.context, 3) == 5);
+
+    LIBCOMMONS_FFI_CALLBACK_FREE(&callback);
+    assert(dropped == 5);
+    assert(callback.call == NULL);
+}
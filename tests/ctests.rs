@@ -48,7 +48,7 @@ fn compile(test: &str) {
 }
 
 macro_rules! ctests {
-    ($($name:ident)*, $(,)?) => {$(
+    ($($name:ident),* $(,)?) => {$(
         #[test]
         fn $name() {
             compile(stringify!($name));
@@ -57,5 +57,13 @@ macro_rules! ctests {
 }
 
 ctests! {
+    arc,
+    boxed,
+    bytes,
+    callback,
+    map,
+    option,
     str,
+    vec,
+    wide,
 }
@@ -0,0 +1,30 @@
+#include <assert.h>
+#define LIBCOMMONS_PREFIX
+#define LIBCOMMONS_IMPLEMENTATION
+
+#include "../include/libcommons.h"
+
+int main() {
+    ffi_map_entry_t entries[] = {
+        {.key = ffi_string_new("name"), .value = ffi_string_new("demo")},
+        {.key = ffi_string_new("verbose"), .value = ffi_string_new("true")},
+    };
+    ffi_map_t map = {
+        .buf = entries,
+        .len = 2,
+        .capacity = 2,
+        .drop = NULL,
+    };
+
+    const ffi_string_t *value = ffi_map_get(&map, ffi_str_new("verbose"));
+    assert(value != NULL);
+    assert(value->len == 4);
+    assert(ffi_map_get(&map, ffi_str_new("missing")) == NULL);
+
+    ffi_map_free(&map);
+    assert(map.buf == NULL);
+    for (int i = 0; i < 2; i++) {
+        ffi_string_free(&entries[i].key);
+        ffi_string_free(&entries[i].value);
+    }
+}
@@ -0,0 +1,24 @@
+#include <assert.h>
+#include <stddef.h>
+#define LIBCOMMONS_PREFIX
+#define LIBCOMMONS_IMPLEMENTATION
+
+#include "../include/libcommons.h"
+
+typedef LIBCOMMONS_FFI_OPTION(uint32_t) option_u32_t;
+typedef LIBCOMMONS_FFI_RESULT(uint64_t, int32_t) result_u64_t;
+
+int main() {
+    // Must match `FfiOption<u32>` and `FfiResult<u64, i32>`.
+    assert(sizeof(option_u32_t) == 8);
+    assert(offsetof(option_u32_t, value) == 4);
+    assert(sizeof(result_u64_t) == 16);
+    assert(offsetof(result_u64_t, ok) == 8);
+    assert(offsetof(result_u64_t, err) == 8);
+
+    option_u32_t some = {.is_some = 1, .value = 5};
+    assert(some.is_some && some.value == 5);
+
+    result_u64_t err = {.is_err = 1, .err = -1};
+    assert(err.is_err && err.err == -1);
+}
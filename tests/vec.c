@@ -0,0 +1,24 @@
+#include <assert.h>
+#define LIBCOMMONS_PREFIX
+#define LIBCOMMONS_IMPLEMENTATION
+
+#include "../include/libcommons.h"
+
+int main() {
+    int items[] = {1, 2, 3};
+    ffi_vec_t v = ffi_vec_new(items, 3, sizeof(int));
+    assert(v.len == 3);
+    assert(((int *)v.buf)[2] == 3);
+
+    ffi_slice_p slice = ffi_vec_slice(&v);
+    assert(slice.len == 3);
+    assert(((int *)slice.buf)[0] == 1);
+
+    ffi_vec_free(&v);
+    assert(v.buf == NULL);
+    assert(v.len == 0);
+
+    ffi_vec_t empty = ffi_vec_new(NULL, 0, sizeof(int));
+    assert(empty.buf == NULL);
+    ffi_vec_free(&empty);
+}
@@ -0,0 +1,29 @@
+#include <assert.h>
+#define LIBCOMMONS_PREFIX
+#define LIBCOMMONS_IMPLEMENTATION
+
+#include "../include/libcommons.h"
+
+int main() {
+    uint16_t data[] = {'h', 0xe9, 'l', 'l', 'o'};
+    ffi_wide_string_t s = ffi_wide_string_new(data, 5);
+    assert(s.len == 5);
+    assert(s.buf[1] == 0xe9);
+
+    ffi_wide_str_p slice = ffi_wide_string_slice(&s);
+    assert(slice.len == 5);
+    assert(slice.buf[4] == 'o');
+
+    ffi_wide_string_free(&s);
+    assert(s.buf == NULL);
+    assert(s.len == 0);
+
+    ffi_wide_string_t empty = ffi_wide_string_new(NULL, 0);
+    assert(empty.buf == NULL);
+    ffi_wide_string_free(&empty);
+
+    ffi_wide_string_t overflow = ffi_wide_string_new(data, UINTPTR_MAX);
+    assert(overflow.buf == NULL);
+    assert(overflow.len == 0);
+    ffi_wide_string_free(&overflow);
+}